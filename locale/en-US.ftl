@@ -5,14 +5,62 @@ view_main_open_dir = Open a directory
 view_main_open_receipt = Open a notice of receipt
 view_main_calc_fingerprints = Checksum calculation
 view_main_check_fingerprints = Data integrity check
+view_main_update_ctn_file = Update the content file
+view_main_select_ctn_file_dir = Write the content file in another directory
+view_main_case_title = Case information
+view_main_case_id = Case ID
+view_main_case_organisation = Organisation
+view_main_case_operator = Operator
+view_main_case_notes = Notes
+view_main_unlock = Remove the protection
+view_main_unlock_title = Protection
+view_main_unlock_ok =
+    { $nb ->
+        [one] The original permissions of one file have been restored.
+        *[other] The original permissions of { $nb } files and directories have been restored.
+    }
+view_main_unlock_err = Unable to remove the protection: { $msg }
+view_main_ctn_file_location = Content file: { $path }
+view_main_receipt_err_title = Unable to load the notice of receipt
+view_main_receipt_err_format = Most likely format: { $format }
+view_main_receipt_err_position = Line { $line }, column { $column }: { $reason }
+view_main_receipt_err_line = Line { $line }: { $reason }
+view_main_receipt_err_tried = Formats tried: { $formats }
+view_main_receipt_warnings_title = Notice of receipt
+view_main_ctn_file_warnings_title = Content file
+view_main_parse_warnings_text =
+    { $nb ->
+        [one] One line has been ignored because it is malformed:
+        *[other] { $nb } lines have been ignored because they are malformed:
+    }
 view_main_check_result_title = Data integrity check result
 view_main_check_result_ok_text = Data integrity check passed.
+view_main_merkle_root = Evidence set fingerprint (Merkle root, { $hash_func }): { $root }
 view_main_check_result_err_text = Data integrity check failed.
 view_main_check_result_err_link = See errors
+view_main_check_result_renamed_text =
+    { $nb ->
+        [one] One file listed in the notice of receipt has been found on disk under another name.
+        *[other] { $nb } files listed in the notice of receipt have been found on disk under another name.
+    }
+view_main_check_result_renamed_link = See renamed files
+view_main_receipt_coverage_title = Notices of receipt
+view_main_receipt_coverage_text = { $nb } of { $total } files are listed in the notices of receipt.
+view_main_receipt_coverage_link = See which files are covered
 view_main_clipboard_btn_list = Copy list
 view_main_clipboard_btn_file = Copy content file
 
 cpn_file_list_delete = Reset
+cpn_file_list_receipt_format = Detected format: { $format } (confidence: { $confidence } %)
+cpn_file_list_receipt_format_forced = Format: { $format } (manually selected)
+cpn_file_list_receipt_other_formats = Also recognized as: { $formats }
+cpn_file_list_receipt_candidate = { $format } ({ $confidence } %)
+cpn_file_list_receipt_format_select = Format used to read the notice of receipt
+cpn_file_list_receipt_format_auto = Automatic detection
+cpn_file_list_receipt_path_matching = File path matching
+cpn_file_list_receipt_path_separators = Consider / and \ as equivalent path separators
+cpn_file_list_receipt_path_unicode = Ignore Unicode normalization differences (NFC and NFD)
+cpn_file_list_receipt_path_case = Ignore case differences
 
 cpn_progress_bar_status = Progress: { $done } / { $total } ({ $percent } %)
 
@@ -47,15 +95,89 @@ cpn_notif_excluded_files_link = { cpn_notif_empty_files_link }
 cpn_notif_duplicated_files_title = Duplicated files detected
 cpn_notif_duplicated_files_text = Several files located in the selected directory are identical.
 cpn_notif_duplicated_files_link = See list
+cpn_notif_previous_evidence_title =
+    { $nb ->
+        [one] File already received
+        *[other] Files already received
+    }
+cpn_notif_previous_evidence_text =
+    { $nb ->
+        [one] A file has already been received in another case.
+        *[other] { $nb } files have already been received in other cases.
+    }
+cpn_notif_unprotected_files_title =
+    { $nb ->
+        [one] Protection removed
+        *[other] Protections removed
+    }
+cpn_notif_unprotected_files_text =
+    { $nb ->
+        [one] The following file or directory is no longer read-only since the content file has been written:
+        *[other] The following { $nb } files or directories are no longer read-only since the content file has been written:
+    }
+cpn_notif_case_write_error_title = Case metadata not saved
+cpn_notif_case_write_error_text = The case metadata could not be written next to the content file: { $msg }. If the directory is protected, remove the protection and calculate the fingerprints again.
+cpn_notif_protection_error_title = Content file not protected
+cpn_notif_protection_error_text = The content file has been written but could not be set as read-only: { $msg }.
+cpn_notif_metadata_changes_title = Metadata changed
+cpn_notif_metadata_changes_text =
+    { $nb ->
+        [one] A metadata change has been detected since the content file has been written.
+        *[other] { $nb } metadata changes have been detected since the content file has been written.
+    }
+cpn_notif_metadata_changes_link = Show the changes
+cpn_notif_known_bad_files_title =
+    { $nb ->
+        [one] Known-bad file detected
+        *[other] Known-bad files detected
+    }
+cpn_notif_known_bad_files_text =
+    { $nb ->
+        [one] A file matches a known-bad hash set.
+        *[other] { $nb } files match a known-bad hash set.
+    }
+cpn_notif_known_good_files_title =
+    { $nb ->
+        [one] Known file
+        *[other] Known files
+    }
+cpn_notif_known_good_files_text =
+    { $nb ->
+        [one] A file matches a known-good hash set and may be ignored during the review.
+        *[other] { $nb } files match a known-good hash set and may be ignored during the review.
+    }
+cpn_notif_known_files_link = See list
 
 ## Check errors view
 
 view_check_errors_title = Verification errors
 view_check_errors_ctn_file_parse = Unable to parse the content file.
+view_check_errors_ctn_file_trailer = The content file has been modified or truncated since it was written: its self-verification digest does not match.
+view_check_errors_ctn_file_merkle_root = The evidence set fingerprint (Merkle root) does not match the one computed from the content file.
 view_check_errors_ctn_file_missing = File is listed in the content file but does not exists on disk.
 view_check_errors_ctn_file_match = File differs from the content file.
 view_check_errors_receipt_missing = File is listed in the receipt but does not exists on disk.
 view_check_errors_receipt_match = File differs from the receipt.
+view_check_errors_receipt_conflict = File is listed with different fingerprints in several notices of receipt.
+view_check_errors_receipt_not_checked = The fingerprints required by this notice of receipt could not be calculated: it has not been checked.
+view_check_errors_path_collision = Another file on disk has the same path once case, separators and Unicode normalization are ignored: only one of them has been checked.
+view_check_errors_renamed_title = Renamed files
+view_check_errors_renamed_intro = The following files listed in the notice of receipt have not been found under the same name, but a file with the same fingerprint exists on disk. The platform may have renamed them when they were uploaded.
+view_check_errors_renamed_local = Found on disk as: { $path }
+
+## Receipt coverage view
+
+view_receipt_coverage_title = Notices of receipt coverage
+view_receipt_coverage_uncovered =
+    { $nb ->
+        [one] One file is not listed in any notice of receipt
+        *[other] { $nb } files are not listed in any notice of receipt
+    }
+view_receipt_coverage_covered =
+    { $nb ->
+        [one] One file is listed in the following notices of receipt
+        *[other] { $nb } files are listed in the following notices of receipt
+    }
 
 ## Empty files view
 
@@ -72,6 +194,249 @@ view_excluded_files_title = { cpn_notif_excluded_files_title }
 ## Duplicated files view
 
 view_duplicated_files_title = Duplicated files
+view_duplicated_files_hide_known_good = Hide the files matching a known-good hash set
+view_duplicated_files_previous_title = Files already received in other cases
+view_duplicated_files_previous_file = File
+view_duplicated_files_previous_case = Earlier case
+view_duplicated_files_previous_path = Earlier path
+view_duplicated_files_previous_date = Date
+
+## Known files view
+
+view_known_files_title = Known files
+view_known_files_filter = Display
+view_known_files_filter_all = All known files
+view_known_files_filter_bad = Known-bad files only
+view_known_files_filter_good = Known-good files only
+view_known_files_bad = Known-bad ({ $set })
+view_known_files_good = Known-good ({ $set })
+
+## Metadata changes view
+
+view_metadata_changes_title = Metadata changes
+view_metadata_changes_intro = The following metadata differ from the ones recorded when the content file has been written. They do not affect the content of the files, which is checked separately.
+view_metadata_changes_file = File
+view_metadata_changes_field = Metadata
+view_metadata_changes_recorded = Recorded
+view_metadata_changes_current = Current
+view_metadata_changes_field_modified = Modification date
+view_metadata_changes_field_changed = Change date
+view_metadata_changes_field_created = Creation date
+view_metadata_changes_field_mode = Permissions
+view_metadata_changes_field_owner = Owner
+view_metadata_changes_field_xattrs = Extended attributes
+
+## Content file update view
+
+view_update_title = Content file update
+view_update_intro = The following changes have been detected since the content file was written. Once confirmed, the previous content file will be archived next to the new one.
+view_update_added = New files
+view_update_modified = Modified files
+view_update_removed = Removed files
+view_update_confirm = Update the content file
+view_update_no_change = No change has been detected since the content file was written.
+view_update_err_archive_exists = A file already exists: { $path }
+view_update_err_io = Unable to update the content file: { $msg }
+view_update_err_parse = Unable to parse the existing content file.
+
+## Receipt formats
+
+receipt_format_cksum_bsd = cksum (BSD)
+receipt_format_cksum_gnu = cksum (GNU)
+receipt_format_cnil_content_file = CNIL content file
+receipt_format_cnil_platform_email_v3 = CNIL platform email (version 3)
+receipt_format_cnil_platform_email_v2 = CNIL platform email (version 2)
+receipt_format_cnil_platform_email_v1 = CNIL platform email (version 1)
+receipt_format_pdf = PDF document
+receipt_format_custom = { $name }
+
+## Parse errors
+
+parse_err_reason_io = the file could not be read
+parse_err_reason_encoding = the file is not valid UTF-8
+parse_err_reason_trailer = the self-verification digest is invalid
+parse_err_reason_missing_header = the header is missing
+parse_err_reason_header = the header is invalid
+parse_err_reason_line = the line is invalid
+parse_err_reason_message = the file is not a valid email
+parse_err_reason_document = the file is not a valid PDF document
+parse_err_reason_grammar = invalid format definition: the regular expression must be valid and capture both `path` and `hash`
+parse_err_reason_no_file = no file has been found
+
+## Tools view
+
+view_tools_title = Tools
+view_tools_convert_ctn_file = Convert a content file
+view_tools_quick_verify = Verify a file or a fingerprint
+view_tools_compare = Compare two directories or content files
+view_tools_audit_log = Audit log
+view_tools_copy = Copy and fingerprint
+
+## Content file conversion view
+
+view_convert_title = Content file conversion
+view_convert_intro = Converts an existing content file or notice of receipt into another format without recalculating the fingerprints. The new content file is written next to the source file.
+view_convert_select_source = Select the file to convert
+view_convert_sizes_from_disk = Read the missing file sizes from the files located next to the source file
+view_convert_submit = Convert
+view_convert_ok = Content file written: { $path }
+view_convert_err_exists = A file already exists: { $path }
+view_convert_err_io = Unable to write the content file: { $msg }
+view_convert_err_sizes = The source file does not contain the file sizes, which are required by the selected format.
+view_convert_err_mixed = The source file uses several hashing functions, which is not supported by the selected format.
+view_convert_err_parse = Unable to parse the source file.
+view_convert_err_unknown_sizes =
+    { $nb ->
+        [one] The size of { $path } cannot be read from disk, so the content file has not been written.
+        *[other] The size of { $nb } files, such as { $path }, cannot be read from disk, so the content file has not been written.
+    }
+
+## Quick verification view
+
+view_quick_verify_title = Quick verification
+view_quick_verify_intro = Looks for a single file, or a fingerprint, in the loaded evidence and notices of receipt. A file may be selected or dropped on this window.
+view_quick_verify_no_entries = No fingerprint is available yet: calculate the evidence's fingerprints or load a notice of receipt first.
+view_quick_verify_select_file = Select a file
+view_quick_verify_digest = Fingerprint
+view_quick_verify_search = Search
+view_quick_verify_lookup_file = File: { $path }
+view_quick_verify_lookup_digest = Fingerprint: { $digest }
+view_quick_verify_detected_algorithm = Probable algorithm: { $algorithm }
+view_quick_verify_pending = Calculating the fingerprints…
+view_quick_verify_invalid_digest = This is not a valid fingerprint: only hexadecimal characters are expected.
+view_quick_verify_io_err = Unable to read the file: { $msg }
+view_quick_verify_no_match = No matching evidence has been found.
+view_quick_verify_match =
+    { $nb ->
+        [one] { $nb } matching entry has been found.
+        *[other] { $nb } matching entries have been found.
+    }
+view_quick_verify_match_path = File
+view_quick_verify_match_source = Source
+view_quick_verify_match_algorithm = Algorithm
+view_quick_verify_source_evidence = Evidence directory
+view_quick_verify_source_receipt = Notice of receipt { $path }
+
+## Comparison view
+
+view_compare_title = Comparison
+view_compare_intro = Compares two evidence directories, two content files, or a directory and a content file. Directories are hashed without writing any content file.
+view_compare_left = Left
+view_compare_right = Right
+view_compare_select_dir = Select a directory
+view_compare_select_ctn_file = Select a content file
+view_compare_submit = Compare
+view_compare_pending = Comparison in progress…
+view_compare_no_difference = Both sides are identical: { $nb } files share the same fingerprints ({ $hash_func }).
+view_compare_differences = Differences have been found ({ $hash_func }).
+view_compare_modified =
+    { $nb ->
+        [one] { $nb } modified file
+        *[other] { $nb } modified files
+    }
+view_compare_moved =
+    { $nb ->
+        [one] { $nb } moved file
+        *[other] { $nb } moved files
+    }
+view_compare_only_left =
+    { $nb ->
+        [one] { $nb } file only on the left
+        *[other] { $nb } files only on the left
+    }
+view_compare_only_right =
+    { $nb ->
+        [one] { $nb } file only on the right
+        *[other] { $nb } files only on the right
+    }
+view_compare_identical =
+    { $nb ->
+        [one] { $nb } identical file
+        *[other] { $nb } identical files
+    }
+view_compare_export = Export the report
+view_compare_export_ok = Report written: { $path }
+view_compare_export_err = Unable to write the report: { $msg }
+view_compare_err_hash_func = Both content files use different hashing functions: { $left } and { $right }.
+view_compare_err_io = Unable to read the files: { $msg }
+view_compare_err_parse = Unable to parse the content file: { $path }
+
+## Copy view
+
+view_copy_title = Copy and fingerprint
+view_copy_intro = Each file of the source directory is read once to be both copied and fingerprinted. The copy is then read again to be verified and the content file is written in the destination directory.
+view_copy_source = Source
+view_copy_destination = Destination
+view_copy_select_dir = Select a directory
+view_copy_submit = Copy
+view_copy_pending = Copy in progress…
+view_copy_ok =
+    { $nb ->
+        [one] One file has been copied to { $path } and verified.
+        *[other] { $nb } files have been copied to { $path } and verified.
+    }
+view_copy_mismatches =
+    { $nb ->
+        [one] One copied file differs from its source.
+        *[other] { $nb } copied files differ from their source.
+    }
+view_copy_errors =
+    { $nb ->
+        [one] One file could not be copied, it is not listed in the content file.
+        *[other] { $nb } files could not be copied, they are not listed in the content file.
+    }
+view_copy_ctn_file = Content file: { $path }
+view_copy_file = File
+view_copy_source_hash = Source fingerprint
+view_copy_destination_hash = Destination fingerprint
+view_copy_error = Error
+view_copy_err = Unable to copy the files: { $msg }
+
+## Audit log view
+
+view_audit_log_title = Audit log
+view_audit_log_intro = Every operation is recorded in { $path }. Each entry contains the fingerprint of the previous one, so any modification or removal breaks the chain.
+view_audit_log_verify = Reload and verify
+view_audit_log_chain_ok_title = Valid audit log
+view_audit_log_chain_ok =
+    { $nb ->
+        [one] The audit log contains { $nb } entry and its chain is valid.
+        *[other] The audit log contains { $nb } entries and its chain is valid.
+    }
+view_audit_log_chain_err_title = Altered audit log
+view_audit_log_chain_err = The chain is broken at line { $line }: { $reason } The following entries are not displayed, up to the next recovery of the audit log.
+view_audit_log_chain_err_parse = the entry cannot be read.
+view_audit_log_chain_err_sequence = an entry is missing.
+view_audit_log_chain_err_prev_hash = the entry is not chained to the previous one.
+view_audit_log_chain_err_hash = the entry has been modified.
+view_audit_log_io_err = Unable to read the audit log: { $msg }
+view_audit_log_date = Date
+view_audit_log_user = User
+view_audit_log_action = Operation
+view_audit_log_hash = Fingerprint
+view_audit_log_action_directory_load = Directory loaded: { $path } ({ $nb } files)
+view_audit_log_action_exclusion = Files excluded from { $path }: { $nb }
+view_audit_log_action_hashing = Fingerprints calculated: { $path } ({ $nb } files, { $hash_func }, Merkle root { $root })
+view_audit_log_action_ctn_file_write = Content file written: { $path }
+view_audit_log_action_readonly = Files set as read-only in { $path }: { $nb }
+view_audit_log_action_unlock = Original permissions restored in { $path }: { $nb }
+view_audit_log_action_check = Fingerprints of { $path } checked against { $reference }: { $nb } errors
+view_audit_log_action_copy = Files copied from { $source } to { $destination }: { $nb }, { $nb_mismatches } mismatches
+view_audit_log_action_clipboard = Clipboard filled for { $path } ({ $content })
+view_audit_log_action_recovery = Audit log resumed after the unreadable entry at line { $line }
+
+## Log view
+
+view_logs_title = Logs
+view_logs_intro = The application logs are stored in { $path }. They may be attached when reporting a problem.
+view_logs_empty = No log file has been written yet.
+view_logs_export = Export
+view_logs_export_ok =
+    { $nb ->
+        [one] One log file has been exported to { $path }.
+        *[other] { $nb } log files have been exported to { $path }.
+    }
+view_logs_export_err = Unable to export the log files: { $msg }
 
 ## Debug view
 
@@ -97,15 +462,27 @@ cpn_config_menu_files_title = Files
 cpn_config_menu_hash_title = Fingerprints
 cpn_config_menu_messages_title = Messages
 cpn_config_menu_clipboard_title = Clipboard
+cpn_config_menu_receipt_formats_title = Receipt formats
+cpn_config_menu_known_files_title = Known files
 
 view_config_main_msg_include_hidden_files = Include hidden files
 view_config_main_msg_include_system_files = Include system files
 view_config_main_msg_set_files_readonly = Set files as read-only
+view_config_main_msg_lock_directories = Also set directories as read-only, so files can neither be added, removed nor renamed
+view_config_main_msg_capture_metadata = Record the files metadata (dates, permissions, owner and extended attributes) to detect their modification
+view_config_main_msg_enable_history = Record the fingerprints in a local history to detect files received in other cases
+view_config_main_msg_log_level = Log level (applied at the next start)
+view_config_main_msg_content_file_dir = Content file directory
+view_config_main_msg_content_file_dir_default = Evidence directory
+view_config_main_msg_content_file_dir_select = Select
+view_config_main_msg_content_file_dir_reset = Evidence directory
 
 view_config_hash_msg_hash_func = Hashing algorithm
 view_config_hash_msg_content_file_format = Content file format
 view_config_hash_msg_content_file_name = Content file name
 view_config_hash_msg_customize_ctn_file_name = Edit
+view_config_hash_msg_content_file_trailer = Add a self-verification digest at the end of the content file
+view_config_hash_msg_content_file_lenient_parsing = Ignore blank lines, comments and malformed lines when reading a content file
 
 view_config_ctn_file_name_custom_value = Use a custom name
 view_config_ctn_file_name_default_value = Use the default name
@@ -132,9 +509,72 @@ view_config_clipboard_msg_has_custom_value = Custom value
 view_config_clipboard_msg_edit_value = Edit
 view_config_clipboard_msg_reset_value = Reset
 
+view_config_receipt_formats_intro = Additional notice of receipt formats can be declared here. They are tried after the built-in formats.
+view_config_receipt_formats_edit = Edit
+view_config_receipt_formats_delete = Delete
+view_config_receipt_formats_new_title = New format
+view_config_receipt_formats_edit_title = Edit the format
+view_config_receipt_formats_name = Name
+view_config_receipt_formats_regex = Line regular expression
+view_config_receipt_formats_begin_marker = Beginning of the list (optional)
+view_config_receipt_formats_end_marker = End of the list (optional)
+view_config_receipt_formats_hash_func = Hashing algorithm
+view_config_receipt_formats_hash_func_auto = Detect automatically
+view_config_receipt_formats_help = The regular expression must capture the file's path and fingerprint using named groups `(?P<path>…)` and `(?P<hash>…)`. The groups `(?P<size>…)` and `(?P<algorithm>…)` are optional. When a beginning of the list is defined, lines which do not match the regular expression are considered malformed.
+view_config_receipt_formats_select_sample = Select a sample file
+view_config_receipt_formats_test = Test
+view_config_receipt_formats_test_pending = Test in progress…
+view_config_receipt_formats_cancel = Cancel
+view_config_receipt_formats_invalid_title = Invalid format
+view_config_receipt_formats_invalid = The format must have a name and a valid regular expression capturing both `path` and `hash`.
+view_config_receipt_formats_result =
+    { $nb ->
+        [one] { $nb } file found
+        *[other] { $nb } files found
+    }, { $warnings ->
+        [one] { $warnings } malformed line
+        *[other] { $warnings } malformed lines
+    }
+view_config_receipt_formats_result_path = File
+view_config_receipt_formats_result_hash_func = Algorithm
+view_config_receipt_formats_result_size = Size
+view_config_receipt_formats_result_hash = Fingerprint
+view_config_receipt_formats_result_more =
+    { $nb ->
+        [one] and { $nb } other file
+        *[other] and { $nb } other files
+    }
+view_config_receipt_formats_result_err = Unable to parse the sample file
+view_config_known_files_intro = Hash sets list the fingerprints of known files. Files matching a known-good set, such as operating system files, may be ignored during the review, while files matching a known-bad set are reported. Plain lists of fingerprints and CSV files with a header are supported, such as the NSRL RDS version 3 exported with its SHA-256 column. The legacy NSRL RDS 2.x files (NSRLFile.txt) only contain SHA-1, MD5 and CRC32 fingerprints and cannot be used.
+view_config_known_files_status_ok =
+    { $nb ->
+        [one] ({ $nb } fingerprint)
+        *[other] ({ $nb } fingerprints)
+    }
+view_config_known_files_status_empty = (warning: no supported fingerprint, this set is ignored)
+view_config_known_files_status_io = (error: unable to read the file: { $msg })
+view_config_known_files_delete = Delete
+view_config_known_files_import_title = Import a hash set
+view_config_known_files_name = Name
+view_config_known_files_kind = Type
+view_config_known_files_kind_good = Known-good
+view_config_known_files_kind_bad = Known-bad
+view_config_known_files_path = File
+view_config_known_files_select = Select
+view_config_known_files_import = Import
+view_config_known_files_import_ok =
+    { $nb ->
+        [one] { $name }: { $nb } fingerprint imported.
+        *[other] { $name }: { $nb } fingerprints imported.
+    }
+view_config_known_files_import_empty = No supported fingerprint has been found in this file. Only the SHA-2, SHA-3, BLAKE2 and BLAKE3 fingerprints are supported, which excludes the legacy NSRL RDS 2.x files.
+view_config_known_files_import_io = Unable to read the file: { $msg }
+
 ## Header
 
 cpn_header_config = Configuration
+cpn_header_tools = Tools
+cpn_header_logs = Logs
 
 ## Theme
 
@@ -5,14 +5,62 @@ view_main_open_dir = Ouvrir un dossier
 view_main_open_receipt = Ouvrir un AR
 view_main_calc_fingerprints = Calculer les empreintes
 view_main_check_fingerprints = Vérifier les empreintes
+view_main_update_ctn_file = Mettre à jour le fichier de contenu
+view_main_select_ctn_file_dir = Écrire le fichier de contenu dans un autre répertoire
+view_main_case_title = Informations sur le dossier
+view_main_case_id = Numéro de dossier
+view_main_case_organisation = Organisme
+view_main_case_operator = Opérateur
+view_main_case_notes = Notes
+view_main_unlock = Retirer la protection
+view_main_unlock_title = Protection
+view_main_unlock_ok =
+    { $nb ->
+        [one] Les permissions d’origine d’un fichier ont été restaurées.
+        *[other] Les permissions d’origine de { $nb } fichiers et répertoires ont été restaurées.
+    }
+view_main_unlock_err = Impossible de retirer la protection : { $msg }
+view_main_ctn_file_location = Fichier de contenu : { $path }
+view_main_receipt_err_title = Impossible de charger l’accusé de réception
+view_main_receipt_err_format = Format le plus probable : { $format }
+view_main_receipt_err_position = Ligne { $line }, colonne { $column } : { $reason }
+view_main_receipt_err_line = Ligne { $line } : { $reason }
+view_main_receipt_err_tried = Formats essayés : { $formats }
+view_main_receipt_warnings_title = Accusé de réception
+view_main_ctn_file_warnings_title = Fichier de contenu
+view_main_parse_warnings_text =
+    { $nb ->
+        [one] Une ligne a été ignorée car elle est mal formée :
+        *[other] { $nb } lignes ont été ignorées car elles sont mal formées :
+    }
 view_main_check_result_title = Vérification des empreintes
 view_main_check_result_ok_text = Les empreintes correspondent.
+view_main_merkle_root = Empreinte de l’ensemble des pièces (racine de Merkle, { $hash_func }) : { $root }
 view_main_check_result_err_text = Échec de la vérification des empreintes.
 view_main_check_result_err_link = Voir les erreurs
+view_main_check_result_renamed_text =
+    { $nb ->
+        [one] Un fichier listé dans l’accusé de réception a été trouvé sur le disque sous un autre nom.
+        *[other] { $nb } fichiers listés dans l’accusé de réception ont été trouvés sur le disque sous un autre nom.
+    }
+view_main_check_result_renamed_link = Voir les fichiers renommés
+view_main_receipt_coverage_title = Accusés de réception
+view_main_receipt_coverage_text = { $nb } fichiers sur { $total } sont listés dans les accusés de réception.
+view_main_receipt_coverage_link = Voir les fichiers couverts
 view_main_clipboard_btn_list = Copier la liste
 view_main_clipboard_btn_file = Copier le fichier de contenu
 
 cpn_file_list_delete = Réinitialiser
+cpn_file_list_receipt_format = Format détecté : { $format } (confiance : { $confidence } %)
+cpn_file_list_receipt_format_forced = Format : { $format } (sélectionné manuellement)
+cpn_file_list_receipt_other_formats = Également reconnu comme : { $formats }
+cpn_file_list_receipt_candidate = { $format } ({ $confidence } %)
+cpn_file_list_receipt_format_select = Format utilisé pour lire l’accusé de réception
+cpn_file_list_receipt_format_auto = Détection automatique
+cpn_file_list_receipt_path_matching = Correspondance des chemins des fichiers
+cpn_file_list_receipt_path_separators = Considérer / et \ comme des séparateurs équivalents
+cpn_file_list_receipt_path_unicode = Ignorer les différences de normalisation Unicode (NFC et NFD)
+cpn_file_list_receipt_path_case = Ignorer les différences de casse
 
 cpn_progress_bar_status = Réalisé : { $done } / { $total } ({ $percent } %)
 
@@ -47,15 +95,89 @@ cpn_notif_excluded_files_link = { cpn_notif_empty_files_link }
 cpn_notif_duplicated_files_title = Fichiers dupliqués détectés
 cpn_notif_duplicated_files_text = Plusieurs fichiers contenus dans le dossier sélectionné sont identiques.
 cpn_notif_duplicated_files_link = Voir la liste
+cpn_notif_previous_evidence_title =
+    { $nb ->
+        [one] Fichier déjà reçu
+        *[other] Fichiers déjà reçus
+    }
+cpn_notif_previous_evidence_text =
+    { $nb ->
+        [one] Un fichier a déjà été reçu dans un autre dossier.
+        *[other] { $nb } fichiers ont déjà été reçus dans d’autres dossiers.
+    }
+cpn_notif_unprotected_files_title =
+    { $nb ->
+        [one] Protection retirée
+        *[other] Protections retirées
+    }
+cpn_notif_unprotected_files_text =
+    { $nb ->
+        [one] Le fichier ou répertoire suivant n’est plus en lecture seule depuis l’écriture du fichier de contenu :
+        *[other] Les { $nb } fichiers ou répertoires suivants ne sont plus en lecture seule depuis l’écriture du fichier de contenu :
+    }
+cpn_notif_case_write_error_title = Informations sur le dossier non enregistrées
+cpn_notif_case_write_error_text = Les informations sur le dossier n’ont pas pu être écrites à côté du fichier de contenu : { $msg }. Si le répertoire est protégé, retirez la protection puis calculez à nouveau les empreintes.
+cpn_notif_protection_error_title = Fichier de contenu non protégé
+cpn_notif_protection_error_text = Le fichier de contenu a été écrit mais n’a pas pu être mis en lecture seule : { $msg }.
+cpn_notif_metadata_changes_title = Métadonnées modifiées
+cpn_notif_metadata_changes_text =
+    { $nb ->
+        [one] Une métadonnée des fichiers a changé depuis l’écriture du fichier de contenu.
+        *[other] { $nb } métadonnées des fichiers ont changé depuis l’écriture du fichier de contenu.
+    }
+cpn_notif_metadata_changes_link = Afficher les modifications
+cpn_notif_known_bad_files_title =
+    { $nb ->
+        [one] Fichier malveillant connu détecté
+        *[other] Fichiers malveillants connus détectés
+    }
+cpn_notif_known_bad_files_text =
+    { $nb ->
+        [one] Un fichier correspond à une liste d’empreintes de fichiers malveillants.
+        *[other] { $nb } fichiers correspondent à une liste d’empreintes de fichiers malveillants.
+    }
+cpn_notif_known_good_files_title =
+    { $nb ->
+        [one] Fichier connu
+        *[other] Fichiers connus
+    }
+cpn_notif_known_good_files_text =
+    { $nb ->
+        [one] Un fichier correspond à une liste d’empreintes de fichiers connus et peut être ignoré lors de l’analyse.
+        *[other] { $nb } fichiers correspondent à une liste d’empreintes de fichiers connus et peuvent être ignorés lors de l’analyse.
+    }
+cpn_notif_known_files_link = Voir la liste
 
 ## Check errors view
 
 view_check_errors_title = Erreurs de vérification
 view_check_errors_ctn_file_parse = Le format du fichier de contenu n’est pas valide.
+view_check_errors_ctn_file_trailer = Le fichier de contenu a été modifié ou tronqué depuis sa création : son empreinte d’auto-vérification ne correspond pas.
+view_check_errors_ctn_file_merkle_root = L’empreinte de l’ensemble des pièces (racine de Merkle) ne correspond pas à celle calculée à partir du fichier de contenu.
 view_check_errors_ctn_file_missing = Fichier listé dans le fichier de contenu mais n’existant pas sur le disque.
 view_check_errors_ctn_file_match = Fichier ayant une empreinte différente que celle mentionnée dans le fichier de contenu.
 view_check_errors_receipt_missing = Fichier listé dans l’accusé de réception mais n’existant pas sur le disque.
 view_check_errors_receipt_match = Fichier ayant une empreinte différente que celle mentionnée dans l’accusé de réception.
+view_check_errors_receipt_conflict = Fichier listé avec des empreintes différentes dans plusieurs accusés de réception.
+view_check_errors_receipt_not_checked = Les empreintes requises par cet accusé de réception n’ont pas pu être calculées : il n’a pas été vérifié.
+view_check_errors_path_collision = Un autre fichier sur le disque a le même chemin une fois la casse, les séparateurs et la normalisation Unicode ignorés : un seul d’entre eux a été vérifié.
+view_check_errors_renamed_title = Fichiers renommés
+view_check_errors_renamed_intro = Les fichiers suivants, listés dans l’accusé de réception, n’ont pas été trouvés sous le même nom, mais un fichier ayant la même empreinte existe sur le disque. La plateforme les a peut-être renommés lors de leur dépôt.
+view_check_errors_renamed_local = Trouvé sur le disque sous le nom : { $path }
+
+## Receipt coverage view
+
+view_receipt_coverage_title = Couverture des accusés de réception
+view_receipt_coverage_uncovered =
+    { $nb ->
+        [one] Un fichier n’est listé dans aucun accusé de réception
+        *[other] { $nb } fichiers ne sont listés dans aucun accusé de réception
+    }
+view_receipt_coverage_covered =
+    { $nb ->
+        [one] Un fichier est listé dans les accusés de réception suivants
+        *[other] { $nb } fichiers sont listés dans les accusés de réception suivants
+    }
 
 ## Empty files view
 
@@ -72,6 +194,249 @@ view_excluded_files_title = { cpn_notif_excluded_files_title }
 ## Duplicated files view
 
 view_duplicated_files_title = Fichiers dupliqués
+view_duplicated_files_hide_known_good = Masquer les fichiers correspondant à une liste d’empreintes de fichiers connus
+view_duplicated_files_previous_title = Fichiers déjà reçus dans d’autres dossiers
+view_duplicated_files_previous_file = Fichier
+view_duplicated_files_previous_case = Dossier antérieur
+view_duplicated_files_previous_path = Chemin antérieur
+view_duplicated_files_previous_date = Date
+
+## Known files view
+
+view_known_files_title = Fichiers connus
+view_known_files_filter = Afficher
+view_known_files_filter_all = Tous les fichiers connus
+view_known_files_filter_bad = Uniquement les fichiers malveillants
+view_known_files_filter_good = Uniquement les fichiers sains
+view_known_files_bad = Malveillant ({ $set })
+view_known_files_good = Sain ({ $set })
+
+## Metadata changes view
+
+view_metadata_changes_title = Modifications des métadonnées
+view_metadata_changes_intro = Les métadonnées suivantes diffèrent de celles enregistrées lors de l’écriture du fichier de contenu. Elles n’affectent pas le contenu des fichiers, qui est vérifié séparément.
+view_metadata_changes_file = Fichier
+view_metadata_changes_field = Métadonnée
+view_metadata_changes_recorded = Enregistrée
+view_metadata_changes_current = Actuelle
+view_metadata_changes_field_modified = Date de modification
+view_metadata_changes_field_changed = Date de changement
+view_metadata_changes_field_created = Date de création
+view_metadata_changes_field_mode = Permissions
+view_metadata_changes_field_owner = Propriétaire
+view_metadata_changes_field_xattrs = Attributs étendus
+
+## Content file update view
+
+view_update_title = Mise à jour du fichier de contenu
+view_update_intro = Les modifications suivantes ont été détectées depuis l’écriture du fichier de contenu. Une fois confirmées, le précédent fichier de contenu sera archivé à côté du nouveau.
+view_update_added = Nouveaux fichiers
+view_update_modified = Fichiers modifiés
+view_update_removed = Fichiers supprimés
+view_update_confirm = Mettre à jour le fichier de contenu
+view_update_no_change = Aucune modification n’a été détectée depuis l’écriture du fichier de contenu.
+view_update_err_archive_exists = Un fichier existe déjà : { $path }
+view_update_err_io = Impossible de mettre à jour le fichier de contenu : { $msg }
+view_update_err_parse = Impossible de lire le fichier de contenu existant.
+
+## Receipt formats
+
+receipt_format_cksum_bsd = cksum (BSD)
+receipt_format_cksum_gnu = cksum (GNU)
+receipt_format_cnil_content_file = fichier de contenu CNIL
+receipt_format_cnil_platform_email_v3 = courriel de la plateforme CNIL (version 3)
+receipt_format_cnil_platform_email_v2 = courriel de la plateforme CNIL (version 2)
+receipt_format_cnil_platform_email_v1 = courriel de la plateforme CNIL (version 1)
+receipt_format_pdf = document PDF
+receipt_format_custom = { $name }
+
+## Parse errors
+
+parse_err_reason_io = le fichier n’a pas pu être lu
+parse_err_reason_encoding = le fichier n’est pas encodé en UTF-8
+parse_err_reason_trailer = l’empreinte d’auto-vérification est invalide
+parse_err_reason_missing_header = l’en-tête est absent
+parse_err_reason_header = l’en-tête est invalide
+parse_err_reason_line = la ligne est invalide
+parse_err_reason_message = le fichier n’est pas un courriel valide
+parse_err_reason_document = le fichier n’est pas un document PDF valide
+parse_err_reason_grammar = définition de format invalide : l’expression rationnelle doit être valide et capturer `path` et `hash`
+parse_err_reason_no_file = aucun fichier n’a été trouvé
+
+## Tools view
+
+view_tools_title = Outils
+view_tools_convert_ctn_file = Convertir un fichier de contenu
+view_tools_quick_verify = Vérifier un fichier ou une empreinte
+view_tools_compare = Comparer deux répertoires ou fichiers de contenu
+view_tools_audit_log = Journal d’audit
+view_tools_copy = Copier et calculer les empreintes
+
+## Content file conversion view
+
+view_convert_title = Conversion d’un fichier de contenu
+view_convert_intro = Convertit un fichier de contenu ou un accusé de réception existant dans un autre format sans recalculer les empreintes. Le nouveau fichier de contenu est écrit à côté du fichier source.
+view_convert_select_source = Sélectionner le fichier à convertir
+view_convert_sizes_from_disk = Lire les tailles manquantes depuis les fichiers situés à côté du fichier source
+view_convert_submit = Convertir
+view_convert_ok = Fichier de contenu écrit : { $path }
+view_convert_err_exists = Un fichier existe déjà : { $path }
+view_convert_err_io = Impossible d’écrire le fichier de contenu : { $msg }
+view_convert_err_sizes = Le fichier source ne contient pas la taille des fichiers, qui est requise par le format sélectionné.
+view_convert_err_mixed = Le fichier source utilise plusieurs algorithmes de hachage, ce qui n’est pas supporté par le format sélectionné.
+view_convert_err_parse = Impossible de lire le fichier source.
+view_convert_err_unknown_sizes =
+    { $nb ->
+        [one] La taille de { $path } ne peut pas être lue sur le disque, le fichier de contenu n’a donc pas été écrit.
+        *[other] La taille de { $nb } fichiers, dont { $path }, ne peut pas être lue sur le disque, le fichier de contenu n’a donc pas été écrit.
+    }
+
+## Quick verification view
+
+view_quick_verify_title = Vérification rapide
+view_quick_verify_intro = Recherche un fichier, ou une empreinte, parmi les pièces et les accusés de réception chargés. Un fichier peut être sélectionné ou déposé sur cette fenêtre.
+view_quick_verify_no_entries = Aucune empreinte n’est encore disponible : calculez les empreintes des pièces ou chargez un accusé de réception au préalable.
+view_quick_verify_select_file = Sélectionner un fichier
+view_quick_verify_digest = Empreinte
+view_quick_verify_search = Rechercher
+view_quick_verify_lookup_file = Fichier : { $path }
+view_quick_verify_lookup_digest = Empreinte : { $digest }
+view_quick_verify_detected_algorithm = Algorithme probable : { $algorithm }
+view_quick_verify_pending = Calcul des empreintes…
+view_quick_verify_invalid_digest = Il ne s’agit pas d’une empreinte valide : seuls des caractères hexadécimaux sont attendus.
+view_quick_verify_io_err = Impossible de lire le fichier : { $msg }
+view_quick_verify_no_match = Aucune pièce correspondante n’a été trouvée.
+view_quick_verify_match =
+    { $nb ->
+        [one] { $nb } entrée correspondante a été trouvée.
+        *[other] { $nb } entrées correspondantes ont été trouvées.
+    }
+view_quick_verify_match_path = Fichier
+view_quick_verify_match_source = Source
+view_quick_verify_match_algorithm = Algorithme
+view_quick_verify_source_evidence = Répertoire des pièces
+view_quick_verify_source_receipt = Accusé de réception { $path }
+
+## Comparison view
+
+view_compare_title = Comparaison
+view_compare_intro = Compare deux répertoires de preuves, deux fichiers de contenu, ou un répertoire et un fichier de contenu. Les répertoires sont hachés sans qu'aucun fichier de contenu ne soit écrit.
+view_compare_left = Gauche
+view_compare_right = Droite
+view_compare_select_dir = Sélectionner un répertoire
+view_compare_select_ctn_file = Sélectionner un fichier de contenu
+view_compare_submit = Comparer
+view_compare_pending = Comparaison en cours…
+view_compare_no_difference = Les deux côtés sont identiques : { $nb } fichiers ont les mêmes empreintes ({ $hash_func }).
+view_compare_differences = Des différences ont été trouvées ({ $hash_func }).
+view_compare_modified =
+    { $nb ->
+        [one] { $nb } fichier modifié
+        *[other] { $nb } fichiers modifiés
+    }
+view_compare_moved =
+    { $nb ->
+        [one] { $nb } fichier déplacé
+        *[other] { $nb } fichiers déplacés
+    }
+view_compare_only_left =
+    { $nb ->
+        [one] { $nb } fichier uniquement à gauche
+        *[other] { $nb } fichiers uniquement à gauche
+    }
+view_compare_only_right =
+    { $nb ->
+        [one] { $nb } fichier uniquement à droite
+        *[other] { $nb } fichiers uniquement à droite
+    }
+view_compare_identical =
+    { $nb ->
+        [one] { $nb } fichier identique
+        *[other] { $nb } fichiers identiques
+    }
+view_compare_export = Exporter le rapport
+view_compare_export_ok = Rapport écrit : { $path }
+view_compare_export_err = Impossible d'écrire le rapport : { $msg }
+view_compare_err_hash_func = Les deux fichiers de contenu utilisent des fonctions de hachage différentes : { $left } et { $right }.
+view_compare_err_io = Impossible de lire les fichiers : { $msg }
+view_compare_err_parse = Impossible d'analyser le fichier de contenu : { $path }
+
+## Copy view
+
+view_copy_title = Copier et calculer les empreintes
+view_copy_intro = Chaque fichier du répertoire source est lu une seule fois pour être à la fois copié et pour en calculer l’empreinte. La copie est ensuite relue afin d’être vérifiée et le fichier de contenu est écrit dans le répertoire de destination.
+view_copy_source = Source
+view_copy_destination = Destination
+view_copy_select_dir = Sélectionner un répertoire
+view_copy_submit = Copier
+view_copy_pending = Copie en cours…
+view_copy_ok =
+    { $nb ->
+        [one] Un fichier a été copié dans { $path } et vérifié.
+        *[other] { $nb } fichiers ont été copiés dans { $path } et vérifiés.
+    }
+view_copy_mismatches =
+    { $nb ->
+        [one] Un fichier copié diffère de sa source.
+        *[other] { $nb } fichiers copiés diffèrent de leur source.
+    }
+view_copy_errors =
+    { $nb ->
+        [one] Un fichier n’a pas pu être copié, il n’est pas listé dans le fichier de contenu.
+        *[other] { $nb } fichiers n’ont pas pu être copiés, ils ne sont pas listés dans le fichier de contenu.
+    }
+view_copy_ctn_file = Fichier de contenu : { $path }
+view_copy_file = Fichier
+view_copy_source_hash = Empreinte de la source
+view_copy_destination_hash = Empreinte de la destination
+view_copy_error = Erreur
+view_copy_err = Impossible de copier les fichiers : { $msg }
+
+## Audit log view
+
+view_audit_log_title = Journal d’audit
+view_audit_log_intro = Chaque opération est enregistrée dans { $path }. Chaque entrée contient l’empreinte de la précédente, de sorte que toute modification ou suppression rompt la chaîne.
+view_audit_log_verify = Recharger et vérifier
+view_audit_log_chain_ok_title = Journal d’audit valide
+view_audit_log_chain_ok =
+    { $nb ->
+        [one] Le journal d’audit contient { $nb } entrée et sa chaîne est valide.
+        *[other] Le journal d’audit contient { $nb } entrées et sa chaîne est valide.
+    }
+view_audit_log_chain_err_title = Journal d’audit altéré
+view_audit_log_chain_err = La chaîne est rompue à la ligne { $line } : { $reason } Les entrées suivantes ne sont pas affichées, jusqu’à la prochaine reprise du journal d’audit.
+view_audit_log_chain_err_parse = l’entrée ne peut pas être lue.
+view_audit_log_chain_err_sequence = une entrée est manquante.
+view_audit_log_chain_err_prev_hash = l’entrée n’est pas chaînée à la précédente.
+view_audit_log_chain_err_hash = l’entrée a été modifiée.
+view_audit_log_io_err = Impossible de lire le journal d’audit : { $msg }
+view_audit_log_date = Date
+view_audit_log_user = Utilisateur
+view_audit_log_action = Opération
+view_audit_log_hash = Empreinte
+view_audit_log_action_directory_load = Dossier chargé : { $path } ({ $nb } fichiers)
+view_audit_log_action_exclusion = Fichiers exclus de { $path } : { $nb }
+view_audit_log_action_hashing = Empreintes calculées : { $path } ({ $nb } fichiers, { $hash_func }, racine de Merkle { $root })
+view_audit_log_action_ctn_file_write = Fichier de contenu écrit : { $path }
+view_audit_log_action_readonly = Fichiers passés en lecture seule dans { $path } : { $nb }
+view_audit_log_action_unlock = Permissions d’origine restaurées dans { $path } : { $nb }
+view_audit_log_action_check = Empreintes de { $path } vérifiées par rapport à { $reference } : { $nb } erreurs
+view_audit_log_action_copy = Fichiers copiés de { $source } vers { $destination } : { $nb }, { $nb_mismatches } différences
+view_audit_log_action_clipboard = Presse-papier rempli pour { $path } ({ $content })
+view_audit_log_action_recovery = Reprise du journal d’audit après l’entrée illisible à la ligne { $line }
+
+## Log view
+
+view_logs_title = Journaux
+view_logs_intro = Les journaux de l’application sont enregistrés dans { $path }. Ils peuvent être joints lors du signalement d’un problème.
+view_logs_empty = Aucun fichier de journal n’a encore été écrit.
+view_logs_export = Exporter
+view_logs_export_ok =
+    { $nb ->
+        [one] Un fichier de journal a été exporté dans { $path }.
+        *[other] { $nb } fichiers de journal ont été exportés dans { $path }.
+    }
+view_logs_export_err = Impossible d’exporter les fichiers de journal : { $msg }
 
 ## Debug view
 
@@ -97,15 +462,27 @@ cpn_config_menu_files_title = Fichiers
 cpn_config_menu_hash_title = Empreintes
 cpn_config_menu_messages_title = Messages
 cpn_config_menu_clipboard_title = Presse-papier
+cpn_config_menu_receipt_formats_title = Formats d’accusé
+cpn_config_menu_known_files_title = Fichiers connus
 
 view_config_main_msg_include_hidden_files = Inclure les fichiers cachés
 view_config_main_msg_include_system_files = Inclure les fichiers système
 view_config_main_msg_set_files_readonly = Passer les fichiers en lecture seule
+view_config_main_msg_lock_directories = Passer également les répertoires en lecture seule, afin que les fichiers ne puissent être ni ajoutés, ni supprimés, ni renommés
+view_config_main_msg_capture_metadata = Enregistrer les métadonnées des fichiers (dates, permissions, propriétaire et attributs étendus) afin de détecter leur modification
+view_config_main_msg_enable_history = Enregistrer les empreintes dans un historique local afin de détecter les fichiers reçus dans d’autres dossiers
+view_config_main_msg_log_level = Niveau de journalisation (appliqué au prochain démarrage)
+view_config_main_msg_content_file_dir = Répertoire du fichier de contenu
+view_config_main_msg_content_file_dir_default = Répertoire des pièces
+view_config_main_msg_content_file_dir_select = Sélectionner
+view_config_main_msg_content_file_dir_reset = Répertoire des pièces
 
 view_config_hash_msg_hash_func = Algorithme de hachage
 view_config_hash_msg_content_file_format = Format du fichier de contenu
 view_config_hash_msg_content_file_name = Nom du fichier de contenu
 view_config_hash_msg_customize_ctn_file_name = Modifier
+view_config_hash_msg_content_file_trailer = Ajouter une empreinte d’auto-vérification à la fin du fichier de contenu
+view_config_hash_msg_content_file_lenient_parsing = Ignorer les lignes vides, les commentaires et les lignes mal formées lors de la lecture d’un fichier de contenu
 
 view_config_ctn_file_name_custom_value = Utiliser un nom personnalisé
 view_config_ctn_file_name_default_value = Utiliser le nom par défaut
@@ -132,9 +509,72 @@ view_config_clipboard_msg_has_custom_value = Valeur personnalisée
 view_config_clipboard_msg_edit_value = Modifier
 view_config_clipboard_msg_reset_value = Réinitialiser
 
+view_config_receipt_formats_intro = Des formats d’accusé de réception supplémentaires peuvent être déclarés ici. Ils sont essayés après les formats intégrés.
+view_config_receipt_formats_edit = Modifier
+view_config_receipt_formats_delete = Supprimer
+view_config_receipt_formats_new_title = Nouveau format
+view_config_receipt_formats_edit_title = Modifier le format
+view_config_receipt_formats_name = Nom
+view_config_receipt_formats_regex = Expression rationnelle d’une ligne
+view_config_receipt_formats_begin_marker = Début de la liste (facultatif)
+view_config_receipt_formats_end_marker = Fin de la liste (facultatif)
+view_config_receipt_formats_hash_func = Algorithme de hachage
+view_config_receipt_formats_hash_func_auto = Détection automatique
+view_config_receipt_formats_help = L’expression rationnelle doit capturer le chemin et l’empreinte du fichier à l’aide des groupes nommés `(?P<path>…)` et `(?P<hash>…)`. Les groupes `(?P<size>…)` et `(?P<algorithm>…)` sont facultatifs. Lorsqu’un début de liste est défini, les lignes qui ne correspondent pas à l’expression rationnelle sont considérées comme malformées.
+view_config_receipt_formats_select_sample = Sélectionner un fichier d’exemple
+view_config_receipt_formats_test = Tester
+view_config_receipt_formats_test_pending = Test du format en cours…
+view_config_receipt_formats_cancel = Annuler
+view_config_receipt_formats_invalid_title = Format invalide
+view_config_receipt_formats_invalid = Le format doit avoir un nom et une expression rationnelle valide capturant `path` et `hash`.
+view_config_receipt_formats_result =
+    { $nb ->
+        [one] { $nb } fichier trouvé
+        *[other] { $nb } fichiers trouvés
+    }, { $warnings ->
+        [one] { $warnings } ligne malformée
+        *[other] { $warnings } lignes malformées
+    }
+view_config_receipt_formats_result_path = Fichier
+view_config_receipt_formats_result_hash_func = Algorithme
+view_config_receipt_formats_result_size = Taille
+view_config_receipt_formats_result_hash = Empreinte
+view_config_receipt_formats_result_more =
+    { $nb ->
+        [one] et { $nb } autre fichier
+        *[other] et { $nb } autres fichiers
+    }
+view_config_receipt_formats_result_err = Impossible d’analyser le fichier d’exemple
+view_config_known_files_intro = Les listes d’empreintes recensent les empreintes de fichiers connus. Les fichiers correspondant à une liste de fichiers sains, comme les fichiers du système d’exploitation, peuvent être ignorés lors de l’analyse, tandis que les fichiers correspondant à une liste de fichiers malveillants sont signalés. Les listes simples d’empreintes et les fichiers CSV avec un en-tête sont acceptés, comme la NSRL RDS version 3 exportée avec sa colonne SHA-256. Les anciens fichiers NSRL RDS 2.x (NSRLFile.txt) ne contiennent que des empreintes SHA-1, MD5 et CRC32 et ne peuvent pas être utilisés.
+view_config_known_files_status_ok =
+    { $nb ->
+        [one] ({ $nb } empreinte)
+        *[other] ({ $nb } empreintes)
+    }
+view_config_known_files_status_empty = (attention : aucune empreinte prise en charge, cette liste est ignorée)
+view_config_known_files_status_io = (erreur : impossible de lire le fichier : { $msg })
+view_config_known_files_delete = Supprimer
+view_config_known_files_import_title = Importer une liste d’empreintes
+view_config_known_files_name = Nom
+view_config_known_files_kind = Type
+view_config_known_files_kind_good = Fichiers sains
+view_config_known_files_kind_bad = Fichiers malveillants
+view_config_known_files_path = Fichier
+view_config_known_files_select = Sélectionner
+view_config_known_files_import = Importer
+view_config_known_files_import_ok =
+    { $nb ->
+        [one] { $name } : { $nb } empreinte importée.
+        *[other] { $name } : { $nb } empreintes importées.
+    }
+view_config_known_files_import_empty = Aucune empreinte prise en charge n’a été trouvée dans ce fichier. Seules les empreintes SHA-2, SHA-3, BLAKE2 et BLAKE3 sont prises en charge, ce qui exclut les anciens fichiers NSRL RDS 2.x.
+view_config_known_files_import_io = Impossible de lire le fichier : { $msg }
+
 ## Header
 
 cpn_header_config = Configuration
+cpn_header_tools = Outils
+cpn_header_logs = Journaux
 
 ## Theme
 
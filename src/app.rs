@@ -3,9 +3,11 @@
 use crate::clipboard::{Clipboard, ClipboardStart};
 use crate::components::HeaderLangSwitchDisplay;
 use crate::config::Config;
+use crate::content_file_update::{ContentFileUpdate, UpdateError};
 use crate::events::{ExternalEventReceiver, ExternalEventSender, ExternalEventSignals};
 use crate::files::FileList;
 use crate::notifications::NotificationBlackList;
+use crate::parsers::ReceiptError;
 use crate::progress::{LoadingBarStatus, ProgressBarStatus};
 use crate::receipt::Receipt;
 use crate::theme::{get_default_theme, set_theme, Theme};
@@ -28,6 +30,26 @@ pub enum Route {
 	ExcludedFiles {},
 	#[route("/duplicated_files")]
 	DuplicatedFiles {},
+	#[route("/known_files")]
+	KnownFiles {},
+	#[route("/metadata_changes")]
+	MetadataChanges {},
+	#[route("/receipt_coverage")]
+	ReceiptCoverage {},
+	#[route("/update_content_file")]
+	UpdateContentFile {},
+	#[route("/tools")]
+	Tools {},
+	#[route("/tools/convert")]
+	ConvertContentFile {},
+	#[route("/tools/quick_verify")]
+	QuickVerify {},
+	#[route("/tools/compare")]
+	Compare {},
+	#[route("/tools/copy")]
+	CopyAndHash {},
+	#[route("/tools/audit_log")]
+	AuditLog {},
 	#[route("/config/files")]
 	FilesConfig {},
 	#[route("/config/hash")]
@@ -36,10 +58,16 @@ pub enum Route {
 	MessagesConfig {},
 	#[route("/config/clipboard")]
 	ClipboardConfig {},
+	#[route("/config/receipt_formats")]
+	ReceiptFormatsConfig {},
+	#[route("/config/known_files")]
+	KnownFilesConfig {},
 	#[route("/config/clipboard/template/:tpl_id")]
 	ClipboardTemplateConfig { tpl_id: usize },
 	#[route("/config/ctn_file_name")]
 	ContentFileNameConfig {},
+	#[route("/logs")]
+	Logs {},
 	#[cfg(feature = "nightly")]
 	#[route("/debug")]
 	Debug {},
@@ -107,8 +135,12 @@ fn initialize_global_context(config: Config, progress_tx: ExternalEventSender) {
 
 	// Files
 	use_context_provider(|| Signal::new(FileList::default()));
-	let receipt_status: Option<Receipt> = None;
-	use_context_provider(|| Signal::new(receipt_status));
+	let receipts: Vec<Receipt> = Vec::new();
+	use_context_provider(|| Signal::new(receipts));
+	let receipt_error: Option<ReceiptError> = None;
+	use_context_provider(|| Signal::new(receipt_error));
+	let ctn_file_update: Option<Result<ContentFileUpdate, UpdateError>> = None;
+	use_context_provider(|| Signal::new(ctn_file_update));
 
 	// Header
 	use_context_provider(|| Signal::new(HeaderLangSwitchDisplay::new()));
@@ -0,0 +1,439 @@
+use crate::config::Config;
+use crate::hash::HashFunc;
+use chrono::Utc;
+use serde_derive::{Deserialize, Serialize};
+use std::fs::{read_to_string, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const AUDIT_LOG_FILE_NAME: &str = "audit.jsonl";
+const AUDIT_HASH_FUNC: HashFunc = HashFunc::Sha256;
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// Entries may be appended from several threads: the lock ensures that each entry is chained to
+// the actual last one. The last entry is kept in memory so the log is only read again when it has
+// been modified by another instance.
+static AUDIT_LOCK: Mutex<Option<ChainHead>> = Mutex::new(None);
+
+struct ChainHead {
+	path: PathBuf,
+	len: u64,
+	seq: u64,
+	hash: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum AuditAction {
+	DirectoryLoad {
+		path: PathBuf,
+		nb_files: usize,
+	},
+	Exclusion {
+		path: PathBuf,
+		nb_files: usize,
+	},
+	Hashing {
+		path: PathBuf,
+		hash_func: String,
+		nb_files: usize,
+		merkle_root: String,
+	},
+	ContentFileWrite {
+		path: PathBuf,
+	},
+	ReadOnly {
+		path: PathBuf,
+		nb_files: usize,
+	},
+	Unlock {
+		path: PathBuf,
+		nb_files: usize,
+	},
+	Copy {
+		source: PathBuf,
+		destination: PathBuf,
+		nb_files: usize,
+		nb_mismatches: usize,
+	},
+	Check {
+		path: PathBuf,
+		reference: PathBuf,
+		nb_errors: usize,
+	},
+	ClipboardCopy {
+		path: PathBuf,
+		content: String,
+	},
+	// The last line of the log could not be read, for instance because the application stopped
+	// while writing it. The chain resumes from the last entry which could be read.
+	Recovery {
+		line: usize,
+	},
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct AuditEntry {
+	seq: u64,
+	timestamp: String,
+	user: String,
+	action: AuditAction,
+	prev_hash: String,
+	hash: String,
+}
+
+impl AuditEntry {
+	fn new(seq: u64, prev_hash: &str, action: AuditAction) -> Self {
+		let mut entry = Self {
+			seq,
+			timestamp: Utc::now().to_rfc3339(),
+			user: current_user(),
+			action,
+			prev_hash: prev_hash.to_string(),
+			hash: String::new(),
+		};
+		entry.hash = entry.compute_hash();
+		entry
+	}
+
+	// The hash covers the whole entry, including the previous entry's hash, except for itself.
+	fn compute_hash(&self) -> String {
+		let mut entry = self.clone();
+		entry.hash = String::new();
+		let json = serde_json::to_string(&entry).unwrap_or_default();
+		AUDIT_HASH_FUNC.hash_bytes(json.as_bytes())
+	}
+
+	pub fn get_seq(&self) -> u64 {
+		self.seq
+	}
+
+	pub fn get_timestamp(&self) -> &str {
+		&self.timestamp
+	}
+
+	pub fn get_user(&self) -> &str {
+		&self.user
+	}
+
+	pub fn get_action(&self) -> &AuditAction {
+		&self.action
+	}
+
+	pub fn get_hash(&self) -> &str {
+		&self.hash
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChainErrorReason {
+	Parse,
+	Sequence,
+	PreviousHash,
+	Hash,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChainError {
+	line: usize,
+	reason: ChainErrorReason,
+}
+
+impl ChainError {
+	pub fn get_line(&self) -> usize {
+		self.line
+	}
+
+	pub fn get_reason(&self) -> ChainErrorReason {
+		self.reason
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditLog {
+	entries: Vec<AuditEntry>,
+	error: Option<ChainError>,
+}
+
+impl AuditLog {
+	pub fn load(path: &Path) -> io::Result<Self> {
+		if !path.is_file() {
+			return Ok(Self::parse(""));
+		}
+		Ok(Self::parse(&read_to_string(path)?))
+	}
+
+	// The first entry breaking the chain is reported. The following entries are skipped, unless a
+	// recovery entry chained to the last valid one has been written afterward.
+	fn parse(content: &str) -> Self {
+		let mut entries: Vec<AuditEntry> = Vec::new();
+		let mut error = None;
+		let mut is_broken = false;
+		for (i, line) in content.lines().enumerate() {
+			if line.trim().is_empty() {
+				continue;
+			}
+			let reason = match serde_json::from_str::<AuditEntry>(line) {
+				Ok(entry) => {
+					let (prev_seq, prev_hash) = match entries.last() {
+						Some(e) => (e.seq, e.hash.as_str()),
+						None => (0, GENESIS_HASH),
+					};
+					let is_resuming = matches!(entry.action, AuditAction::Recovery { .. });
+					if is_broken && !is_resuming {
+						continue;
+					}
+					if entry.seq != prev_seq + 1 {
+						Some(ChainErrorReason::Sequence)
+					} else if entry.prev_hash != prev_hash {
+						Some(ChainErrorReason::PreviousHash)
+					} else if entry.hash != entry.compute_hash() {
+						Some(ChainErrorReason::Hash)
+					} else {
+						entries.push(entry);
+						is_broken = false;
+						None
+					}
+				}
+				Err(_) => Some(ChainErrorReason::Parse),
+			};
+			if let Some(reason) = reason {
+				is_broken = true;
+				error.get_or_insert(ChainError {
+					line: i + 1,
+					reason,
+				});
+			}
+		}
+		Self { entries, error }
+	}
+
+	pub fn get_entries(&self) -> &[AuditEntry] {
+		&self.entries
+	}
+
+	pub fn get_error(&self) -> Option<ChainError> {
+		self.error
+	}
+}
+
+pub fn get_file_path() -> PathBuf {
+	Config::get_config_dir().join(AUDIT_LOG_FILE_NAME)
+}
+
+pub fn record(action: AuditAction) {
+	record_in(&get_file_path(), action);
+}
+
+pub fn record_in(path: &Path, action: AuditAction) {
+	tracing::info!("Audit log: {action:?}");
+	if let Err(e) = append(path, action) {
+		tracing::error!("Unable to write in the audit log: {e}");
+	}
+}
+
+fn append(path: &Path, action: AuditAction) -> io::Result<AuditEntry> {
+	let mut head = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+	let len = match path.metadata() {
+		Ok(metadata) => metadata.len(),
+		Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+		Err(e) => return Err(e),
+	};
+	let mut content = String::new();
+	let (seq, prev_hash) = match head.as_ref() {
+		Some(h) if h.path == path && h.len == len => (h.seq, h.hash.clone()),
+		_ => {
+			let tail = read_tail(path)?;
+			let (seq, prev_hash) = match tail.last {
+				Some(entry) => (entry.seq, entry.hash),
+				None => (0, GENESIS_HASH.to_string()),
+			};
+			match tail.broken_line {
+				Some(line) => {
+					tracing::warn!(
+						"Audit log: unreadable entry at line {line}, resuming the chain"
+					);
+					if !tail.ends_with_newline {
+						content.push('\n');
+					}
+					let recovery =
+						AuditEntry::new(seq + 1, &prev_hash, AuditAction::Recovery { line });
+					content.push_str(&serde_json::to_string(&recovery).map_err(io::Error::other)?);
+					content.push('\n');
+					(recovery.seq, recovery.hash)
+				}
+				None => (seq, prev_hash),
+			}
+		}
+	};
+	let entry = AuditEntry::new(seq + 1, &prev_hash, action);
+	content.push_str(&serde_json::to_string(&entry).map_err(io::Error::other)?);
+	content.push('\n');
+	let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+	f.write_all(content.as_bytes())?;
+	*head = Some(ChainHead {
+		path: path.to_path_buf(),
+		len: len + content.len() as u64,
+		seq: entry.seq,
+		hash: entry.hash.clone(),
+	});
+	Ok(entry)
+}
+
+struct LogTail {
+	last: Option<AuditEntry>,
+	broken_line: Option<usize>,
+	ends_with_newline: bool,
+}
+
+// Looks for the last entry which can be read. When the last line cannot be read, its number is
+// returned so a recovery entry can be written.
+fn read_tail(path: &Path) -> io::Result<LogTail> {
+	let content = match read_to_string(path) {
+		Ok(content) => content,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+		Err(e) => return Err(e),
+	};
+	let mut tail = LogTail {
+		last: None,
+		broken_line: None,
+		ends_with_newline: content.is_empty() || content.ends_with('\n'),
+	};
+	let lines: Vec<&str> = content.lines().collect();
+	for (i, line) in lines.iter().enumerate().rev() {
+		if line.trim().is_empty() {
+			continue;
+		}
+		match serde_json::from_str::<AuditEntry>(line) {
+			Ok(entry) => {
+				tail.last = Some(entry);
+				break;
+			}
+			Err(_) => {
+				tail.broken_line.get_or_insert(i + 1);
+			}
+		}
+	}
+	Ok(tail)
+}
+
+fn current_user() -> String {
+	std::env::var("USER")
+		.or_else(|_| std::env::var("USERNAME"))
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn get_chain() -> String {
+		let mut prev_hash = GENESIS_HASH.to_string();
+		let mut lines = Vec::new();
+		for seq in 1..=3 {
+			let entry = AuditEntry::new(
+				seq,
+				&prev_hash,
+				AuditAction::DirectoryLoad {
+					path: PathBuf::from("/tmp/evidence"),
+					nb_files: seq as usize,
+				},
+			);
+			prev_hash = entry.hash.clone();
+			lines.push(serde_json::to_string(&entry).unwrap());
+		}
+		lines.join("\n")
+	}
+
+	#[test]
+	fn valid_chain() {
+		let log = AuditLog::parse(&get_chain());
+		assert_eq!(log.get_entries().len(), 3);
+		assert_eq!(log.get_error(), None);
+		assert_eq!(AuditLog::parse("").get_entries().len(), 0);
+	}
+
+	#[test]
+	fn altered_entry() {
+		let content = get_chain().replace("\"nb_files\":2", "\"nb_files\":5");
+		let log = AuditLog::parse(&content);
+		assert_eq!(log.get_entries().len(), 1);
+		assert_eq!(
+			log.get_error(),
+			Some(ChainError {
+				line: 2,
+				reason: ChainErrorReason::Hash
+			})
+		);
+	}
+
+	#[test]
+	fn removed_entry() {
+		let chain = get_chain();
+		let lines: Vec<&str> = chain.lines().collect();
+		let content = format!("{}\n{}", lines[0], lines[2]);
+		let log = AuditLog::parse(&content);
+		assert_eq!(
+			log.get_error().unwrap().get_reason(),
+			ChainErrorReason::Sequence
+		);
+		let log = AuditLog::parse("not json");
+		assert_eq!(
+			log.get_error().unwrap().get_reason(),
+			ChainErrorReason::Parse
+		);
+	}
+
+	fn load_action(nb_files: usize) -> AuditAction {
+		AuditAction::DirectoryLoad {
+			path: PathBuf::from("/tmp/evidence"),
+			nb_files,
+		}
+	}
+
+	#[test]
+	fn append_entries() {
+		let path = std::env::temp_dir().join(format!("loutre-{}.jsonl", uuid::Uuid::new_v4()));
+		for nb in 1..=3 {
+			let entry = append(&path, load_action(nb)).unwrap();
+			assert_eq!(entry.get_seq(), nb as u64);
+		}
+		let log = AuditLog::load(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(log.get_entries().len(), 3);
+		assert_eq!(log.get_error(), None);
+	}
+
+	#[test]
+	fn torn_last_entry() {
+		let path = std::env::temp_dir().join(format!("loutre-{}.jsonl", uuid::Uuid::new_v4()));
+		append(&path, load_action(1)).unwrap();
+		append(&path, load_action(2)).unwrap();
+		let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+		write!(f, "{{\"seq\":3,\"timest").unwrap();
+		let entry = append(&path, load_action(3)).unwrap();
+		assert_eq!(entry.get_seq(), 4);
+		append(&path, load_action(4)).unwrap();
+		let log = AuditLog::load(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(
+			log.get_error(),
+			Some(ChainError {
+				line: 3,
+				reason: ChainErrorReason::Parse
+			})
+		);
+		let actions: Vec<&AuditAction> = log.get_entries().iter().map(|e| e.get_action()).collect();
+		assert_eq!(
+			actions,
+			[
+				&load_action(1),
+				&load_action(2),
+				&AuditAction::Recovery { line: 3 },
+				&load_action(3),
+				&load_action(4)
+			]
+		);
+	}
+}
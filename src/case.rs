@@ -0,0 +1,120 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fs::{read_to_string, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const CASE_FILE_SUFFIX: &str = ".case.toml";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct CaseMetadata {
+	pub case_id: String,
+	pub organisation: String,
+	pub operator: String,
+	pub notes: String,
+}
+
+impl CaseMetadata {
+	pub fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+
+	pub fn get_case_id(&self) -> Option<&str> {
+		match self.case_id.trim() {
+			"" => None,
+			id => Some(id),
+		}
+	}
+
+	pub fn load(ctn_file_path: &Path) -> io::Result<Option<Self>> {
+		let path = case_file_path(ctn_file_path);
+		if !path.is_file() {
+			return Ok(None);
+		}
+		let content = read_to_string(&path)?;
+		let case = toml::from_str(&content).map_err(io::Error::other)?;
+		tracing::info!("Case metadata loaded: {}", path.display());
+		Ok(Some(case))
+	}
+
+	pub fn write(&self, ctn_file_path: &Path) -> io::Result<PathBuf> {
+		let path = case_file_path(ctn_file_path);
+		let content = toml::to_string(self).map_err(io::Error::other)?;
+		let mut f = File::create(&path)?;
+		f.write_all(content.as_bytes())?;
+		tracing::info!("Case metadata written: {}", path.display());
+		Ok(path)
+	}
+
+	// Avoids writing next to the content file, which directory may be locked, when the case
+	// metadata have not been modified since they were loaded.
+	pub fn write_if_changed(&self, ctn_file_path: &Path) -> io::Result<Option<PathBuf>> {
+		match Self::load(ctn_file_path) {
+			Ok(Some(case)) if &case == self => Ok(None),
+			_ => self.write(ctn_file_path).map(Some),
+		}
+	}
+}
+
+// The case metadata are stored next to the content file, which name they share.
+pub fn case_file_path(ctn_file_path: &Path) -> PathBuf {
+	let mut name = ctn_file_path.file_name().unwrap_or_default().to_os_string();
+	name.push(CASE_FILE_SUFFIX);
+	ctn_file_path.with_file_name(name)
+}
+
+pub fn is_case_file(path: &Path, ctn_file_path: &Path) -> bool {
+	path == case_file_path(ctn_file_path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn case_file() {
+		let ctn_file = Path::new("/tmp/evidence/contenu.txt");
+		assert_eq!(
+			case_file_path(ctn_file),
+			PathBuf::from("/tmp/evidence/contenu.txt.case.toml")
+		);
+		assert!(is_case_file(
+			Path::new("/tmp/evidence/contenu.txt.case.toml"),
+			ctn_file
+		));
+		assert!(!is_case_file(ctn_file, ctn_file));
+	}
+
+	#[test]
+	fn case_id() {
+		let mut case = CaseMetadata::default();
+		assert!(case.is_empty());
+		assert_eq!(case.get_case_id(), None);
+		case.case_id = " 24-001 ".to_string();
+		assert!(!case.is_empty());
+		assert_eq!(case.get_case_id(), Some("24-001"));
+	}
+
+	#[test]
+	fn write_if_changed() {
+		let dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let ctn_file = dir.join("contenu.txt");
+		let mut case = CaseMetadata {
+			case_id: "24-001".to_string(),
+			..Default::default()
+		};
+		assert!(case.write_if_changed(&ctn_file).unwrap().is_some());
+		assert!(case.write_if_changed(&ctn_file).unwrap().is_none());
+		case.operator = "J. Doe".to_string();
+		assert!(case.write_if_changed(&ctn_file).unwrap().is_some());
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn partial_content() {
+		let case: CaseMetadata = toml::from_str("operator = \"J. Doe\"\n").unwrap();
+		assert_eq!(case.operator, "J. Doe");
+		assert!(case.case_id.is_empty());
+	}
+}
@@ -1,7 +1,11 @@
 use crate::files::{HashedFile, HashedFileList};
+use crate::hash::HashFunc;
+use crate::merkle::merkle_root;
+use crate::path_matching::PathMatchingOptions;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy)]
 pub enum CheckType {
@@ -12,16 +16,23 @@ pub enum CheckType {
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub enum CheckResultError {
 	ContentFileParseError,
+	ContentFileTrailerMismatch,
+	ContentFileMerkleRootMismatch,
 	ContentFileMissingFile(PathBuf),
 	ContentFileNonMatchingFile(PathBuf),
 	ReceiptMissingFile(PathBuf),
 	ReceiptNonMatchingFile(PathBuf),
+	ReceiptConflict(PathBuf),
+	ReceiptNotChecked(PathBuf),
+	PathCollision(PathBuf),
 }
 
 impl fmt::Display for CheckResultError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let ctn_file_fmt = match &self {
 			Self::ContentFileParseError => "content file: parse error".to_string(),
+			Self::ContentFileTrailerMismatch => "content file: trailer mismatch".to_string(),
+			Self::ContentFileMerkleRootMismatch => "content file: Merkle root mismatch".to_string(),
 			Self::ContentFileMissingFile(p) => {
 				format!("content file: missing file: {}", p.display())
 			}
@@ -32,6 +43,11 @@ impl fmt::Display for CheckResultError {
 			Self::ReceiptNonMatchingFile(p) => {
 				format!("receipt: non matching file: {}", p.display())
 			}
+			Self::ReceiptConflict(p) => {
+				format!("receipt: conflicting fingerprints: {}", p.display())
+			}
+			Self::ReceiptNotChecked(p) => format!("receipt: not checked: {}", p.display()),
+			Self::PathCollision(p) => format!("path collision: {}", p.display()),
 		};
 		write!(f, "{ctn_file_fmt}")
 	}
@@ -52,63 +68,211 @@ impl CheckResult {
 	pub fn is_ok(&self) -> bool {
 		matches!(self, Self::Ok)
 	}
-}
 
-macro_rules! populate_map {
-	($dest_map: ident, $from_lst: ident, $base_dir: ident, $errors: ident, $t: ident, $add_err: expr) => {
-		for ref_file in $from_lst.get_files() {
-			let hash = ref_file.get_hash();
-			let rel_path = ref_file.get_relative_path();
-			let ref_file =
-				HashedFile::new_base_dir($base_dir, rel_path, 0, hash, ref_file.get_hash_func());
-			match ref_file.get_absolute_path() {
-				Ok(absolute_path) => {
-					$dest_map.insert(absolute_path, ref_file);
-				}
-				Err(_) => {
-					if $add_err {
-						add_missing_file(&mut $errors, &ref_file, $t);
-					}
-				}
-			};
+	pub fn nb_errors(&self) -> usize {
+		match self {
+			Self::Error(errors) => errors.len(),
+			Self::Ok | Self::None => 0,
 		}
-	};
+	}
+}
+
+// Platforms may rename the uploaded files. Such files are not reported as errors.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenamedFile {
+	receipt_path: PathBuf,
+	local_path: PathBuf,
+}
+
+impl RenamedFile {
+	pub fn get_receipt_path(&self) -> &Path {
+		&self.receipt_path
+	}
+
+	pub fn get_local_path(&self) -> &Path {
+		&self.local_path
+	}
+}
+
+// The covered files are the local files which are listed in the reference file list, whether or not
+// their fingerprint matches.
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+	pub result: CheckResult,
+	pub renamed: Vec<RenamedFile>,
+	pub covered: Vec<PathBuf>,
 }
 
 pub fn check(
 	calculated_fl: &HashedFileList,
 	reference_fl: &HashedFileList,
 	t: CheckType,
-) -> CheckResult {
+	path_matching: &PathMatchingOptions,
+) -> CheckReport {
 	tracing::info!("Starting fingerprint check");
 	let mut errors = HashSet::new();
-	let base_dir = calculated_fl.get_base_dir();
 
-	let mut reference_map = HashMap::with_capacity(reference_fl.len(None));
-	populate_map!(reference_map, reference_fl, base_dir, errors, t, true);
-	let mut calculated_map = HashMap::with_capacity(calculated_fl.len(None));
-	populate_map!(calculated_map, calculated_fl, base_dir, errors, t, false);
-
-	for (path, ref_file) in reference_map.iter() {
-		match calculated_map.get(path) {
+	let mut calculated_map = get_calculated_map(calculated_fl, path_matching, &mut errors);
+	let mut unmatched = Vec::new();
+	let mut covered = Vec::new();
+	for ref_file in reference_fl.get_files() {
+		match calculated_map.remove(&path_matching.get_key(ref_file.get_relative_path())) {
 			Some(calc_file) => {
-				if calc_file.get_hash() != ref_file.get_hash() {
+				covered.push(calc_file.get_relative_path().to_path_buf());
+				if calc_file.get_hash() != ref_file.get_hash()
+					|| !ref_file.size_matches(calc_file.get_size())
+				{
 					add_non_matching_file(&mut errors, ref_file, t);
 				}
 			}
-			None => {
-				add_missing_file(&mut errors, ref_file, t);
-			}
+			None => unmatched.push(ref_file),
+		}
+	}
+
+	let renamed = match t {
+		CheckType::ContentFile => Vec::new(),
+		CheckType::Receipt => {
+			let mut local_files: Vec<&HashedFile> = calculated_map.into_values().collect();
+			reconcile_by_digest(&mut unmatched, &mut local_files)
+		}
+	};
+	for ref_file in unmatched {
+		add_missing_file(&mut errors, ref_file, t);
+	}
+	covered.extend(renamed.iter().map(|r| r.local_path.clone()));
+
+	if let CheckType::ContentFile = t {
+		if !merkle_root_matches(calculated_fl, reference_fl, path_matching) {
+			let e = CheckResultError::ContentFileMerkleRootMismatch;
+			tracing::warn!("{e}");
+			errors.insert(e);
 		}
 	}
 
-	if errors.is_empty() {
+	let result = if errors.is_empty() {
 		tracing::info!("Fingerprint check done: ok");
 		CheckResult::Ok
 	} else {
 		tracing::warn!("Fingerprint check done: {} errors", errors.len());
 		CheckResult::Error(errors.into_iter().collect())
+	};
+	CheckReport {
+		result,
+		renamed,
+		covered,
+	}
+}
+
+// Local files which only differ by case, separators or Unicode normalization may share the same
+// key. Only the first one is matched, each one of them is reported.
+fn get_calculated_map<'a>(
+	calculated_fl: &'a HashedFileList,
+	path_matching: &PathMatchingOptions,
+	errors: &mut HashSet<CheckResultError>,
+) -> HashMap<String, &'a HashedFile> {
+	let mut files: Vec<&HashedFile> = calculated_fl.get_files().collect();
+	files.sort_by_key(|f| f.get_relative_path().to_path_buf());
+	let mut calculated_map: HashMap<String, &HashedFile> = HashMap::with_capacity(files.len());
+	for file in files {
+		match calculated_map.entry(path_matching.get_key(file.get_relative_path())) {
+			Entry::Occupied(entry) => {
+				tracing::warn!(
+					"{} and {}: path collision",
+					entry.get().get_relative_path().display(),
+					file.get_relative_path().display()
+				);
+				for path in [entry.get().get_relative_path(), file.get_relative_path()] {
+					errors.insert(CheckResultError::PathCollision(path.to_path_buf()));
+				}
+			}
+			Entry::Vacant(entry) => {
+				entry.insert(file);
+			}
+		}
 	}
+	calculated_map
+}
+
+// Each local file may only be matched with a single receipt entry. Both lists are sorted so the
+// matching does not depend on the iteration order when several files share the same content.
+fn reconcile_by_digest(
+	unmatched: &mut Vec<&HashedFile>,
+	local_files: &mut Vec<&HashedFile>,
+) -> Vec<RenamedFile> {
+	unmatched.sort_by_key(|f| f.get_relative_path().to_path_buf());
+	local_files.sort_by_key(|f| f.get_relative_path().to_path_buf());
+	let mut renamed = Vec::new();
+	unmatched.retain(|ref_file| {
+		let pos = local_files.iter().position(|f| {
+			f.get_hash_func() == ref_file.get_hash_func()
+				&& f.get_hash().eq_ignore_ascii_case(ref_file.get_hash())
+				&& ref_file.size_matches(f.get_size())
+		});
+		match pos {
+			Some(i) => {
+				let local_file = local_files.remove(i);
+				tracing::info!(
+					"Receipt entry {} matches local file {}",
+					ref_file.get_relative_path().display(),
+					local_file.get_relative_path().display()
+				);
+				renamed.push(RenamedFile {
+					receipt_path: ref_file.get_relative_path().to_path_buf(),
+					local_path: local_file.get_relative_path().to_path_buf(),
+				});
+				false
+			}
+			None => true,
+		}
+	});
+	renamed
+}
+
+// Some content file formats do not store the file size. In such case, the size of the calculated
+// file is used since, if both fingerprints are identical, so are the sizes. Both roots are computed
+// over the same normalized paths as the ones used to match the files.
+fn merkle_root_matches(
+	calculated_fl: &HashedFileList,
+	reference_fl: &HashedFileList,
+	path_matching: &PathMatchingOptions,
+) -> bool {
+	let hash_func = if calculated_fl.is_empty() {
+		HashFunc::default()
+	} else {
+		calculated_fl.get_main_hashing_function()
+	};
+	let calculated: Vec<(PathBuf, u64, &str)> = calculated_fl
+		.get_files()
+		.map(|f| {
+			let key = path_matching.get_key(f.get_relative_path());
+			(PathBuf::from(key), f.get_size(), f.get_hash())
+		})
+		.collect();
+	let calculated_sizes: HashMap<&Path, u64> = calculated
+		.iter()
+		.map(|(path, size, _)| (path.as_path(), *size))
+		.collect();
+	let reference: Vec<(PathBuf, u64, &str)> = reference_fl
+		.get_files()
+		.map(|f| {
+			let path = PathBuf::from(path_matching.get_key(f.get_relative_path()));
+			let size = match f.get_size() {
+				0 => calculated_sizes.get(path.as_path()).copied().unwrap_or(0),
+				s => s,
+			};
+			(path, size, f.get_hash())
+		})
+		.collect();
+	let calculated_root = merkle_root(
+		calculated.iter().map(|(p, s, h)| (p.as_path(), *s, *h)),
+		hash_func,
+	);
+	let reference_root = merkle_root(
+		reference.iter().map(|(p, s, h)| (p.as_path(), *s, *h)),
+		hash_func,
+	);
+	tracing::info!("Merkle root: calculated {calculated_root}, reference {reference_root}");
+	calculated_root == reference_root
 }
 
 #[inline]
@@ -132,3 +296,139 @@ fn add_non_matching_file(errors: &mut HashSet<CheckResultError>, file: &HashedFi
 	tracing::warn!("{e}");
 	errors.insert(e);
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+	const HASH_2: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+
+	fn get_list(paths: &[&str]) -> HashedFileList {
+		let mut lst = HashedFileList::new();
+		for path in paths {
+			lst.insert_file(HashedFile::new(path, 4, HASH, HashFunc::Sha256));
+		}
+		lst
+	}
+
+	#[test]
+	fn windows_receipt() {
+		let calculated = get_list(&["dir/a.txt", "b.txt"]);
+		let reference = get_list(&["dir\\a.txt", "b.txt"]);
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::Receipt,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_ok());
+		assert!(report.renamed.is_empty());
+	}
+
+	#[test]
+	fn normalized_content_file() {
+		let calculated = get_list(&["dir/\u{e9}t\u{e9}.txt", "b.txt"]);
+		let reference = get_list(&["dir\\e\u{301}te\u{301}.txt", "b.txt"]);
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::ContentFile,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_ok());
+	}
+
+	#[test]
+	fn missing_file() {
+		let calculated = get_list(&["a.txt"]);
+		let reference = get_list(&["A.txt"]);
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::ContentFile,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_err());
+		let opts = PathMatchingOptions {
+			case_insensitive: true,
+			..PathMatchingOptions::default()
+		};
+		let report = check(&calculated, &reference, CheckType::Receipt, &opts);
+		assert!(report.result.is_ok());
+	}
+
+	#[test]
+	fn path_collision() {
+		let calculated = get_list(&["a.txt", "A.txt"]);
+		let reference = get_list(&["a.txt"]);
+		let opts = PathMatchingOptions {
+			case_insensitive: true,
+			..PathMatchingOptions::default()
+		};
+		let report = check(&calculated, &reference, CheckType::Receipt, &opts);
+		let CheckResult::Error(errors) = report.result else {
+			panic!("path collision not reported");
+		};
+		assert_eq!(errors.len(), 2);
+		assert!(errors.contains(&CheckResultError::PathCollision(PathBuf::from("A.txt"))));
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::Receipt,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_ok());
+	}
+
+	#[test]
+	fn renamed_file() {
+		let mut calculated = get_list(&["a.txt"]);
+		calculated.insert_file(HashedFile::new("Été.txt", 4, HASH_2, HashFunc::Sha256));
+		let mut reference = get_list(&["a.txt"]);
+		reference.insert_file(HashedFile::new("Ete_1.txt", 4, HASH_2, HashFunc::Sha256));
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::Receipt,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_ok());
+		assert_eq!(report.renamed.len(), 1);
+		assert_eq!(report.renamed[0].get_receipt_path(), Path::new("Ete_1.txt"));
+		assert_eq!(report.renamed[0].get_local_path(), Path::new("Été.txt"));
+		assert_eq!(report.covered.len(), 2);
+	}
+
+	#[test]
+	fn renamed_file_size_mismatch() {
+		let mut calculated = HashedFileList::new();
+		calculated.insert_file(HashedFile::new("b.txt", 8, HASH_2, HashFunc::Sha256));
+		let mut reference = HashedFileList::new();
+		reference.insert_file(HashedFile::new("a.txt", 4, HASH_2, HashFunc::Sha256));
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::Receipt,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_err());
+		assert!(report.renamed.is_empty());
+	}
+
+	#[test]
+	fn renamed_duplicates() {
+		let calculated = get_list(&["x.txt", "y.txt"]);
+		let reference = get_list(&["a.txt", "b.txt"]);
+		let report = check(
+			&calculated,
+			&reference,
+			CheckType::Receipt,
+			&PathMatchingOptions::default(),
+		);
+		assert!(report.result.is_ok());
+		assert_eq!(report.renamed[0].get_receipt_path(), Path::new("a.txt"));
+		assert_eq!(report.renamed[0].get_local_path(), Path::new("x.txt"));
+		assert_eq!(report.renamed[1].get_local_path(), Path::new("y.txt"));
+	}
+}
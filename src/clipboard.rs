@@ -1,3 +1,4 @@
+use crate::audit::{self, AuditAction};
 use crate::config::Config;
 use crate::files::HashedFileList;
 use crate::templates::{filter_add_dir_level, filter_nb_letters, EntryTemplate};
@@ -186,8 +187,9 @@ impl Clipboard {
 		start: ClipboardStart,
 	) -> Result<(), ClipboardError> {
 		let ret = self.inner_set_clipboard_list(config, file_list, start);
-		if let Err(ref e) = ret {
-			tracing::error!("{e}");
+		match ret {
+			Ok(_) => record_copy(file_list, "list"),
+			Err(ref e) => tracing::error!("{e}"),
 		}
 		ret
 	}
@@ -199,8 +201,9 @@ impl Clipboard {
 		start: ClipboardStart,
 	) -> Result<(), ClipboardError> {
 		let ret = self.inner_set_clipboard_ctn_file(config, file_list, start);
-		if let Err(ref e) = ret {
-			tracing::error!("{e}");
+		match ret {
+			Ok(_) => record_copy(file_list, "content-file"),
+			Err(ref e) => tracing::error!("{e}"),
 		}
 		ret
 	}
@@ -221,6 +224,7 @@ impl Clipboard {
 			hash_func => file_list.get_main_hashing_function().to_string(),
 			nb_start => start.0,
 			evidences,
+			case => file_list.get_case(),
 		);
 		let model_txt = ClipboardDefaultTemplate::ListText.get_template(config, 0);
 		let model_html = ClipboardDefaultTemplate::ListHtml.get_template(config, 0);
@@ -274,6 +278,7 @@ impl Clipboard {
 		let hash_func = hash_func.to_string();
 		let ctx = context!(
 			hash_func,
+			merkle_root => file_list.get_merkle_root(),
 			nb_evidences => nb_evidences,
 			nb_start => start.0,
 			evidence => context!(
@@ -282,6 +287,7 @@ impl Clipboard {
 				hash,
 				hash_func,
 			),
+			case => file_list.get_case(),
 		);
 		let model_txt =
 			ClipboardDefaultTemplate::ContentFileText.get_template(config, nb_evidences);
@@ -318,3 +324,10 @@ impl Clipboard {
 		}
 	}
 }
+
+fn record_copy(file_list: &HashedFileList, content: &str) {
+	audit::record(AuditAction::ClipboardCopy {
+		path: file_list.get_base_dir().to_path_buf(),
+		content: content.to_string(),
+	});
+}
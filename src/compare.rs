@@ -0,0 +1,404 @@
+use crate::check::{check, CheckResult, CheckResultError, CheckType, RenamedFile};
+use crate::config::Config;
+use crate::events::{send_event, ExternalEvent, ExternalEventSender};
+use crate::files::{HashedFileList, NonHashedFileList};
+use crate::hash::HashFunc;
+use crate::path_matching::PathMatchingOptions;
+use crate::receipt::Receipt;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const REPORT_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompareSource {
+	Directory(PathBuf),
+	ContentFile(PathBuf),
+}
+
+impl CompareSource {
+	pub fn get_path(&self) -> &Path {
+		match self {
+			Self::Directory(p) | Self::ContentFile(p) => p,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompareError {
+	HashFunctionMismatch(HashFunc, HashFunc),
+	Io(String),
+	Parse(PathBuf),
+}
+
+impl fmt::Display for CompareError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::HashFunctionMismatch(l, r) => {
+				write!(f, "different hashing functions: {l} and {r}")
+			}
+			Self::Io(e) => write!(f, "I/O error: {e}"),
+			Self::Parse(p) => write!(f, "{}: unable to parse the content file", p.display()),
+		}
+	}
+}
+
+impl From<io::Error> for CompareError {
+	fn from(e: io::Error) -> Self {
+		Self::Io(e.to_string())
+	}
+}
+
+// Paths are relative to their own side. Moved files have the same fingerprint on both sides but
+// not the same path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+	left: CompareSource,
+	right: CompareSource,
+	hash_func: HashFunc,
+	only_left: Vec<PathBuf>,
+	only_right: Vec<PathBuf>,
+	identical: Vec<PathBuf>,
+	modified: Vec<PathBuf>,
+	moved: Vec<RenamedFile>,
+}
+
+impl Comparison {
+	pub fn get_hash_func(&self) -> HashFunc {
+		self.hash_func
+	}
+
+	pub fn get_only_left(&self) -> &[PathBuf] {
+		&self.only_left
+	}
+
+	pub fn get_only_right(&self) -> &[PathBuf] {
+		&self.only_right
+	}
+
+	pub fn get_identical(&self) -> &[PathBuf] {
+		&self.identical
+	}
+
+	pub fn get_modified(&self) -> &[PathBuf] {
+		&self.modified
+	}
+
+	pub fn get_moved(&self) -> &[RenamedFile] {
+		&self.moved
+	}
+
+	pub fn has_differences(&self) -> bool {
+		!self.only_left.is_empty()
+			|| !self.only_right.is_empty()
+			|| !self.modified.is_empty()
+			|| !self.moved.is_empty()
+	}
+
+	pub fn write_report(&self, dir: &Path) -> io::Result<PathBuf> {
+		let file_name = format!(
+			"comparison-{}.csv",
+			Utc::now().format(REPORT_TIMESTAMP_FORMAT)
+		);
+		let path = dir.join(file_name);
+		let mut f = File::create_new(&path)?;
+		self.write_csv(&mut f)?;
+		tracing::info!("Comparison report written: {}", path.display());
+		Ok(path)
+	}
+
+	fn write_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+		writeln!(w, "# left: {}", self.left.get_path().display())?;
+		writeln!(w, "# right: {}", self.right.get_path().display())?;
+		writeln!(w, "# hashing function: {}", self.hash_func)?;
+		writeln!(w, "status,left,right")?;
+		let empty = Path::new("");
+		for p in &self.identical {
+			write_csv_line(w, "identical", p, p)?;
+		}
+		for p in &self.modified {
+			write_csv_line(w, "modified", p, p)?;
+		}
+		for r in &self.moved {
+			write_csv_line(w, "moved", r.get_receipt_path(), r.get_local_path())?;
+		}
+		for p in &self.only_left {
+			write_csv_line(w, "only-left", p, empty)?;
+		}
+		for p in &self.only_right {
+			write_csv_line(w, "only-right", empty, p)?;
+		}
+		Ok(())
+	}
+}
+
+fn write_csv_line<W: Write>(w: &mut W, status: &str, left: &Path, right: &Path) -> io::Result<()> {
+	writeln!(
+		w,
+		"{status},{},{}",
+		csv_field(&left.display().to_string()),
+		csv_field(&right.display().to_string())
+	)
+}
+
+fn csv_field(value: &str) -> String {
+	if value.contains([',', '"', '\n']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
+
+pub async fn compare_sources(
+	left: CompareSource,
+	right: CompareSource,
+	config: &Config,
+	tx: ExternalEventSender,
+) -> Result<Comparison, CompareError> {
+	tracing::info!(
+		"Comparing {} and {}",
+		left.get_path().display(),
+		right.get_path().display()
+	);
+	let left_ctn = load_content_file(&left, config)?;
+	let right_ctn = load_content_file(&right, config)?;
+	// Directories are hashed with the function used by the other side's content file, if any.
+	let hash_func = match (&left_ctn, &right_ctn) {
+		(Some(l), Some(r)) => {
+			let (lh, rh) = (l.get_main_hashing_function(), r.get_main_hashing_function());
+			if lh != rh {
+				return Err(CompareError::HashFunctionMismatch(lh, rh));
+			}
+			lh
+		}
+		(Some(c), None) | (None, Some(c)) => c.get_main_hashing_function(),
+		(None, None) => config.hash_function,
+	};
+	let left_dir = load_directory(&left, config).await?;
+	let right_dir = load_directory(&right, config).await?;
+	let total_size = [&left_dir, &right_dir]
+		.iter()
+		.filter_map(|d| d.as_ref())
+		.fold(0, |acc, d| acc + d.total_size());
+	send_event(&tx, ExternalEvent::ProgressBarCreate(total_size));
+	let left_lst = get_file_list(left_ctn, left_dir, config, hash_func, tx.clone());
+	let right_lst = get_file_list(right_ctn, right_dir, config, hash_func, tx.clone());
+	send_event(&tx, ExternalEvent::ProgressBarDelete);
+	let cmp = compare(left, right, hash_func, &left_lst?, &right_lst?);
+	tracing::info!(
+		"Comparison done: {} identical, {} modified, {} moved, {} only on the left, {} only on the right",
+		cmp.identical.len(),
+		cmp.modified.len(),
+		cmp.moved.len(),
+		cmp.only_left.len(),
+		cmp.only_right.len()
+	);
+	Ok(cmp)
+}
+
+// The left side is checked as if it were a notice of receipt of the right side, so files are
+// matched using the same rules, including the reconciliation of renamed files.
+pub fn compare(
+	left: CompareSource,
+	right: CompareSource,
+	hash_func: HashFunc,
+	left_lst: &HashedFileList,
+	right_lst: &HashedFileList,
+) -> Comparison {
+	let report = check(
+		right_lst,
+		left_lst,
+		CheckType::Receipt,
+		&PathMatchingOptions::default(),
+	);
+	let mut only_left = Vec::new();
+	let mut modified = Vec::new();
+	if let CheckResult::Error(errors) = report.result {
+		for e in errors {
+			match e {
+				CheckResultError::ReceiptMissingFile(p) => only_left.push(p),
+				CheckResultError::ReceiptNonMatchingFile(p) => modified.push(p),
+				_ => {}
+			}
+		}
+	}
+	let mut moved = report.renamed;
+	let left_others: HashSet<&Path> = only_left
+		.iter()
+		.chain(modified.iter())
+		.map(PathBuf::as_path)
+		.chain(moved.iter().map(|r| r.get_receipt_path()))
+		.collect();
+	let mut identical: Vec<PathBuf> = left_lst
+		.get_files()
+		.map(|f| f.get_relative_path())
+		.filter(|p| !left_others.contains(p))
+		.map(Path::to_path_buf)
+		.collect();
+	let covered: HashSet<PathBuf> = report.covered.into_iter().collect();
+	let mut only_right: Vec<PathBuf> = right_lst
+		.get_files()
+		.map(|f| f.get_relative_path().to_path_buf())
+		.filter(|p| !covered.contains(p))
+		.collect();
+	only_left.sort();
+	only_right.sort();
+	identical.sort();
+	modified.sort();
+	moved.sort_by(|a, b| a.get_receipt_path().cmp(b.get_receipt_path()));
+	Comparison {
+		left,
+		right,
+		hash_func,
+		only_left,
+		only_right,
+		identical,
+		modified,
+		moved,
+	}
+}
+
+fn load_content_file(
+	src: &CompareSource,
+	config: &Config,
+) -> Result<Option<Receipt>, CompareError> {
+	match src {
+		CompareSource::ContentFile(path) => {
+			let default_hash = match crate::analyse_hash::from_path(path) {
+				Some(h) => h,
+				None => config.hash_function,
+			};
+			Receipt::new(path, default_hash, config.get_parse_mode(), &[])
+				.map(Some)
+				.map_err(|_| CompareError::Parse(path.clone()))
+		}
+		CompareSource::Directory(_) => Ok(None),
+	}
+}
+
+async fn load_directory(
+	src: &CompareSource,
+	config: &Config,
+) -> Result<Option<NonHashedFileList>, CompareError> {
+	match src {
+		CompareSource::Directory(path) => Ok(Some(
+			NonHashedFileList::from_dir(
+				path,
+				config.include_hidden_files(),
+				config.include_system_files(),
+			)
+			.await?,
+		)),
+		CompareSource::ContentFile(_) => Ok(None),
+	}
+}
+
+fn get_file_list(
+	ctn_file: Option<Receipt>,
+	dir: Option<NonHashedFileList>,
+	config: &Config,
+	hash_func: HashFunc,
+	tx: ExternalEventSender,
+) -> Result<HashedFileList, CompareError> {
+	match (ctn_file, dir) {
+		(Some(ctn_file), _) => Ok(ctn_file.get_file_list().clone()),
+		(None, Some(dir)) => Ok(dir.fingerprints(config, hash_func, tx)?),
+		(None, None) => Ok(HashedFileList::new()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::files::HashedFile;
+
+	const HASH_A: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+	const HASH_B: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+	const HASH_C: &str = "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a";
+
+	fn get_list(files: &[(&str, &str)]) -> HashedFileList {
+		let mut lst = HashedFileList::new();
+		for (path, hash) in files {
+			lst.insert_file(HashedFile::new(path, 4, hash, HashFunc::Sha256));
+		}
+		lst
+	}
+
+	fn get_comparison() -> Comparison {
+		let left = get_list(&[
+			("same.txt", HASH_A),
+			("changed.txt", HASH_A),
+			("old/moved.txt", HASH_B),
+			("removed.txt", HASH_C),
+		]);
+		let right = get_list(&[
+			("same.txt", HASH_A),
+			("changed.txt", HASH_C),
+			("new/moved.txt", HASH_B),
+			("added, new.txt", HASH_A),
+		]);
+		compare(
+			CompareSource::ContentFile(PathBuf::from("left.txt")),
+			CompareSource::Directory(PathBuf::from("right")),
+			HashFunc::Sha256,
+			&left,
+			&right,
+		)
+	}
+
+	#[test]
+	fn compare_lists() {
+		let cmp = get_comparison();
+		assert!(cmp.has_differences());
+		assert_eq!(cmp.get_identical(), [PathBuf::from("same.txt")]);
+		assert_eq!(cmp.get_modified(), [PathBuf::from("changed.txt")]);
+		assert_eq!(cmp.get_only_left(), [PathBuf::from("removed.txt")]);
+		assert_eq!(cmp.get_only_right(), [PathBuf::from("added, new.txt")]);
+		assert_eq!(cmp.get_moved().len(), 1);
+		assert_eq!(
+			cmp.get_moved()[0].get_receipt_path(),
+			Path::new("old/moved.txt")
+		);
+		assert_eq!(
+			cmp.get_moved()[0].get_local_path(),
+			Path::new("new/moved.txt")
+		);
+	}
+
+	#[test]
+	fn identical_lists() {
+		let lst = get_list(&[("a.txt", HASH_A), ("b.txt", HASH_B)]);
+		let cmp = compare(
+			CompareSource::Directory(PathBuf::from("left")),
+			CompareSource::Directory(PathBuf::from("right")),
+			HashFunc::Sha256,
+			&lst,
+			&lst,
+		);
+		assert!(!cmp.has_differences());
+		assert_eq!(cmp.get_identical().len(), 2);
+	}
+
+	#[test]
+	fn csv_report() {
+		let mut out = Vec::new();
+		get_comparison().write_csv(&mut out).unwrap();
+		let out = String::from_utf8(out).unwrap();
+		let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+		assert_eq!(
+			lines,
+			[
+				"status,left,right",
+				"identical,same.txt,same.txt",
+				"modified,changed.txt,changed.txt",
+				"moved,old/moved.txt,new/moved.txt",
+				"only-left,removed.txt,",
+				"only-right,,\"added, new.txt\"",
+			]
+		);
+	}
+}
@@ -9,7 +9,7 @@ mod progress_bar;
 mod root;
 
 pub use button::{Button, FileButton};
-pub use file_list::{FileListIndicator, FileListReceipt};
+pub use file_list::{receipt_format_name, FileListIndicator, FileListReceipt};
 pub use form::{ApplyConfig, Checkbox, Select, SelectOption};
 pub use header::{Header, HeaderLangSwitchDisplay};
 pub use main_section::MainSection;
@@ -10,6 +10,8 @@ pub enum ConfigMenuHighlight {
 	Hash,
 	Messages,
 	Clipboard,
+	ReceiptFormats,
+	KnownFiles,
 }
 
 #[component]
@@ -41,6 +43,18 @@ pub fn ConfigMenu(hl: ConfigMenuHighlight) -> Element {
 				hl: hl,
 				current: ConfigMenuHighlight::Clipboard
 			}
+			ConfigMenuElement {
+				target: Route::ReceiptFormatsConfig {},
+				target_str: "cpn_config_menu_receipt_formats_title",
+				hl: hl,
+				current: ConfigMenuHighlight::ReceiptFormats
+			}
+			ConfigMenuElement {
+				target: Route::KnownFilesConfig {},
+				target_str: "cpn_config_menu_known_files_title",
+				hl: hl,
+				current: ConfigMenuHighlight::KnownFiles
+			}
 		}
 	}
 }
@@ -1,10 +1,17 @@
 #![allow(non_snake_case)]
 
+use crate::components::config::ConfigElement;
+use crate::components::{Checkbox, Select, SelectOption};
 use crate::events::{send_event, ExternalEvent, ExternalEventSender};
 use crate::files::FileList;
-use crate::receipt::Receipt;
+use crate::parsers::{parse_bool, CustomReceiptFormat};
+use crate::path_matching::PathMatchingOptions;
+use crate::receipt::{Receipt, ReceiptFormat};
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 #[component]
 pub fn FileListIndicator() -> Element {
@@ -17,38 +24,50 @@ pub fn FileListIndicator() -> Element {
 
 #[component]
 pub fn FileListReceipt() -> Element {
+	let receipts_sig = use_context::<Signal<Vec<Receipt>>>();
 	rsx! {
-		FileListMeta {
-			is_receipt: true,
+		for (idx, rcpt) in receipts_sig().into_iter().enumerate() {
+			FileListIndicatorElement {
+				key: "{rcpt}",
+				path: rcpt.get_path().to_path_buf(),
+				is_receipt: true,
+			}
+			ReceiptFormatInfo {
+				idx: idx,
+				path: rcpt.get_path().to_path_buf(),
+			}
+			ReceiptPathMatching {
+				idx: idx,
+				path: rcpt.get_path().to_path_buf(),
+			}
 		}
 	}
 }
 
 #[component]
 fn FileListMeta(is_receipt: bool) -> Element {
-	let receipt_opt_sig = use_context::<Signal<Option<Receipt>>>();
 	let file_list = use_context::<Signal<FileList>>()();
-	let path_opt = if is_receipt {
-		receipt_opt_sig().map(|rcpt| rcpt.to_string())
-	} else {
-		match file_list {
-			FileList::NonHashed(lst) => Some(lst.get_base_dir().display().to_string()),
-			FileList::Hashed(lst) => Some(lst.get_base_dir().display().to_string()),
-			FileList::None => None,
-		}
+	let path_opt = match file_list {
+		FileList::NonHashed(lst) => Some(lst.get_base_dir().to_path_buf()),
+		FileList::Hashed(lst) => Some(lst.get_base_dir().to_path_buf()),
+		FileList::None => None,
 	};
 	rsx! {
 		if let Some(path) = path_opt {
 			FileListIndicatorElement {
-				path: "{path}",
+				path: path,
 				is_receipt: is_receipt,
 			}
 		}
 	}
 }
 
+fn get_receipt(receipts_sig: Signal<Vec<Receipt>>, path: &Path) -> Option<Receipt> {
+	receipts_sig().into_iter().find(|r| r.get_path() == path)
+}
+
 #[component]
-fn FileListIndicatorElement(path: String, is_receipt: bool) -> Element {
+fn FileListIndicatorElement(path: PathBuf, is_receipt: bool) -> Element {
 	let tx = use_context::<Signal<ExternalEventSender>>()();
 	let icon_class = if is_receipt {
 		"ri-mail-check-line"
@@ -63,17 +82,18 @@ fn FileListIndicatorElement(path: String, is_receipt: bool) -> Element {
 			}
 			span {
 				class: "component-file-list-content",
-				"{path}"
+				"{path.display()}"
 			}
 			span {
 				class: "component-file-list-delete ri-close-large-line",
 				title: tid!("cpn_file_list_delete"),
 				onclick: move |_| {
 					let txc = tx.clone();
+					let path = path.clone();
 					spawn(async move {
 						if is_receipt {
-							send_event(&txc, ExternalEvent::ReceiptReset);
-							tracing::info!("Removing receipt");
+							tracing::info!("Removing receipt: {}", path.display());
+							send_event(&txc, ExternalEvent::ReceiptRemove(path));
 						} else {
 							tracing::info!("Removing file list");
 							send_event(&txc, ExternalEvent::FileListReset);
@@ -84,3 +104,172 @@ fn FileListIndicatorElement(path: String, is_receipt: bool) -> Element {
 		}
 	}
 }
+
+#[component]
+fn ReceiptFormatInfo(idx: usize, path: PathBuf) -> Element {
+	let tx = use_context::<Signal<ExternalEventSender>>()();
+	let receipts_sig = use_context::<Signal<Vec<Receipt>>>();
+	let Some(receipt) = get_receipt(receipts_sig, &path) else {
+		return rsx! {};
+	};
+	let custom_formats = receipt.get_custom_formats();
+	let format = receipt.get_format();
+	let format_name = receipt_format_name(format.get_format(), custom_formats);
+	let selected_format = if receipt.is_format_forced() {
+		format.get_format().get_value()
+	} else {
+		String::new()
+	};
+	let mut format_opts = vec![SelectOption::new(
+		tid!("cpn_file_list_receipt_format_auto"),
+		String::new(),
+	)];
+	format_opts.extend(
+		ReceiptFormat::iter()
+			.chain((0..custom_formats.len()).map(ReceiptFormat::Custom))
+			.map(|f| SelectOption::new(receipt_format_name(f, custom_formats), f.get_value())),
+	);
+	let other_formats: Vec<String> = receipt
+		.get_other_candidates()
+		.map(|c| {
+			tid!(
+				"cpn_file_list_receipt_candidate",
+				format: receipt_format_name(c.get_format(), custom_formats),
+				confidence: c.get_confidence()
+			)
+		})
+		.collect();
+
+	rsx! {
+		div {
+			class: "component-file-list-receipt-format",
+			p {
+				if receipt.is_format_forced() {
+					{ tid!("cpn_file_list_receipt_format_forced", format: format_name) }
+				} else {
+					{ tid!("cpn_file_list_receipt_format", format: format_name, confidence: format.get_confidence()) }
+				}
+			}
+			if !other_formats.is_empty() {
+				p {
+					{ tid!("cpn_file_list_receipt_other_formats", formats: other_formats.join(", ")) }
+				}
+			}
+			label {
+				r#for: "cpn_file_list_receipt_format_select_{idx}",
+				{ tid!("cpn_file_list_receipt_format_select") }
+			}
+			Select {
+				id: "cpn_file_list_receipt_format_select_{idx}",
+				name: "cpn_file_list_receipt_format_select_{idx}",
+				options: format_opts,
+				selected_option: selected_format,
+				onchange: move |event: FormEvent| {
+					let format = ReceiptFormat::from_str(&event.data.value()).ok();
+					if let Some(receipt) = get_receipt(receipts_sig, &path) {
+						let txc = tx.clone();
+						std::thread::spawn(move || {
+							send_event(&txc, ExternalEvent::ReceiptErrorReset);
+							send_event(&txc, ExternalEvent::LoadingBarAdd);
+							match receipt.reload(format) {
+								Ok(new_receipt) => {
+									send_event(&txc, ExternalEvent::ReceiptSet(new_receipt));
+								}
+								Err(e) => {
+									tracing::error!("Unable to reload receipt: {receipt}: {e}");
+									send_event(&txc, ExternalEvent::ReceiptErrorSet(e));
+								}
+							}
+							send_event(&txc, ExternalEvent::LoadingBarDelete);
+						});
+					}
+				},
+			}
+		}
+	}
+}
+
+pub fn receipt_format_name(
+	format: ReceiptFormat,
+	custom_formats: &[CustomReceiptFormat],
+) -> String {
+	tid!(
+		format.get_tid_key(),
+		name: format.get_custom_name(custom_formats)
+	)
+}
+
+#[component]
+fn ReceiptPathMatching(idx: usize, path: PathBuf) -> Element {
+	let tx = use_context::<Signal<ExternalEventSender>>()();
+	let receipts_sig = use_context::<Signal<Vec<Receipt>>>();
+	let Some(receipt) = get_receipt(receipts_sig, &path) else {
+		return rsx! {};
+	};
+	let path_sep = path.clone();
+	let path_unicode = path.clone();
+	let opts = *receipt.get_path_matching();
+	let tx_sep = tx.clone();
+	let tx_unicode = tx.clone();
+
+	rsx! {
+		details {
+			class: "component-file-list-receipt-path-matching",
+			summary {
+				{ tid!("cpn_file_list_receipt_path_matching") }
+			}
+			ConfigElement {
+				id: "cpn_file_list_receipt_path_separators_{idx}",
+				label: tid!("cpn_file_list_receipt_path_separators"),
+				Checkbox {
+					id: "cpn_file_list_receipt_path_separators_{idx}",
+					name: "cpn_file_list_receipt_path_separators_{idx}",
+					checked: opts.normalize_separators,
+					onchange: move |event: FormEvent| {
+						let value = parse_bool(&event.data.value());
+						update_path_matching(&tx_sep, get_receipt(receipts_sig, &path_sep), |o| o.normalize_separators = value);
+					},
+				}
+			}
+			ConfigElement {
+				id: "cpn_file_list_receipt_path_unicode_{idx}",
+				label: tid!("cpn_file_list_receipt_path_unicode"),
+				Checkbox {
+					id: "cpn_file_list_receipt_path_unicode_{idx}",
+					name: "cpn_file_list_receipt_path_unicode_{idx}",
+					checked: opts.normalize_unicode,
+					onchange: move |event: FormEvent| {
+						let value = parse_bool(&event.data.value());
+						update_path_matching(&tx_unicode, get_receipt(receipts_sig, &path_unicode), |o| o.normalize_unicode = value);
+					},
+				}
+			}
+			ConfigElement {
+				id: "cpn_file_list_receipt_path_case_{idx}",
+				label: tid!("cpn_file_list_receipt_path_case"),
+				Checkbox {
+					id: "cpn_file_list_receipt_path_case_{idx}",
+					name: "cpn_file_list_receipt_path_case_{idx}",
+					checked: opts.case_insensitive,
+					onchange: move |event: FormEvent| {
+						let value = parse_bool(&event.data.value());
+						update_path_matching(&tx, get_receipt(receipts_sig, &path), |o| o.case_insensitive = value);
+					},
+				}
+			}
+		}
+	}
+}
+
+fn update_path_matching<F>(tx: &ExternalEventSender, receipt_opt: Option<Receipt>, update: F)
+where
+	F: FnOnce(&mut PathMatchingOptions),
+{
+	if let Some(mut receipt) = receipt_opt {
+		let mut opts = *receipt.get_path_matching();
+		update(&mut opts);
+		tracing::info!("Receipt path matching options: {opts:?}");
+		receipt.set_path_matching(opts);
+		send_event(tx, ExternalEvent::ReceiptSet(receipt));
+	}
+}
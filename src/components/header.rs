@@ -9,7 +9,7 @@ pub use self::lang_switch::HeaderLangSwitchDisplay;
 use self::lang_switch::LangSwitch;
 use self::logo::Logo;
 use self::theme_switch::ThemeSwitch;
-use crate::app::Route::FilesConfig;
+use crate::app::Route::{FilesConfig, Logs, Tools};
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
 
@@ -27,12 +27,32 @@ pub fn Header() -> Element {
 				menu {
 					class: "component-header-menu",
 					DebugLink {}
+					li {
+						span {
+							class: "component-header-menu-item",
+							Link {
+								class: "ri-file-list-3-line",
+								to: Logs {},
+								title: tid!("cpn_header_logs"),
+							}
+						}
+					}
 					li {
 						LangSwitch {}
 					}
 					li {
 						ThemeSwitch {}
 					}
+					li {
+						span {
+							class: "component-header-menu-item",
+							Link {
+								class: "ri-tools-line",
+								to: Tools {},
+								title: tid!("cpn_header_tools"),
+							}
+						}
+					}
 					li {
 						span {
 							class: "component-header-menu-item",
@@ -4,6 +4,7 @@ use crate::app::Route;
 use crate::components::Button;
 use crate::config::Config;
 use crate::files::FileList;
+use crate::known_files::KnownFileKind;
 use crate::notifications::{NotificationBlackList, NotificationLevel};
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
@@ -14,6 +15,13 @@ pub fn NotificationList() -> Element {
 	let cfg = use_context::<Signal<Config>>()();
 	let nb_empty_files = fl.nb_empty_files();
 	let nb_excluded_files = fl.nb_excluded_files();
+	let nb_known_bad_files = fl.nb_known_files(KnownFileKind::Bad);
+	let nb_known_good_files = fl.nb_known_files(KnownFileKind::Good);
+	let nb_previous_evidence = fl.nb_previous_evidence();
+	let unprotected_files = fl.get_unprotected_files();
+	let nb_metadata_changes = fl.get_metadata_changes().len();
+	let case_write_error = fl.get_case_write_error();
+	let protection_error = fl.get_protection_error();
 
 	rsx! {
 		if cfg.is_empty_file_warning_enabled() && nb_empty_files != 0 {
@@ -66,6 +74,106 @@ pub fn NotificationList() -> Element {
 				}
 			}
 		}
+
+		if nb_previous_evidence != 0 {
+			Notification {
+				id: "previous_evidence_{fl.get_id()}",
+				level: NotificationLevel::Warning,
+				title: tid!("cpn_notif_previous_evidence_title", nb: nb_previous_evidence),
+				p { { tid!("cpn_notif_previous_evidence_text", nb: nb_previous_evidence) } }
+				p {
+					Button {
+						onclick: move |_event| {
+							navigator().push(Route::DuplicatedFiles {});
+						},
+						{ tid!("cpn_notif_duplicated_files_link") }
+					}
+				}
+			}
+		}
+
+		if !unprotected_files.is_empty() {
+			Notification {
+				id: "unprotected_files_{fl.get_id()}",
+				level: NotificationLevel::Error,
+				title: tid!("cpn_notif_unprotected_files_title", nb: unprotected_files.len()),
+				p { { tid!("cpn_notif_unprotected_files_text", nb: unprotected_files.len()) } }
+				ul {
+					for path in unprotected_files {
+						li { "{path.display()}" }
+					}
+				}
+			}
+		}
+
+		if let Some(msg) = case_write_error {
+			Notification {
+				id: "case_write_error_{fl.get_id()}",
+				level: NotificationLevel::Error,
+				title: tid!("cpn_notif_case_write_error_title"),
+				p { { tid!("cpn_notif_case_write_error_text", msg: msg) } }
+			}
+		}
+
+		if let Some(msg) = protection_error {
+			Notification {
+				id: "protection_error_{fl.get_id()}",
+				level: NotificationLevel::Error,
+				title: tid!("cpn_notif_protection_error_title"),
+				p { { tid!("cpn_notif_protection_error_text", msg: msg) } }
+			}
+		}
+
+		if nb_metadata_changes != 0 {
+			Notification {
+				id: "metadata_changes_{fl.get_id()}",
+				level: NotificationLevel::Warning,
+				title: tid!("cpn_notif_metadata_changes_title"),
+				p { { tid!("cpn_notif_metadata_changes_text", nb: nb_metadata_changes) } }
+				p {
+					Button {
+						onclick: move |_event| {
+							navigator().push(Route::MetadataChanges {});
+						},
+						{ tid!("cpn_notif_metadata_changes_link") }
+					}
+				}
+			}
+		}
+
+		if nb_known_bad_files != 0 {
+			Notification {
+				id: "known_bad_files_{fl.get_id()}",
+				level: NotificationLevel::Error,
+				title: tid!("cpn_notif_known_bad_files_title", nb: nb_known_bad_files),
+				p { { tid!("cpn_notif_known_bad_files_text", nb: nb_known_bad_files) } }
+				p {
+					Button {
+						onclick: move |_event| {
+							navigator().push(Route::KnownFiles {});
+						},
+						{ tid!("cpn_notif_known_files_link") }
+					}
+				}
+			}
+		}
+
+		if nb_known_good_files != 0 {
+			Notification {
+				id: "known_good_files_{fl.get_id()}",
+				level: NotificationLevel::Info,
+				title: tid!("cpn_notif_known_good_files_title", nb: nb_known_good_files),
+				p { { tid!("cpn_notif_known_good_files_text", nb: nb_known_good_files) } }
+				p {
+					Button {
+						onclick: move |_event| {
+							navigator().push(Route::KnownFiles {});
+						},
+						{ tid!("cpn_notif_known_files_link") }
+					}
+				}
+			}
+		}
 	}
 }
 
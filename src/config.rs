@@ -2,6 +2,9 @@ use crate::clipboard::ClipboardPersistence;
 use crate::content_file_format::ContentFileFormat;
 use crate::hash::HashFunc;
 use crate::i18n::Lang;
+use crate::known_files::KnownHashSet;
+use crate::logging::LogLevel;
+use crate::parsers::{CustomReceiptFormat, ParseMode};
 use crate::theme::Theme;
 use serde_derive::{Deserialize, Serialize};
 use std::fs::{create_dir_all, read_to_string, File};
@@ -19,11 +22,18 @@ pub struct Config {
 	pub include_hidden_files: Option<bool>,
 	pub include_system_files: Option<bool>,
 	pub set_files_as_readonly: Option<bool>,
+	pub lock_directories: Option<bool>,
+	pub capture_metadata: Option<bool>,
+	pub enable_history: Option<bool>,
+	pub log_level: Option<LogLevel>,
 
 	// Fingerprints
 	pub hash_function: HashFunc,
 	pub content_file_format: ContentFileFormat,
 	pub content_file_name: Option<String>,
+	pub content_file_trailer: Option<bool>,
+	pub content_file_lenient_parsing: Option<bool>,
+	pub content_file_dir: Option<PathBuf>,
 
 	// Messages
 	pub enable_duplicate_file_warning: Option<bool>,
@@ -36,6 +46,12 @@ pub struct Config {
 	pub clipboard_tpl_txt_ctn_file: Option<String>,
 	pub clipboard_tpl_html_list: Option<String>,
 	pub clipboard_tpl_txt_list: Option<String>,
+
+	// Receipts
+	pub custom_receipt_formats: Vec<CustomReceiptFormat>,
+
+	// Known files
+	pub known_hash_sets: Vec<KnownHashSet>,
 }
 
 impl Config {
@@ -57,7 +73,7 @@ impl Config {
 		}
 	}
 
-	fn get_file_path() -> PathBuf {
+	pub fn get_config_dir() -> PathBuf {
 		let mut path = match dirs::config_dir() {
 			Some(p) => p,
 			None => PathBuf::new(),
@@ -67,6 +83,11 @@ impl Config {
 		if !path.is_dir() {
 			let _ = create_dir_all(&path);
 		}
+		path
+	}
+
+	fn get_file_path() -> PathBuf {
+		let mut path = Config::get_config_dir();
 		path.push(crate::CONFIG_FILE_NAME);
 		path
 	}
@@ -89,6 +110,23 @@ impl Config {
 		self.set_files_as_readonly.unwrap_or(true)
 	}
 
+	// Windows ignores the read-only attribute on directories, hence this option has no effect there.
+	pub fn lock_directories(&self) -> bool {
+		cfg!(unix) && self.lock_directories.unwrap_or(false)
+	}
+
+	pub fn is_metadata_capture_enabled(&self) -> bool {
+		self.capture_metadata.unwrap_or(false)
+	}
+
+	pub fn is_history_enabled(&self) -> bool {
+		self.enable_history.unwrap_or(false)
+	}
+
+	pub fn get_log_level(&self) -> LogLevel {
+		self.log_level.unwrap_or_default()
+	}
+
 	// Fingerprints
 
 	pub fn get_content_file_name(&self) -> String {
@@ -100,6 +138,22 @@ impl Config {
 		}
 	}
 
+	pub fn has_content_file_trailer(&self) -> bool {
+		self.content_file_trailer.unwrap_or(false)
+	}
+
+	pub fn has_content_file_lenient_parsing(&self) -> bool {
+		self.content_file_lenient_parsing.unwrap_or(false)
+	}
+
+	pub fn get_parse_mode(&self) -> ParseMode {
+		if self.has_content_file_lenient_parsing() {
+			ParseMode::Lenient
+		} else {
+			ParseMode::Strict
+		}
+	}
+
 	// Messages
 
 	pub fn is_duplicate_file_warning_enabled(&self) -> bool {
@@ -135,9 +189,11 @@ lang = "fr"
 number_representation = "letters"
 content_file_format = "cksum-gnu"
 hash_function = "sha-512"
+log_level = "debug"
 "#;
 		let cfg = Config::load_config(s);
 		assert_eq!(cfg.theme, Some(Theme::Dark));
+		assert_eq!(cfg.get_log_level(), LogLevel::Debug);
 		assert_eq!(cfg.lang, langid!("fr").into());
 		assert_eq!(cfg.hash_function, HashFunc::Sha512);
 		assert_eq!(cfg.content_file_name, None);
@@ -167,6 +223,7 @@ hash_function = "sha-256"
 	fn test_empty_config() {
 		let cfg = Config::load_config("");
 		assert_eq!(cfg.theme, None);
+		assert_eq!(cfg.get_log_level(), LogLevel::Info);
 		assert_eq!(cfg.lang, Lang::default());
 		assert_eq!(cfg.hash_function, HashFunc::default());
 		assert_eq!(cfg.content_file_name, None);
@@ -177,6 +234,29 @@ hash_function = "sha-256"
 		);
 	}
 
+	#[test]
+	fn test_custom_receipt_formats() {
+		let s = r#"
+hash_function = "sha-256"
+
+[[custom_receipt_formats]]
+name = "Example platform"
+line_regex = '^(?P<hash>[0-9a-f]+)\s+(?P<path>.+)$'
+begin_marker = "BEGIN"
+hash_function = "sha-512"
+"#;
+		let cfg = Config::load_config(s);
+		assert_eq!(cfg.custom_receipt_formats.len(), 1);
+		let fmt = &cfg.custom_receipt_formats[0];
+		assert_eq!(fmt.name, "Example platform");
+		assert_eq!(fmt.begin_marker, Some("BEGIN".to_string()));
+		assert_eq!(fmt.end_marker, None);
+		assert_eq!(fmt.hash_function, Some(HashFunc::Sha512));
+		let cfg = Config::load_config(&toml::to_string(&cfg).unwrap());
+		assert_eq!(cfg.custom_receipt_formats.len(), 1);
+		assert_eq!(cfg.custom_receipt_formats[0].line_regex, fmt.line_regex);
+	}
+
 	#[test]
 	fn test_invalid_config() {
 		let s = r#"
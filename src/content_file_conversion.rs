@@ -0,0 +1,255 @@
+use crate::content_file_format::ContentFileFormat;
+use crate::files::{HashedFile, HashedFileList};
+use crate::hash::HashFunc;
+use crate::parsers::ParseMode;
+use crate::receipt::Receipt;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConversionError {
+	DestinationExists(PathBuf),
+	Io(String),
+	MissingSizes,
+	MixedHashFunctions,
+	Parse,
+	UnknownSizes(Vec<PathBuf>),
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DestinationExists(p) => write!(f, "{}: file already exists", p.display()),
+			Self::Io(e) => write!(f, "I/O error: {e}"),
+			Self::MissingSizes => write!(f, "the source content file does not contain file sizes"),
+			Self::MixedHashFunctions => {
+				write!(f, "the source content file uses several hashing functions")
+			}
+			Self::Parse => write!(f, "unable to parse the source content file"),
+			Self::UnknownSizes(paths) => {
+				write!(
+					f,
+					"unable to read the size of {} files from disk",
+					paths.len()
+				)
+			}
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct ConversionOptions {
+	pub format: ContentFileFormat,
+	pub file_name: String,
+	pub sizes_from_disk: bool,
+	pub with_trailer: bool,
+	pub parse_mode: ParseMode,
+}
+
+// The default name of the converted content file depends on the hashing function used in the
+// source, which may differ from the configured one.
+pub fn get_source_hash_func(src: &Path, default_hash: HashFunc, parse_mode: ParseMode) -> HashFunc {
+	match Receipt::new(src, default_hash, parse_mode, &[]) {
+		Ok(receipt) => receipt.get_main_hashing_function(),
+		Err(_) => default_hash,
+	}
+}
+
+pub fn convert_content_file(
+	src: &Path,
+	default_hash: HashFunc,
+	options: &ConversionOptions,
+) -> Result<PathBuf, ConversionError> {
+	tracing::info!(
+		"Converting content file {} to {}",
+		src.display(),
+		options.format
+	);
+	let receipt = Receipt::new(src, default_hash, options.parse_mode, &[])
+		.map_err(|_| ConversionError::Parse)?;
+	let base_dir = src.parent().unwrap_or(Path::new(""));
+	let dest = base_dir.join(&options.file_name);
+	if dest.exists() {
+		return Err(ConversionError::DestinationExists(dest));
+	}
+	let files = prepare_file_list(
+		receipt.get_file_list(),
+		options.format,
+		base_dir,
+		options.sizes_from_disk,
+	)?;
+	let mut f = File::create_new(&dest).map_err(|e| ConversionError::Io(e.to_string()))?;
+	options
+		.format
+		.write_content_file(&mut f, &files, options.with_trailer)
+		.map_err(|e| ConversionError::Io(e.to_string()))?;
+	tracing::info!("Content file converted: {}", dest.display());
+	Ok(dest)
+}
+
+// The CNIL format stores the file sizes and a single hashing function in its header, while the
+// GNU variant of the cksum format does not store the hashing function at all: the source content
+// file must therefore be checked before being written in such formats. Rounded sizes, such as the
+// ones displayed in the platform's emails, cannot be used. Sizes which cannot be read from disk
+// are unknown and reported as such, since writing them would make the next check fail.
+fn prepare_file_list(
+	src_files: &HashedFileList,
+	format: ContentFileFormat,
+	base_dir: &Path,
+	sizes_from_disk: bool,
+) -> Result<HashedFileList, ConversionError> {
+	let hash_funcs: HashSet<HashFunc> = src_files.get_files().map(|f| f.get_hash_func()).collect();
+	if hash_funcs.len() > 1 && format != ContentFileFormat::CksumBsd {
+		return Err(ConversionError::MixedHashFunctions);
+	}
+	let has_sizes = src_files.get_files().any(|f| f.get_size() != 0)
+		&& src_files.get_files().all(|f| f.get_size_tolerance() == 0);
+	if format != ContentFileFormat::Cnil || has_sizes || src_files.is_empty() {
+		return Ok(src_files.clone());
+	}
+	if !sizes_from_disk {
+		return Err(ConversionError::MissingSizes);
+	}
+	let mut files = HashedFileList::new();
+	let mut unknown_sizes = Vec::new();
+	for file in src_files.get_files() {
+		let path = base_dir.join(file.get_relative_path());
+		match path.metadata() {
+			Ok(metadata) if metadata.is_file() => {
+				files.insert_file(HashedFile::new(
+					file.get_relative_path(),
+					metadata.len(),
+					file.get_hash(),
+					file.get_hash_func(),
+				));
+			}
+			_ => {
+				tracing::error!("{}: unable to read the file size", path.display());
+				unknown_sizes.push(file.get_relative_path().to_path_buf());
+			}
+		}
+	}
+	if !unknown_sizes.is_empty() {
+		unknown_sizes.sort();
+		return Err(ConversionError::UnknownSizes(unknown_sizes));
+	}
+	Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+	fn get_list(files: &[(&str, u64, HashFunc)]) -> HashedFileList {
+		let mut lst = HashedFileList::new();
+		for (name, size, hash_func) in files {
+			lst.insert_file(HashedFile::new(name, *size, HASH, *hash_func));
+		}
+		lst
+	}
+
+	#[test]
+	fn cksum_to_cksum() {
+		let lst = get_list(&[
+			("a.txt", 0, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Sha256),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::CksumGnu, Path::new(""), false);
+		assert!(res.is_ok());
+		assert_eq!(res.unwrap().len(None), 2);
+	}
+
+	#[test]
+	fn mixed_to_bsd() {
+		let lst = get_list(&[
+			("a.txt", 0, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Blake3),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::CksumBsd, Path::new(""), false);
+		assert!(res.is_ok());
+	}
+
+	#[test]
+	fn mixed_to_gnu() {
+		let lst = get_list(&[
+			("a.txt", 0, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Blake3),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::CksumGnu, Path::new(""), false);
+		assert_eq!(res.unwrap_err(), ConversionError::MixedHashFunctions);
+	}
+
+	#[test]
+	fn mixed_to_cnil() {
+		let lst = get_list(&[
+			("a.txt", 4, HashFunc::Sha256),
+			("b.txt", 2, HashFunc::Sha512),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, Path::new(""), false);
+		assert_eq!(res.unwrap_err(), ConversionError::MixedHashFunctions);
+	}
+
+	#[test]
+	fn cnil_to_cnil() {
+		let lst = get_list(&[
+			("a.txt", 4, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Sha256),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, Path::new(""), false);
+		assert!(res.is_ok());
+	}
+
+	#[test]
+	fn missing_sizes() {
+		let lst = get_list(&[
+			("a.txt", 0, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Sha256),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, Path::new(""), false);
+		assert_eq!(res.unwrap_err(), ConversionError::MissingSizes);
+	}
+
+	#[test]
+	fn rounded_sizes() {
+		let mut lst = HashedFileList::new();
+		lst.insert_file(
+			HashedFile::new("a.txt", 405, HASH, HashFunc::Sha256).with_size_tolerance(56),
+		);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, Path::new(""), false);
+		assert_eq!(res.unwrap_err(), ConversionError::MissingSizes);
+	}
+
+	#[test]
+	fn missing_sizes_not_on_disk() {
+		let base_dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(&base_dir).unwrap();
+		std::fs::write(base_dir.join("a.txt"), "test").unwrap();
+		let lst = get_list(&[
+			("a.txt", 0, HashFunc::Sha256),
+			("b.txt", 0, HashFunc::Sha256),
+		]);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, &base_dir, true);
+		std::fs::remove_dir_all(&base_dir).unwrap();
+		assert_eq!(
+			res.unwrap_err(),
+			ConversionError::UnknownSizes(vec![PathBuf::from("b.txt")])
+		);
+	}
+
+	#[test]
+	fn sizes_from_disk() {
+		let base_dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(base_dir.join("dir")).unwrap();
+		std::fs::write(base_dir.join("dir").join("a.txt"), "test").unwrap();
+		let lst = get_list(&[("dir/a.txt", 0, HashFunc::Sha256)]);
+		let res = prepare_file_list(&lst, ContentFileFormat::Cnil, &base_dir, true);
+		std::fs::remove_dir_all(&base_dir).unwrap();
+		let res = res.unwrap();
+		let file = res.get_files().next().unwrap();
+		assert_eq!(file.get_size(), 4);
+	}
+}
@@ -1,10 +1,11 @@
+use crate::content_file_trailer::format_trailer;
 use crate::files::HashedFileList;
 use crate::hash::HashFunc;
 use crate::serializers::{ctn_file_cksum_bsd, ctn_file_cksum_gnu, ctn_file_cnil};
 use serde_derive::{Deserialize, Serialize};
 use std::fmt;
 use std::fs::File;
-use std::io;
+use std::io::{self, Write};
 use strum::EnumIter;
 
 #[derive(Clone, Copy, Debug, Default, EnumIter, Deserialize, PartialEq, Serialize)]
@@ -67,12 +68,20 @@ impl ContentFileFormat {
 		&self,
 		ctn_file: &mut File,
 		hashed_list: &HashedFileList,
+		with_trailer: bool,
 	) -> io::Result<()> {
+		let mut content = Vec::new();
 		match self {
-			Self::CksumBsd => ctn_file_cksum_bsd(ctn_file, hashed_list),
-			Self::CksumGnu => ctn_file_cksum_gnu(ctn_file, hashed_list),
-			Self::Cnil => ctn_file_cnil(ctn_file, hashed_list),
+			Self::CksumBsd => ctn_file_cksum_bsd(&mut content, hashed_list)?,
+			Self::CksumGnu => ctn_file_cksum_gnu(&mut content, hashed_list)?,
+			Self::Cnil => ctn_file_cnil(&mut content, hashed_list)?,
+		};
+		if with_trailer {
+			let hash_func = hashed_list.get_main_hashing_function();
+			let trailer = format_trailer(*self, hash_func, &content);
+			content.extend_from_slice(trailer.as_bytes());
 		}
+		ctn_file.write_all(&content)
 	}
 }
 
@@ -0,0 +1,244 @@
+use crate::config::Config;
+use crate::hash::HashFunc;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const DIR_ID_LEN: usize = 8;
+
+// The associations are read from the disk only once, they are then kept up to date in memory each
+// time they are saved.
+static ASSOCIATIONS: Mutex<Option<ContentFileAssociations>> = Mutex::new(None);
+
+// Content files written outside of the evidence directory are recorded in a file located in the
+// configuration directory so they can be found again when the same directory is opened later.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContentFileAssociations {
+	#[serde(skip)]
+	path: PathBuf,
+	associations: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl ContentFileAssociations {
+	fn get_cached() -> Self {
+		let mut cache = ASSOCIATIONS.lock().unwrap_or_else(|e| e.into_inner());
+		cache
+			.get_or_insert_with(|| {
+				Self::load(
+					&Config::get_config_dir().join(crate::CONTENT_FILE_ASSOCIATIONS_FILE_NAME),
+				)
+			})
+			.clone()
+	}
+
+	fn load(path: &Path) -> Self {
+		let ctn = if path.is_file() {
+			read_to_string(path).unwrap_or_default()
+		} else {
+			String::new()
+		};
+		let mut associations = Self::parse(&ctn);
+		associations.path = path.to_path_buf();
+		associations
+	}
+
+	pub fn save(&self) -> io::Result<()> {
+		let ctn = toml::to_string(&self).map_err(io::Error::other)?;
+		let mut f = File::create(&self.path)?;
+		f.write_all(ctn.as_bytes())?;
+		let mut cache = ASSOCIATIONS.lock().unwrap_or_else(|e| e.into_inner());
+		if cache.as_ref().is_some_and(|c| c.path == self.path) {
+			*cache = Some(self.clone());
+		}
+		Ok(())
+	}
+
+	pub fn get(&self, base_dir: &Path) -> Option<&Path> {
+		self.associations.get(base_dir).map(|p| p.as_path())
+	}
+
+	pub fn set(&mut self, base_dir: &Path, ctn_file_path: &Path) {
+		self.associations
+			.insert(base_dir.to_path_buf(), ctn_file_path.to_path_buf());
+	}
+
+	fn parse(content: &str) -> Self {
+		toml::from_str(content).unwrap_or_default()
+	}
+}
+
+pub fn resolve_content_file_path(base_dir: &Path, config: &Config) -> io::Result<PathBuf> {
+	resolve_with(base_dir, config, &ContentFileAssociations::get_cached())
+}
+
+// The content file is looked up, in order, in the associations, in the external content file
+// directory and finally in the evidence directory itself. If none exists, the content file will be
+// written in the external content file directory, if any.
+fn resolve_with(
+	base_dir: &Path,
+	config: &Config,
+	associations: &ContentFileAssociations,
+) -> io::Result<PathBuf> {
+	let base_dir = base_dir.canonicalize()?;
+	let candidates = get_candidates(&base_dir, config, associations);
+	match candidates.iter().find(|p| p.is_file()) {
+		Some(path) => Ok(path.to_path_buf()),
+		None => Ok(default_content_file_path(&base_dir, config)),
+	}
+}
+
+fn get_candidates(
+	base_dir: &Path,
+	config: &Config,
+	associations: &ContentFileAssociations,
+) -> Vec<PathBuf> {
+	let ctn_file_name = config.get_content_file_name();
+	let mut candidates = Vec::new();
+	if let Some(path) = associations.get(base_dir) {
+		candidates.push(path.to_path_buf());
+	}
+	if let Some(dir) = &config.content_file_dir {
+		candidates.push(dir.join(external_dir_name(base_dir)).join(&ctn_file_name));
+	}
+	candidates.push(base_dir.join(ctn_file_name));
+	candidates
+}
+
+pub fn remember_content_file_path(base_dir: &Path, ctn_file_path: &Path) -> io::Result<()> {
+	remember_with(
+		base_dir,
+		ctn_file_path,
+		ContentFileAssociations::get_cached(),
+	)
+}
+
+fn remember_with(
+	base_dir: &Path,
+	ctn_file_path: &Path,
+	mut associations: ContentFileAssociations,
+) -> io::Result<()> {
+	let base_dir = base_dir.canonicalize()?;
+	if ctn_file_path.parent() == Some(base_dir.as_path()) {
+		return Ok(());
+	}
+	if associations.get(&base_dir) == Some(ctn_file_path) {
+		return Ok(());
+	}
+	tracing::info!(
+		"Associating {} with content file {}",
+		base_dir.display(),
+		ctn_file_path.display()
+	);
+	associations.set(&base_dir, ctn_file_path);
+	associations.save()
+}
+
+fn default_content_file_path(base_dir: &Path, config: &Config) -> PathBuf {
+	let ctn_file_name = config.get_content_file_name();
+	match &config.content_file_dir {
+		Some(dir) => dir.join(external_dir_name(base_dir)).join(ctn_file_name),
+		None => base_dir.join(ctn_file_name),
+	}
+}
+
+// Several evidence directories may share the same name, hence the short fingerprint of the full
+// path appended to the directory's name.
+pub fn external_dir_name(base_dir: &Path) -> String {
+	let name = base_dir
+		.file_name()
+		.map(|n| n.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let path = base_dir.as_os_str().as_encoded_bytes();
+	let mut id = HashFunc::Sha256.hash_bytes(path);
+	id.truncate(DIR_ID_LEN);
+	format!("{name}-{id}")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn associations() {
+		let mut assoc = ContentFileAssociations::default();
+		assoc.set(Path::new("/tmp/a"), Path::new("/tmp/out/contenu.txt"));
+		let ctn = toml::to_string(&assoc).unwrap();
+		let assoc = ContentFileAssociations::parse(&ctn);
+		assert_eq!(
+			assoc.get(Path::new("/tmp/a")),
+			Some(Path::new("/tmp/out/contenu.txt"))
+		);
+		assert_eq!(assoc.get(Path::new("/tmp/b")), None);
+	}
+
+	#[test]
+	fn invalid_associations() {
+		let assoc = ContentFileAssociations::parse("not toml");
+		assert_eq!(assoc.get(Path::new("/tmp/a")), None);
+	}
+
+	#[test]
+	fn default_path() {
+		let cfg = Config::default();
+		let path = default_content_file_path(Path::new("/tmp/a"), &cfg);
+		assert_eq!(path, Path::new("/tmp/a").join(cfg.get_content_file_name()));
+	}
+
+	#[test]
+	fn external_path() {
+		let cfg = Config {
+			content_file_dir: Some(PathBuf::from("/tmp/out")),
+			..Config::default()
+		};
+		let path_a = default_content_file_path(Path::new("/tmp/a/evidence"), &cfg);
+		let path_b = default_content_file_path(Path::new("/tmp/b/evidence"), &cfg);
+		assert!(path_a.starts_with("/tmp/out"));
+		assert_eq!(path_a.file_name(), path_b.file_name());
+		assert_ne!(path_a, path_b);
+		let dir_name = path_a.parent().unwrap().file_name().unwrap();
+		assert!(dir_name.to_string_lossy().starts_with("evidence-"));
+	}
+
+	#[test]
+	fn existing_path() {
+		let base_dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(&base_dir).unwrap();
+		let base_dir = base_dir.canonicalize().unwrap();
+		let cfg = Config {
+			content_file_dir: Some(base_dir.join("out")),
+			..Config::default()
+		};
+		let assoc = ContentFileAssociations::load(&base_dir.join("content_files.toml"));
+		let path = resolve_with(&base_dir, &cfg, &assoc).unwrap();
+		assert!(path.starts_with(base_dir.join("out")));
+		let ctn_file = base_dir.join(cfg.get_content_file_name());
+		std::fs::write(&ctn_file, "").unwrap();
+		assert_eq!(resolve_with(&base_dir, &cfg, &assoc).unwrap(), ctn_file);
+		std::fs::remove_dir_all(&base_dir).unwrap();
+	}
+
+	#[test]
+	fn remembered_path() {
+		let base_dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(base_dir.join("evidence")).unwrap();
+		std::fs::create_dir_all(base_dir.join("elsewhere")).unwrap();
+		let base_dir = base_dir.canonicalize().unwrap();
+		let evidence_dir = base_dir.join("evidence");
+		let ctn_file = base_dir.join("elsewhere").join("contenu.txt");
+		std::fs::write(&ctn_file, "").unwrap();
+		let assoc_path = base_dir.join("content_files.toml");
+		let assoc = ContentFileAssociations::load(&assoc_path);
+		remember_with(&evidence_dir, &ctn_file, assoc).unwrap();
+		let assoc = ContentFileAssociations::load(&assoc_path);
+		assert_eq!(assoc.get(&evidence_dir), Some(ctn_file.as_path()));
+		assert_eq!(
+			resolve_with(&evidence_dir, &Config::default(), &assoc).unwrap(),
+			ctn_file
+		);
+		std::fs::remove_dir_all(&base_dir).unwrap();
+	}
+}
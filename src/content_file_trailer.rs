@@ -0,0 +1,165 @@
+use crate::content_file_format::ContentFileFormat;
+use crate::hash::HashFunc;
+use nom::bytes::complete::{tag, take_till1};
+use nom::character::complete::{char, hex_digit1, one_of};
+use nom::combinator::{eof, fail, opt};
+use nom::multi::many1;
+use nom::{IResult, Parser};
+use std::path::Path;
+use std::str::FromStr;
+
+const TRAILER_PREFIX: &str = "# SELF-";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrailerError {
+	Malformed,
+	Mismatch,
+}
+
+pub fn format_trailer(format: ContentFileFormat, hash_func: HashFunc, content: &[u8]) -> String {
+	let hash = hash_func.hash_bytes(content);
+	match format {
+		ContentFileFormat::CksumBsd | ContentFileFormat::CksumGnu => {
+			format!("{TRAILER_PREFIX}{hash_func}: {hash}\n")
+		}
+		ContentFileFormat::Cnil => format!("{TRAILER_PREFIX}{hash_func}\t\t{hash}\r\n"),
+	}
+}
+
+// Returns the content file without its trailer, if any. The trailer's digest is checked against
+// every byte that precedes it.
+pub fn strip_trailer(raw: &[u8]) -> Result<&[u8], TrailerError> {
+	let (content, trailer) = split_last_line(raw);
+	let trailer = match std::str::from_utf8(trailer) {
+		Ok(t) if t.starts_with(TRAILER_PREFIX) => t,
+		_ => return Ok(raw),
+	};
+	let (_, (hash_func, hash)) = parse_trailer(trailer).map_err(|_| TrailerError::Malformed)?;
+	if hash_func.hash_bytes(content) != hash.to_ascii_lowercase() {
+		tracing::warn!("content file trailer: digest mismatch");
+		return Err(TrailerError::Mismatch);
+	}
+	Ok(content)
+}
+
+pub fn has_invalid_trailer(path: &Path) -> bool {
+	match std::fs::read(path) {
+		Ok(raw) => strip_trailer(&raw).is_err(),
+		Err(_) => false,
+	}
+}
+
+fn split_last_line(raw: &[u8]) -> (&[u8], &[u8]) {
+	let mut end = raw.len();
+	if raw[..end].ends_with(b"\n") {
+		end -= 1;
+	}
+	if raw[..end].ends_with(b"\r") {
+		end -= 1;
+	}
+	match raw[..end].iter().rposition(|c| *c == b'\n') {
+		Some(pos) => (&raw[..pos + 1], &raw[pos + 1..]),
+		None => (&raw[..0], raw),
+	}
+}
+
+fn parse_trailer(input: &str) -> IResult<&str, (HashFunc, &str)> {
+	let (input, _) = tag(TRAILER_PREFIX)(input)?;
+	let (input, hash_func_name) = take_till1(|c| c == ':' || c == '\t')(input)?;
+	let res = HashFunc::from_str(hash_func_name);
+	if res.is_err() {
+		let _: (&str, HashFunc) = fail().parse(input)?;
+	}
+	let hash_func = res.unwrap();
+	let (input, _) = opt(char(':')).parse(input)?;
+	let (input, _) = many1(one_of(" \t")).parse(input)?;
+	let (input, hash) = hex_digit1(input)?;
+	let (input, _) = opt(char('\t')).parse(input)?;
+	let (input, _) = opt(char('\r')).parse(input)?;
+	let (input, _) = opt(char('\n')).parse(input)?;
+	let (input, _) = eof(input)?;
+	Ok((input, (hash_func, hash)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CKSUM_CONTENT: &str = "SHA256 (test_file.txt) = 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08\n";
+	const CNIL_CONTENT: &str = "Nom du document\tTaille (octets)\tSHA256\r\ntest_file.txt\t42\t9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08\r\n";
+
+	#[test]
+	fn no_trailer() {
+		let res = strip_trailer(CKSUM_CONTENT.as_bytes());
+		assert_eq!(res, Ok(CKSUM_CONTENT.as_bytes()));
+	}
+
+	#[test]
+	fn empty() {
+		let res = strip_trailer(b"");
+		assert_eq!(res, Ok(&b""[..]));
+	}
+
+	#[test]
+	fn cksum_valid() {
+		let trailer = format_trailer(
+			ContentFileFormat::CksumBsd,
+			HashFunc::Sha256,
+			CKSUM_CONTENT.as_bytes(),
+		);
+		assert!(trailer.starts_with("# SELF-SHA256: "));
+		assert!(trailer.ends_with('\n'));
+		let full = format!("{CKSUM_CONTENT}{trailer}");
+		let res = strip_trailer(full.as_bytes());
+		assert_eq!(res, Ok(CKSUM_CONTENT.as_bytes()));
+	}
+
+	#[test]
+	fn cnil_valid() {
+		let trailer = format_trailer(
+			ContentFileFormat::Cnil,
+			HashFunc::Sha3_256,
+			CNIL_CONTENT.as_bytes(),
+		);
+		assert!(trailer.starts_with("# SELF-SHA3-256\t\t"));
+		assert!(trailer.ends_with("\r\n"));
+		let full = format!("{CNIL_CONTENT}{trailer}");
+		let res = strip_trailer(full.as_bytes());
+		assert_eq!(res, Ok(CNIL_CONTENT.as_bytes()));
+	}
+
+	#[test]
+	fn tampered() {
+		let trailer = format_trailer(
+			ContentFileFormat::CksumGnu,
+			HashFunc::Blake3,
+			CKSUM_CONTENT.as_bytes(),
+		);
+		let full = format!(
+			"{}{trailer}",
+			CKSUM_CONTENT.replace("test_file", "test_fila")
+		);
+		let res = strip_trailer(full.as_bytes());
+		assert_eq!(res, Err(TrailerError::Mismatch));
+	}
+
+	#[test]
+	fn truncated() {
+		let trailer = format_trailer(
+			ContentFileFormat::Cnil,
+			HashFunc::Sha256,
+			CNIL_CONTENT.as_bytes(),
+		);
+		let (header, _) = CNIL_CONTENT.split_once("\r\n").unwrap();
+		let full = format!("{header}\r\n{trailer}");
+		let res = strip_trailer(full.as_bytes());
+		assert_eq!(res, Err(TrailerError::Mismatch));
+	}
+
+	#[test]
+	fn malformed() {
+		let full = format!("{CKSUM_CONTENT}# SELF-INVALID: 42\n");
+		let res = strip_trailer(full.as_bytes());
+		assert_eq!(res, Err(TrailerError::Malformed));
+	}
+}
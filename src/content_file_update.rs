@@ -0,0 +1,386 @@
+use crate::audit::{self, AuditAction};
+use crate::case::is_case_file;
+use crate::config::Config;
+use crate::content_file_format::ContentFileFormat;
+use crate::events::{send_event, ExternalEvent, ExternalEventSender};
+use crate::file_metadata::{is_metadata_file, FileMetadata, MetadataManifest};
+use crate::files::{HashedFile, HashedFileList, NonHashedFileList};
+use crate::protection::{is_protection_file, Protection};
+use crate::receipt::Receipt;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+const ARCHIVE_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UpdateError {
+	ArchiveExists(PathBuf),
+	Io(String),
+	Parse,
+}
+
+impl fmt::Display for UpdateError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::ArchiveExists(p) => write!(f, "{}: file already exists", p.display()),
+			Self::Io(e) => write!(f, "I/O error: {e}"),
+			Self::Parse => write!(f, "unable to parse the content file"),
+		}
+	}
+}
+
+impl From<std::io::Error> for UpdateError {
+	fn from(e: std::io::Error) -> Self {
+		Self::Io(e.to_string())
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct ContentFileUpdate {
+	added: Vec<PathBuf>,
+	removed: Vec<PathBuf>,
+	modified: Vec<PathBuf>,
+	files: HashedFileList,
+	format: ContentFileFormat,
+}
+
+impl ContentFileUpdate {
+	pub fn new(
+		file_list: &NonHashedFileList,
+		config: &Config,
+		tx: ExternalEventSender,
+	) -> Result<Self, UpdateError> {
+		let ctn_file_path = file_list.get_content_file_absolute_path(config)?;
+		tracing::info!("Preparing content file update: {}", ctn_file_path.display());
+		let default_hash = match crate::analyse_hash::from_path(&ctn_file_path) {
+			Some(h) => h,
+			None => config.hash_function,
+		};
+		let ctn_file = Receipt::new(&ctn_file_path, default_hash, config.get_parse_mode(), &[])
+			.map_err(|_| UpdateError::Parse)?;
+		let format = ctn_file
+			.get_format()
+			.get_format()
+			.get_content_file_format()
+			.unwrap_or(config.content_file_format);
+		let ctn_file_mtime = ctn_file_path.metadata()?.modified()?;
+		let new_hash_func = if ctn_file.get_file_list().is_empty() {
+			config.hash_function
+		} else {
+			ctn_file.get_main_hashing_function()
+		};
+		let mut reference: HashMap<PathBuf, &HashedFile> = ctn_file
+			.get_file_list()
+			.get_files()
+			.map(|f| (normalize_path(f.get_relative_path()), f))
+			.collect();
+
+		let mut added = Vec::new();
+		let mut unchanged = Vec::new();
+		let mut to_hash = Vec::new();
+		for file in file_list.get_files() {
+			let abs_path = file.get_absolute_path()?;
+			if abs_path == ctn_file_path
+				|| is_content_file_archive(&abs_path, &ctn_file_path)
+				|| is_case_file(&abs_path, &ctn_file_path)
+				|| is_protection_file(&abs_path, &ctn_file_path)
+				|| is_metadata_file(&abs_path, &ctn_file_path)
+			{
+				continue;
+			}
+			let modified_after = abs_path.metadata()?.modified()? > ctn_file_mtime;
+			match reference.remove(&normalize_path(file.get_relative_path())) {
+				Some(ref_file) => {
+					if needs_hashing(ref_file, file.get_size(), modified_after) {
+						to_hash.push((file, Some(ref_file)));
+					} else {
+						unchanged.push(HashedFile::new_base_dir(
+							file_list.get_base_dir(),
+							file.get_relative_path(),
+							file.get_size(),
+							ref_file.get_hash(),
+							ref_file.get_hash_func(),
+						));
+					}
+				}
+				None => {
+					added.push(file.get_relative_path().to_path_buf());
+					to_hash.push((file, None));
+				}
+			}
+		}
+		let mut removed: Vec<PathBuf> = reference
+			.values()
+			.map(|f| f.get_relative_path().to_path_buf())
+			.collect();
+
+		let total_size = to_hash.iter().fold(0, |acc, (f, _)| acc + f.get_size());
+		tracing::info!(
+			"{} files to hash ({total_size} bytes), {} unchanged files",
+			to_hash.len(),
+			unchanged.len()
+		);
+		send_event(&tx, ExternalEvent::ProgressBarCreate(total_size));
+		let hashed = to_hash
+			.par_iter()
+			.map(|(file, ref_opt)| -> std::io::Result<(HashedFile, bool)> {
+				let hash_func = match ref_opt {
+					Some(ref_file) => ref_file.get_hash_func(),
+					None => new_hash_func,
+				};
+				let hashed_file = file.hash(hash_func, tx.clone())?;
+				let is_modified = match ref_opt {
+					Some(ref_file) => !ref_file
+						.get_hash()
+						.eq_ignore_ascii_case(hashed_file.get_hash()),
+					None => false,
+				};
+				Ok((hashed_file, is_modified))
+			})
+			.collect::<std::io::Result<Vec<(HashedFile, bool)>>>();
+		send_event(&tx, ExternalEvent::ProgressBarDelete);
+
+		let mut modified = Vec::new();
+		for (file, is_modified) in hashed? {
+			if is_modified {
+				modified.push(file.get_relative_path().to_path_buf());
+			}
+			unchanged.push(file);
+		}
+		added.sort();
+		removed.sort();
+		modified.sort();
+		let mut files = HashedFileList::from_files(file_list.get_base_dir(), unchanged);
+		files.set_explicit_content_file_path(file_list.get_explicit_content_file_path());
+		files.set_case(file_list.get_case().clone());
+		Ok(Self {
+			added,
+			removed,
+			modified,
+			files,
+			format,
+		})
+	}
+
+	pub fn get_added(&self) -> &[PathBuf] {
+		&self.added
+	}
+
+	pub fn get_removed(&self) -> &[PathBuf] {
+		&self.removed
+	}
+
+	pub fn get_modified(&self) -> &[PathBuf] {
+		&self.modified
+	}
+
+	pub fn has_changes(&self) -> bool {
+		!(self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty())
+	}
+
+	pub fn apply(&self, config: &Config) -> Result<(HashedFileList, PathBuf), UpdateError> {
+		let ctn_file_path = self.files.get_content_file_absolute_path(config)?;
+		let archive_path = archive_path(&ctn_file_path, SystemTime::now().into());
+		if archive_path.exists() {
+			return Err(UpdateError::ArchiveExists(archive_path));
+		}
+		tracing::info!(
+			"Archiving content file {} to {}",
+			ctn_file_path.display(),
+			archive_path.display()
+		);
+		replace_content_file(&ctn_file_path, &archive_path, |f| {
+			self.format
+				.write_content_file(f, &self.files, config.has_content_file_trailer())
+		})?;
+		audit::record(AuditAction::ContentFileWrite {
+			path: ctn_file_path.clone(),
+		});
+		if config.set_files_as_readonly() {
+			let mut protection = Protection::load(self.files.get_base_dir(), &ctn_file_path)?;
+			for path in self.added.iter().chain(self.modified.iter()) {
+				protection.add(&self.files.get_base_dir().join(path))?;
+			}
+			protection.add(&ctn_file_path)?;
+			if config.lock_directories() {
+				protection.add_directories()?;
+			}
+			protection.write(&ctn_file_path)?;
+			protection.apply()?;
+			audit::record(AuditAction::ReadOnly {
+				path: self.files.get_base_dir().to_path_buf(),
+				nb_files: self.added.len() + self.modified.len(),
+			});
+		}
+		if let Some(mut manifest) = MetadataManifest::load(&ctn_file_path)? {
+			for path in &self.removed {
+				manifest.remove(path);
+			}
+			for path in self.added.iter().chain(self.modified.iter()) {
+				let metadata = FileMetadata::from_path(&self.files.get_base_dir().join(path))?;
+				manifest.insert(path, metadata);
+			}
+			manifest.write(&ctn_file_path)?;
+		}
+		// Files which have not been modified since the previous content file have not been hashed
+		// again, hence the new content file has not been checked.
+		Ok((self.files.clone(), archive_path))
+	}
+}
+
+// The new content file is fully written next to the previous one before the latter is archived, so
+// the directory is never left without a content file.
+fn replace_content_file<F>(ctn_file_path: &Path, archive_path: &Path, write: F) -> io::Result<()>
+where
+	F: FnOnce(&mut File) -> io::Result<()>,
+{
+	let mut tmp_name = OsString::from(".");
+	tmp_name.push(ctn_file_path.file_name().unwrap_or_default());
+	tmp_name.push(format!(".{}.tmp", Uuid::new_v4()));
+	let tmp_path = ctn_file_path.with_file_name(tmp_name);
+	let res = File::create_new(&tmp_path).and_then(|mut f| {
+		write(&mut f)?;
+		f.sync_all()
+	});
+	if let Err(e) = res {
+		let _ = fs::remove_file(&tmp_path);
+		return Err(e);
+	}
+	if let Err(e) = fs::rename(ctn_file_path, archive_path) {
+		let _ = fs::remove_file(&tmp_path);
+		return Err(e);
+	}
+	if let Err(e) = fs::rename(&tmp_path, ctn_file_path) {
+		tracing::error!("Unable to write the new content file, restoring the previous one: {e}");
+		if let Err(e) = fs::rename(archive_path, ctn_file_path) {
+			tracing::error!("Unable to restore the previous content file: {e}");
+		}
+		let _ = fs::remove_file(&tmp_path);
+		return Err(e);
+	}
+	Ok(())
+}
+
+pub fn is_content_file_archive(path: &Path, ctn_file_path: &Path) -> bool {
+	if path.parent() != ctn_file_path.parent() {
+		return false;
+	}
+	let (Some(name), Some(ctn_name)) = (path.file_name(), ctn_file_path.file_name()) else {
+		return false;
+	};
+	let prefix = format!("{}.", ctn_name.to_string_lossy());
+	match name.to_string_lossy().strip_prefix(&prefix) {
+		Some(ts) => NaiveDateTime::parse_from_str(ts, ARCHIVE_TIMESTAMP_FORMAT).is_ok(),
+		None => false,
+	}
+}
+
+fn archive_path(ctn_file_path: &Path, date: DateTime<Utc>) -> PathBuf {
+	let mut name = ctn_file_path.file_name().unwrap_or_default().to_os_string();
+	name.push(format!(".{}", date.format(ARCHIVE_TIMESTAMP_FORMAT)));
+	ctn_file_path.with_file_name(name)
+}
+
+// Content files written without the files' sizes store a size of 0. In such a case, a file can
+// only be considered as modified if it has been written after the content file.
+fn needs_hashing(ref_file: &HashedFile, size: u64, modified_after: bool) -> bool {
+	modified_after || (ref_file.get_size() != 0 && ref_file.get_size() != size)
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+	path.components()
+		.filter(|c| matches!(c, Component::Normal(_)))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::hash::HashFunc;
+	use chrono::TimeZone;
+	use std::io::Write;
+
+	const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+	#[test]
+	fn archive_name() {
+		let date = Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 2).unwrap();
+		let path = archive_path(Path::new("/tmp/test/contenu.txt"), date);
+		assert_eq!(
+			path,
+			PathBuf::from("/tmp/test/contenu.txt.20240309T140502Z")
+		);
+	}
+
+	#[test]
+	fn archive_detection() {
+		let ctn_file = Path::new("/tmp/test/contenu.txt");
+		let date = Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 2).unwrap();
+		assert!(is_content_file_archive(
+			&archive_path(ctn_file, date),
+			ctn_file
+		));
+		assert!(!is_content_file_archive(ctn_file, ctn_file));
+		assert!(!is_content_file_archive(
+			Path::new("/tmp/test/contenu.txt.bak"),
+			ctn_file
+		));
+		assert!(!is_content_file_archive(
+			Path::new("/tmp/test/sub/contenu.txt.20240309T140502Z"),
+			ctn_file
+		));
+		assert!(!is_content_file_archive(
+			Path::new("/tmp/test/other.txt.20240309T140502Z"),
+			ctn_file
+		));
+	}
+
+	#[test]
+	fn hashing_with_size() {
+		let f = HashedFile::new("a.txt", 42, HASH, HashFunc::Sha256);
+		assert!(!needs_hashing(&f, 42, false));
+		assert!(needs_hashing(&f, 41, false));
+		assert!(needs_hashing(&f, 42, true));
+	}
+
+	#[test]
+	fn hashing_without_size() {
+		let f = HashedFile::new("a.txt", 0, HASH, HashFunc::Sha256);
+		assert!(!needs_hashing(&f, 42, false));
+		assert!(needs_hashing(&f, 42, true));
+	}
+
+	#[test]
+	fn path_normalization() {
+		assert_eq!(
+			normalize_path(Path::new("./dir/a.txt")),
+			normalize_path(Path::new("dir/a.txt"))
+		);
+	}
+
+	#[test]
+	fn replacement() {
+		let dir = std::env::temp_dir().join(format!("loutre-{}", Uuid::new_v4()));
+		fs::create_dir_all(&dir).unwrap();
+		let ctn_file = dir.join("contenu.txt");
+		let archive = dir.join("contenu.txt.20240309T140502Z");
+		fs::write(&ctn_file, "old").unwrap();
+		let res = replace_content_file(&ctn_file, &archive, |_| Err(io::Error::other("test")));
+		assert!(res.is_err());
+		assert_eq!(fs::read_to_string(&ctn_file).unwrap(), "old");
+		assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+		replace_content_file(&ctn_file, &archive, |f| f.write_all(b"new")).unwrap();
+		assert_eq!(fs::read_to_string(&ctn_file).unwrap(), "new");
+		assert_eq!(fs::read_to_string(&archive).unwrap(), "old");
+		assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+		fs::remove_dir_all(&dir).unwrap();
+	}
+}
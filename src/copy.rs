@@ -0,0 +1,357 @@
+use crate::audit::{self, AuditAction};
+use crate::config::Config;
+use crate::content_file_location::resolve_content_file_path;
+use crate::events::{send_event, ExternalEvent, ExternalEventSender};
+use crate::files::{
+	is_content_file_companion, HashedFile, HashedFileList, NonHashedFile, NonHashedFileList,
+};
+use crate::hash::HashFunc;
+use crate::protection::Protection;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+// Writes everything which is read, so the source file is read only once to be both copied and
+// hashed.
+struct TeeReader<R: Read, W: Write> {
+	reader: R,
+	writer: W,
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let n = self.reader.read(buf)?;
+		self.writer.write_all(&buf[..n])?;
+		Ok(n)
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopyMismatch {
+	path: PathBuf,
+	source_hash: String,
+	destination_hash: String,
+}
+
+impl CopyMismatch {
+	pub fn get_path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn get_source_hash(&self) -> &str {
+		&self.source_hash
+	}
+
+	pub fn get_destination_hash(&self) -> &str {
+		&self.destination_hash
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopyError {
+	path: PathBuf,
+	message: String,
+}
+
+impl CopyError {
+	pub fn get_path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn get_message(&self) -> &str {
+		&self.message
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CopyReport {
+	destination: PathBuf,
+	ctn_file_path: PathBuf,
+	nb_files: usize,
+	mismatches: Vec<CopyMismatch>,
+	errors: Vec<CopyError>,
+}
+
+impl CopyReport {
+	pub fn get_destination(&self) -> &Path {
+		&self.destination
+	}
+
+	pub fn get_content_file_path(&self) -> &Path {
+		&self.ctn_file_path
+	}
+
+	pub fn get_nb_files(&self) -> usize {
+		self.nb_files
+	}
+
+	pub fn get_mismatches(&self) -> &[CopyMismatch] {
+		&self.mismatches
+	}
+
+	pub fn get_errors(&self) -> &[CopyError] {
+		&self.errors
+	}
+}
+
+// The content file lists the fingerprints of the source files, hence a file which has not been
+// correctly copied is also reported when the destination is checked later on. Files which could not
+// be copied at all are reported and left out of the content file.
+pub async fn copy_and_hash(
+	source: &Path,
+	destination: &Path,
+	config: &Config,
+	tx: ExternalEventSender,
+) -> io::Result<CopyReport> {
+	if destination.starts_with(source) {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			"the destination directory is located in the source directory",
+		));
+	}
+	let hash_func = config.hash_function;
+	let src_lst = NonHashedFileList::from_dir(
+		source,
+		config.include_hidden_files(),
+		config.include_system_files(),
+	)
+	.await?;
+	// A source which has already been processed comes with its own content file and companion
+	// files, which are not part of the evidence set.
+	let src_ctn_file_path = src_lst.get_content_file_absolute_path(config)?;
+	let src_files: Vec<&NonHashedFile> = src_lst
+		.get_files()
+		.filter(|f| match f.get_absolute_path() {
+			Ok(path) => !is_content_file_companion(&path, &src_ctn_file_path),
+			Err(_) => true,
+		})
+		.collect();
+	let ctn_file_path = resolve_content_file_path(destination, config)?;
+	check_destination(
+		src_files.iter().map(|f| f.get_relative_path()),
+		destination,
+		&ctn_file_path,
+	)?;
+	tracing::info!(
+		"Copying {} files from {} to {}",
+		src_files.len(),
+		source.display(),
+		destination.display()
+	);
+
+	// Each file is read twice: once while being copied and once to be verified.
+	let total_size = src_files.iter().fold(0, |acc, f| acc + f.get_size());
+	send_event(&tx, ExternalEvent::ProgressBarCreate(total_size * 2));
+	let res = copy_files(&src_files, destination, hash_func, tx.clone());
+	send_event(&tx, ExternalEvent::ProgressBarDelete);
+	let (files, mismatches, errors) = res;
+	if let (true, Some(e)) = (files.is_empty(), errors.first()) {
+		return Err(io::Error::other(format!(
+			"no file could be copied: {}: {}",
+			e.path.display(),
+			e.message
+		)));
+	}
+
+	let hashed_lst = HashedFileList::from_files(destination, files);
+	let written = hashed_lst.write_content_file_opt(
+		&ctn_file_path,
+		config.content_file_format,
+		config.has_content_file_trailer(),
+	)?;
+	if !written {
+		return Err(io::Error::new(
+			io::ErrorKind::AlreadyExists,
+			format!("{}: file already exists", ctn_file_path.display()),
+		));
+	}
+	audit::record(AuditAction::Copy {
+		source: source.to_path_buf(),
+		destination: destination.to_path_buf(),
+		nb_files: hashed_lst.len(None),
+		nb_mismatches: mismatches.len(),
+	});
+	audit::record(AuditAction::ContentFileWrite {
+		path: ctn_file_path.clone(),
+	});
+	if config.set_files_as_readonly() {
+		let mut protection = Protection::load(destination, &ctn_file_path)?;
+		for file in hashed_lst.get_files() {
+			protection.add(&file.get_absolute_path()?)?;
+		}
+		protection.add(&ctn_file_path)?;
+		if config.lock_directories() {
+			protection.add_directories()?;
+		}
+		protection.write(&ctn_file_path)?;
+		protection.apply()?;
+	}
+	tracing::info!(
+		"Copy done: {} files, {} mismatches, {} errors",
+		hashed_lst.len(None),
+		mismatches.len(),
+		errors.len()
+	);
+	Ok(CopyReport {
+		destination: destination.to_path_buf(),
+		ctn_file_path,
+		nb_files: hashed_lst.len(None),
+		mismatches,
+		errors,
+	})
+}
+
+// Existing files are never overwritten: the copy does not start if any of them, or the content
+// file, is already present in the destination directory.
+fn check_destination<'a, I>(
+	relative_paths: I,
+	destination: &Path,
+	ctn_file_path: &Path,
+) -> io::Result<()>
+where
+	I: Iterator<Item = &'a Path>,
+{
+	let mut existing: Vec<PathBuf> = relative_paths
+		.map(|p| destination.join(p))
+		.chain(std::iter::once(ctn_file_path.to_path_buf()))
+		.filter(|p| p.exists())
+		.collect();
+	existing.sort();
+	match existing.first() {
+		Some(path) => Err(io::Error::new(
+			io::ErrorKind::AlreadyExists,
+			format!(
+				"{} files already exist in the destination directory, including {}",
+				existing.len(),
+				path.display()
+			),
+		)),
+		None => Ok(()),
+	}
+}
+
+fn copy_files(
+	src_files: &[&NonHashedFile],
+	destination: &Path,
+	hash_func: HashFunc,
+	tx: ExternalEventSender,
+) -> (Vec<HashedFile>, Vec<CopyMismatch>, Vec<CopyError>) {
+	let mut files = Vec::with_capacity(src_files.len());
+	let mut mismatches = Vec::new();
+	let mut errors = Vec::new();
+	for file in src_files {
+		let relative_path = file.get_relative_path();
+		let dest_path = destination.join(relative_path);
+		let res = file
+			.get_absolute_path()
+			.and_then(|src_path| copy_file(&src_path, &dest_path, hash_func, Some(tx.clone())));
+		let (source_hash, destination_hash) = match res {
+			Ok(hashes) => hashes,
+			Err(e) => {
+				tracing::error!("{}: unable to copy: {e}", relative_path.display());
+				errors.push(CopyError {
+					path: relative_path.to_path_buf(),
+					message: e.to_string(),
+				});
+				continue;
+			}
+		};
+		if source_hash != destination_hash {
+			tracing::error!(
+				"{}: copy mismatch, source: {source_hash}, destination: {destination_hash}",
+				relative_path.display()
+			);
+			mismatches.push(CopyMismatch {
+				path: relative_path.to_path_buf(),
+				source_hash: source_hash.clone(),
+				destination_hash,
+			});
+		}
+		files.push(HashedFile::new_base_dir(
+			destination,
+			relative_path,
+			file.get_size(),
+			source_hash,
+			hash_func,
+		));
+	}
+	mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+	(files, mismatches, errors)
+}
+
+// Existing files are never overwritten. The destination is synchronized to the disk before being
+// read again, which ensures that the data has been handed to the storage device. The page cache is
+// however not dropped, hence the verification may read the copy from memory: it detects errors
+// occurring while copying, not the ones of the storage device itself.
+fn copy_file(
+	source: &Path,
+	destination: &Path,
+	hash_func: HashFunc,
+	tx: Option<ExternalEventSender>,
+) -> io::Result<(String, String)> {
+	if let Some(parent) = destination.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	let dest_file = File::create_new(destination)?;
+	let mut tee = TeeReader {
+		reader: File::open(source)?,
+		writer: dest_file,
+	};
+	let source_hash = hash_func.hash_reader(&mut tee, tx.clone())?;
+	tee.writer.sync_all()?;
+	let destination_hash = hash_func.hash_file(destination, tx)?;
+	Ok((source_hash, destination_hash))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use uuid::Uuid;
+
+	#[test]
+	fn tee_reader() {
+		let mut out = Vec::new();
+		let mut tee = TeeReader {
+			reader: "test".as_bytes(),
+			writer: &mut out,
+		};
+		let hash = HashFunc::Sha256.hash_reader(&mut tee, None).unwrap();
+		assert_eq!(
+			hash,
+			"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+		);
+		assert_eq!(out, b"test");
+	}
+
+	#[test]
+	fn copy_single_file() {
+		let dir = std::env::temp_dir().join(format!("loutre-{}", Uuid::new_v4()));
+		fs::create_dir_all(&dir).unwrap();
+		let source = dir.join("a.txt");
+		fs::write(&source, "test").unwrap();
+		let destination = dir.join("copy").join("a.txt");
+		let (source_hash, destination_hash) =
+			copy_file(&source, &destination, HashFunc::Sha256, None).unwrap();
+		assert_eq!(source_hash, destination_hash);
+		assert_eq!(fs::read(&destination).unwrap(), b"test");
+		assert!(copy_file(&source, &destination, HashFunc::Sha256, None).is_err());
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn existing_destination() {
+		let dir = std::env::temp_dir().join(format!("loutre-{}", Uuid::new_v4()));
+		fs::create_dir_all(dir.join("sub")).unwrap();
+		let ctn_file = dir.join("contenu.txt");
+		let paths = [Path::new("a.txt"), Path::new("sub/b.txt")];
+		assert!(check_destination(paths.into_iter(), &dir, &ctn_file).is_ok());
+		fs::write(dir.join("sub").join("b.txt"), "test").unwrap();
+		let err = check_destination(paths.into_iter(), &dir, &ctn_file).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+		fs::remove_file(dir.join("sub").join("b.txt")).unwrap();
+		fs::write(&ctn_file, "").unwrap();
+		let err = check_destination(paths.into_iter(), &dir, &ctn_file).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+		fs::remove_dir_all(&dir).unwrap();
+	}
+}
@@ -1,9 +1,12 @@
 use crate::clipboard::{Clipboard, ClipboardStart};
 use crate::config::Config;
+use crate::content_file_update::{ContentFileUpdate, UpdateError};
 use crate::files::{FileList, HashedFileList, NonHashedFileList};
+use crate::parsers::ReceiptError;
 use crate::progress::{LoadingBarStatus, ProgressBarStatus};
 use crate::receipt::Receipt;
 use dioxus::prelude::*;
+use std::path::PathBuf;
 
 pub type ExternalEventReceiver = UnboundedReceiver<ExternalEvent>;
 pub type ExternalEventSender = UnboundedSender<ExternalEvent>;
@@ -19,30 +22,38 @@ pub fn send_event(tx: &ExternalEventSender, event: ExternalEvent) -> bool {
 #[derive(Clone)]
 pub struct ExternalEventSignals {
 	config: Signal<Config>,
+	content_file_update: Signal<Option<Result<ContentFileUpdate, UpdateError>>>,
 	clipboard: Signal<Clipboard>,
 	clipboard_start: Signal<ClipboardStart>,
 	file_list: Signal<FileList>,
 	loading_bar: Signal<LoadingBarStatus>,
 	progress_bar: Signal<Option<ProgressBarStatus>>,
-	receipt: Signal<Option<Receipt>>,
+	receipts: Signal<Vec<Receipt>>,
+	receipt_error: Signal<Option<ReceiptError>>,
 }
 
 impl ExternalEventSignals {
 	pub fn new() -> Self {
 		Self {
 			config: use_context::<Signal<Config>>(),
+			content_file_update: use_context::<
+				Signal<Option<Result<ContentFileUpdate, UpdateError>>>,
+			>(),
 			clipboard: use_context::<Signal<Clipboard>>(),
 			clipboard_start: use_context::<Signal<ClipboardStart>>(),
 			file_list: use_context::<Signal<FileList>>(),
 			loading_bar: use_context::<Signal<LoadingBarStatus>>(),
 			progress_bar: use_context::<Signal<Option<ProgressBarStatus>>>(),
-			receipt: use_context::<Signal<Option<Receipt>>>(),
+			receipts: use_context::<Signal<Vec<Receipt>>>(),
+			receipt_error: use_context::<Signal<Option<ReceiptError>>>(),
 		}
 	}
 }
 
 #[derive(Clone, Debug)]
 pub enum ExternalEvent {
+	ContentFileUpdateReset,
+	ContentFileUpdateSet(Result<ContentFileUpdate, UpdateError>),
 	FileListReset,
 	HashedFileListSet(HashedFileList),
 	NonHashedFileListSet(NonHashedFileList),
@@ -51,13 +62,21 @@ pub enum ExternalEvent {
 	ProgressBarAdd(u64),
 	ProgressBarCreate(u64),
 	ProgressBarDelete,
-	ReceiptReset,
+	ReceiptRemove(PathBuf),
 	ReceiptSet(Receipt),
+	ReceiptErrorReset,
+	ReceiptErrorSet(ReceiptError),
 }
 
 impl ExternalEvent {
 	pub fn handle(self, signals: &mut ExternalEventSignals) {
 		match self {
+			Self::ContentFileUpdateReset => {
+				signals.content_file_update.set(None);
+			}
+			Self::ContentFileUpdateSet(update) => {
+				signals.content_file_update.set(Some(update));
+			}
 			Self::FileListReset => {
 				signals.file_list.set(FileList::None);
 			}
@@ -99,11 +118,25 @@ impl ExternalEvent {
 			Self::ProgressBarDelete => {
 				signals.progress_bar.set(None);
 			}
-			Self::ReceiptReset => {
-				signals.receipt.set(None);
+			Self::ReceiptRemove(path) => {
+				signals.receipts.write().retain(|r| r.get_path() != path);
 			}
+			// A receipt which has already been loaded is replaced.
 			Self::ReceiptSet(rcpt) => {
-				signals.receipt.set(Some(rcpt));
+				let mut receipts = signals.receipts.write();
+				match receipts
+					.iter_mut()
+					.find(|r| r.get_path() == rcpt.get_path())
+				{
+					Some(r) => *r = rcpt,
+					None => receipts.push(rcpt),
+				}
+			}
+			Self::ReceiptErrorReset => {
+				signals.receipt_error.set(None);
+			}
+			Self::ReceiptErrorSet(e) => {
+				signals.receipt_error.set(Some(e));
 			}
 		}
 	}
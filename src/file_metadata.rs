@@ -0,0 +1,328 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, File, Metadata};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const METADATA_FILE_SUFFIX: &str = ".metadata.toml";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetadataField {
+	Modified,
+	Changed,
+	Created,
+	Mode,
+	Owner,
+	ExtendedAttributes,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataChange {
+	path: PathBuf,
+	field: MetadataField,
+	recorded: String,
+	current: String,
+}
+
+impl MetadataChange {
+	pub fn get_path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn get_field(&self) -> MetadataField {
+		self.field
+	}
+
+	pub fn get_recorded(&self) -> &str {
+		&self.recorded
+	}
+
+	pub fn get_current(&self) -> &str {
+		&self.current
+	}
+}
+
+// On Unix, the change time is the last status change, which is updated when the permissions,
+// the owner or the extended attributes are modified. Elsewhere, the creation time is recorded
+// instead. Fields which are not available on the current platform are left empty, so a manifest
+// may be checked on another platform than the one it has been written on.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, Hash, PartialEq)]
+#[serde(default)]
+pub struct FileMetadata {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	modified: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	changed: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	created: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	mode: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	uid: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	gid: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	xattrs: Option<BTreeMap<String, String>>,
+}
+
+impl FileMetadata {
+	pub fn from_path(path: &Path) -> io::Result<Self> {
+		let metadata = path.symlink_metadata()?;
+		let mut file_metadata = Self {
+			modified: metadata.modified().ok().map(format_time),
+			xattrs: get_xattrs(path)?,
+			..Default::default()
+		};
+		file_metadata.set_platform_metadata(&metadata);
+		Ok(file_metadata)
+	}
+
+	#[cfg(unix)]
+	fn set_platform_metadata(&mut self, metadata: &Metadata) {
+		use std::os::unix::fs::MetadataExt;
+		self.changed = DateTime::from_timestamp(metadata.ctime(), metadata.ctime_nsec() as u32)
+			.map(|d| d.to_rfc3339());
+		self.mode = Some(metadata.mode());
+		self.uid = Some(metadata.uid());
+		self.gid = Some(metadata.gid());
+	}
+
+	#[cfg(not(unix))]
+	fn set_platform_metadata(&mut self, metadata: &Metadata) {
+		self.created = metadata.created().ok().map(format_time);
+	}
+
+	fn get_owner(&self) -> Option<String> {
+		match (self.uid, self.gid) {
+			(Some(uid), Some(gid)) => Some(format!("{uid}:{gid}")),
+			_ => None,
+		}
+	}
+
+	fn get_xattrs(&self) -> Option<String> {
+		self.xattrs.as_ref().map(|xattrs| {
+			xattrs
+				.iter()
+				.map(|(k, v)| format!("{k}={v}"))
+				.collect::<Vec<String>>()
+				.join(", ")
+		})
+	}
+
+	// Only the fields which have been captured both when recorded and now are compared.
+	pub fn get_changes(&self, path: &Path, current: &Self) -> Vec<MetadataChange> {
+		let fields = [
+			(
+				MetadataField::Modified,
+				self.modified.clone(),
+				current.modified.clone(),
+			),
+			(
+				MetadataField::Changed,
+				self.changed.clone(),
+				current.changed.clone(),
+			),
+			(
+				MetadataField::Created,
+				self.created.clone(),
+				current.created.clone(),
+			),
+			(
+				MetadataField::Mode,
+				self.mode.map(|m| format!("{m:o}")),
+				current.mode.map(|m| format!("{m:o}")),
+			),
+			(MetadataField::Owner, self.get_owner(), current.get_owner()),
+			(
+				MetadataField::ExtendedAttributes,
+				self.get_xattrs(),
+				current.get_xattrs(),
+			),
+		];
+		fields
+			.into_iter()
+			.filter_map(|(field, recorded, current)| match (recorded, current) {
+				(Some(recorded), Some(current)) if recorded != current => {
+					Some((field, recorded, current))
+				}
+				_ => None,
+			})
+			.map(|(field, recorded, current)| MetadataChange {
+				path: path.to_path_buf(),
+				field,
+				recorded,
+				current,
+			})
+			.collect()
+	}
+}
+
+// The metadata are stored next to the content file, which name they share.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct MetadataManifest {
+	files: BTreeMap<String, FileMetadata>,
+}
+
+impl MetadataManifest {
+	pub fn load(ctn_file_path: &Path) -> io::Result<Option<Self>> {
+		let path = metadata_file_path(ctn_file_path);
+		if !path.is_file() {
+			return Ok(None);
+		}
+		let content = read_to_string(&path)?;
+		let manifest = toml::from_str(&content).map_err(io::Error::other)?;
+		tracing::info!("Metadata manifest loaded: {}", path.display());
+		Ok(Some(manifest))
+	}
+
+	pub fn write(&self, ctn_file_path: &Path) -> io::Result<PathBuf> {
+		let path = metadata_file_path(ctn_file_path);
+		let content = toml::to_string(self).map_err(io::Error::other)?;
+		let mut f = File::create(&path)?;
+		f.write_all(content.as_bytes())?;
+		tracing::info!("Metadata manifest written: {}", path.display());
+		Ok(path)
+	}
+
+	pub fn insert(&mut self, relative_path: &Path, metadata: FileMetadata) {
+		self.files.insert(manifest_key(relative_path), metadata);
+	}
+
+	pub fn remove(&mut self, relative_path: &Path) {
+		self.files.remove(&manifest_key(relative_path));
+	}
+
+	// Files which are not listed in the manifest are not reported, the check already reports them
+	// as added.
+	pub fn get_changes(&self, relative_path: &Path, current: &FileMetadata) -> Vec<MetadataChange> {
+		match self.files.get(&manifest_key(relative_path)) {
+			Some(recorded) => recorded.get_changes(relative_path, current),
+			None => Vec::new(),
+		}
+	}
+}
+
+// Paths use forward slashes so a manifest written on a platform can be checked on another one.
+fn manifest_key(relative_path: &Path) -> String {
+	relative_path
+		.components()
+		.map(|c| c.as_os_str().to_string_lossy())
+		.collect::<Vec<_>>()
+		.join("/")
+}
+
+pub fn metadata_file_path(ctn_file_path: &Path) -> PathBuf {
+	let mut name = ctn_file_path.file_name().unwrap_or_default().to_os_string();
+	name.push(METADATA_FILE_SUFFIX);
+	ctn_file_path.with_file_name(name)
+}
+
+pub fn is_metadata_file(path: &Path, ctn_file_path: &Path) -> bool {
+	path == metadata_file_path(ctn_file_path)
+}
+
+fn format_time(time: std::time::SystemTime) -> String {
+	DateTime::<Utc>::from(time).to_rfc3339()
+}
+
+#[cfg(target_os = "linux")]
+fn get_xattrs(path: &Path) -> io::Result<Option<BTreeMap<String, String>>> {
+	let mut xattrs = BTreeMap::new();
+	for name in xattr::list(path)? {
+		let value = xattr::get(path, &name)?.unwrap_or_default();
+		let value = value.iter().map(|b| format!("{b:02x}")).collect();
+		xattrs.insert(name.to_string_lossy().to_string(), value);
+	}
+	Ok(Some(xattrs))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_xattrs(_path: &Path) -> io::Result<Option<BTreeMap<String, String>>> {
+	Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn get_metadata() -> FileMetadata {
+		FileMetadata {
+			modified: Some("2024-11-04T10:12:35+00:00".to_string()),
+			changed: Some("2024-11-04T10:12:35+00:00".to_string()),
+			mode: Some(0o100444),
+			uid: Some(1000),
+			gid: Some(1000),
+			xattrs: Some(BTreeMap::new()),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn metadata_file() {
+		let ctn_file = Path::new("/tmp/evidence/contenu.txt");
+		assert_eq!(
+			metadata_file_path(ctn_file),
+			PathBuf::from("/tmp/evidence/contenu.txt.metadata.toml")
+		);
+		assert!(is_metadata_file(
+			Path::new("/tmp/evidence/contenu.txt.metadata.toml"),
+			ctn_file
+		));
+	}
+
+	#[test]
+	fn changes() {
+		let recorded = get_metadata();
+		let path = Path::new("sub/a.txt");
+		assert!(recorded.get_changes(path, &recorded).is_empty());
+
+		let mut current = get_metadata();
+		current.mode = Some(0o100644);
+		current.uid = Some(0);
+		current
+			.xattrs
+			.get_or_insert_default()
+			.insert("user.comment".to_string(), "6f6b".to_string());
+		let changes = recorded.get_changes(path, &current);
+		assert_eq!(changes.len(), 3);
+		assert_eq!(changes[0].get_field(), MetadataField::Mode);
+		assert_eq!(changes[0].get_recorded(), "100444");
+		assert_eq!(changes[0].get_current(), "100644");
+		assert_eq!(changes[1].get_recorded(), "1000:1000");
+		assert_eq!(changes[1].get_current(), "0:1000");
+		assert_eq!(changes[2].get_current(), "user.comment=6f6b");
+	}
+
+	#[test]
+	fn other_platform() {
+		let recorded = get_metadata();
+		let current = FileMetadata {
+			modified: recorded.modified.clone(),
+			created: Some("2024-11-04T10:12:35+00:00".to_string()),
+			..Default::default()
+		};
+		assert!(recorded
+			.get_changes(Path::new("a.txt"), &current)
+			.is_empty());
+	}
+
+	#[test]
+	fn manifest() {
+		let mut manifest = MetadataManifest::default();
+		manifest.insert(&Path::new("sub").join("a.txt"), get_metadata());
+		manifest.insert(Path::new("b.txt"), get_metadata());
+		manifest.remove(Path::new("b.txt"));
+		let content = toml::to_string(&manifest).unwrap();
+		let manifest: MetadataManifest = toml::from_str(&content).unwrap();
+		let mut current = get_metadata();
+		current.modified = Some("2025-01-01T00:00:00+00:00".to_string());
+		let changes = manifest.get_changes(Path::new("sub/a.txt"), &current);
+		assert_eq!(changes.len(), 1);
+		assert_eq!(changes[0].get_field(), MetadataField::Modified);
+		assert!(manifest
+			.get_changes(Path::new("b.txt"), &current)
+			.is_empty());
+	}
+}
@@ -1,15 +1,26 @@
-use crate::check::{CheckResult, CheckResultError};
+use crate::audit::{self, AuditAction};
+use crate::case::{is_case_file, CaseMetadata};
+use crate::check::{CheckResult, CheckResultError, RenamedFile};
 use crate::config::Config;
 use crate::content_file_format::ContentFileFormat;
+use crate::content_file_location::{remember_content_file_path, resolve_content_file_path};
+use crate::content_file_update::is_content_file_archive;
 use crate::events::ExternalEventSender;
+use crate::file_metadata::{is_metadata_file, FileMetadata, MetadataChange, MetadataManifest};
 use crate::hash::HashFunc;
+use crate::history::{History, PreviousEvidence};
+use crate::known_files::{KnownFileKind, KnownFileMatch, KnownFiles};
+use crate::merkle::merkle_root_files;
+use crate::parsers::ReceiptParseError;
+use crate::protection::{is_protection_file, Protection};
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io;
 #[cfg(windows)]
 use std::os::windows::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use uuid::Uuid;
 
 // Microsoft Windows File Attribute Constants
@@ -19,6 +30,8 @@ const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
 #[cfg(windows)]
 const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
 
+// A single file list is held at once, hence the size of its largest variant does not matter.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Default)]
 pub enum FileList {
 	NonHashed(NonHashedFileList),
@@ -61,7 +74,7 @@ impl FileList {
 
 	pub fn nb_excluded_files(&self) -> usize {
 		match self {
-			Self::NonHashed(lst) => lst.excluded_files.len(),
+			Self::NonHashed(lst) => lst.nb_excluded_files(),
 			Self::Hashed(_) | Self::None => 0,
 		}
 	}
@@ -80,6 +93,48 @@ impl FileList {
 		}
 	}
 
+	pub fn nb_known_files(&self, kind: KnownFileKind) -> usize {
+		match self {
+			Self::Hashed(lst) => lst.nb_known_files(kind),
+			Self::NonHashed(_) | Self::None => 0,
+		}
+	}
+
+	pub fn nb_previous_evidence(&self) -> usize {
+		match self {
+			Self::Hashed(lst) => lst.nb_previous_evidence(),
+			Self::NonHashed(_) | Self::None => 0,
+		}
+	}
+
+	pub fn get_unprotected_files(&self) -> Vec<PathBuf> {
+		match self {
+			Self::Hashed(lst) => lst.get_unprotected_files().to_vec(),
+			Self::NonHashed(_) | Self::None => Vec::new(),
+		}
+	}
+
+	pub fn get_metadata_changes(&self) -> Vec<MetadataChange> {
+		match self {
+			Self::Hashed(lst) => lst.get_metadata_changes().to_vec(),
+			Self::NonHashed(_) | Self::None => Vec::new(),
+		}
+	}
+
+	pub fn get_case_write_error(&self) -> Option<String> {
+		match self {
+			Self::Hashed(lst) => lst.get_case_write_error().map(|e| e.to_string()),
+			Self::NonHashed(_) | Self::None => None,
+		}
+	}
+
+	pub fn get_protection_error(&self) -> Option<String> {
+		match self {
+			Self::Hashed(lst) => lst.get_protection_error().map(|e| e.to_string()),
+			Self::NonHashed(_) | Self::None => None,
+		}
+	}
+
 	pub fn duplicated_files(&self) -> Vec<Vec<HashedFile>> {
 		match self {
 			Self::Hashed(lst) => lst
@@ -108,7 +163,13 @@ macro_rules! common_lst_impl {
 							.files
 							.values()
 							.filter(|e| match e.get_absolute_path() {
-								Ok(path) => path != ctn_file,
+								Ok(path) => {
+									path != ctn_file
+										&& !is_content_file_archive(&path, &ctn_file)
+										&& !is_case_file(&path, &ctn_file)
+										&& !is_protection_file(&path, &ctn_file)
+										&& !is_metadata_file(&path, &ctn_file)
+								}
 								Err(_) => false,
 							})
 							.count();
@@ -126,9 +187,22 @@ macro_rules! common_lst_impl {
 			}
 
 			pub fn get_content_file_absolute_path(&self, config: &Config) -> io::Result<PathBuf> {
-				let mut path = self.base_dir.clone().canonicalize()?;
-				path.push(config.get_content_file_name());
-				Ok(path)
+				match &self.explicit_content_file_path {
+					Some(path) => Ok(path.clone()),
+					None => resolve_content_file_path(&self.base_dir, config),
+				}
+			}
+
+			pub fn set_explicit_content_file_path(&mut self, path: Option<PathBuf>) {
+				self.explicit_content_file_path = path;
+			}
+
+			pub fn get_case(&self) -> &CaseMetadata {
+				&self.case
+			}
+
+			pub fn set_case(&mut self, case: CaseMetadata) {
+				self.case = case;
 			}
 		}
 	};
@@ -138,6 +212,8 @@ macro_rules! common_lst_impl {
 pub struct NonHashedFileList {
 	id: Uuid,
 	base_dir: PathBuf,
+	explicit_content_file_path: Option<PathBuf>,
+	case: CaseMetadata,
 	files: HashMap<FileId, NonHashedFile>,
 	empty_files: HashSet<FileId>,
 	excluded_files: HashSet<NonHashedFile>,
@@ -150,6 +226,30 @@ impl NonHashedFileList {
 		self.files.values().fold(0, |acc, f| acc + f.size)
 	}
 
+	pub fn get_files(&self) -> std::collections::hash_map::Values<'_, FileId, NonHashedFile> {
+		self.files.values()
+	}
+
+	pub fn get_explicit_content_file_path(&self) -> Option<PathBuf> {
+		self.explicit_content_file_path.clone()
+	}
+
+	pub fn nb_excluded_files(&self) -> usize {
+		self.excluded_files.len()
+	}
+
+	// Case metadata entered when the directory was previously hashed are reloaded.
+	pub fn load_case(&mut self, config: &Config) {
+		let Ok(ctn_file_path) = self.get_content_file_absolute_path(config) else {
+			return;
+		};
+		match CaseMetadata::load(&ctn_file_path) {
+			Ok(Some(case)) => self.case = case,
+			Ok(None) => {}
+			Err(e) => tracing::error!("Unable to load the case metadata: {e}"),
+		}
+	}
+
 	pub fn content_file_exists(&self, config: &Config) -> bool {
 		if let Ok(ctn_file_path) = self.get_content_file_absolute_path(config) {
 			return ctn_file_path.is_file();
@@ -250,6 +350,8 @@ impl NonHashedFileList {
 		Ok(Self {
 			id: Uuid::new_v4(),
 			base_dir: dir_path,
+			explicit_content_file_path: None,
+			case: CaseMetadata::default(),
 			files,
 			empty_files,
 			excluded_files,
@@ -263,17 +365,139 @@ impl NonHashedFileList {
 		tx: ExternalEventSender,
 	) -> io::Result<HashedFileList> {
 		let ctn_file_absolute_path = self.get_content_file_absolute_path(config)?;
+		let set_ro = config.set_files_as_readonly();
+		let mut protection = Protection::load(&self.base_dir, &ctn_file_absolute_path)?;
+		let unprotected_files = protection.get_unprotected_files();
+		let metadata_changes = self.get_metadata_changes(&ctn_file_absolute_path)?;
+		if set_ro {
+			for f in self.files.values() {
+				let abs_path = f.get_absolute_path()?;
+				if !is_content_file_companion(&abs_path, &ctn_file_absolute_path) {
+					protection.add(&abs_path)?;
+				}
+			}
+			// The original permissions are recorded before being altered, so the protection can
+			// be removed even if the hashing fails.
+			protection.write(&ctn_file_absolute_path)?;
+			protection.apply()?;
+		}
+		let mut hashed_lst = self.hash_files(&ctn_file_absolute_path, hash_func, tx)?;
+		hashed_lst.unprotected_files = unprotected_files;
+		hashed_lst.metadata_changes = metadata_changes;
+		audit::record(AuditAction::Hashing {
+			path: self.base_dir.clone(),
+			hash_func: hash_func.to_string(),
+			nb_files: hashed_lst.len(None),
+			merkle_root: hashed_lst.get_merkle_root(),
+		});
+		if set_ro {
+			audit::record(AuditAction::ReadOnly {
+				path: self.base_dir.clone(),
+				nb_files: hashed_lst.len(None),
+			});
+		}
+		let written = hashed_lst.write_content_file_opt(
+			ctn_file_absolute_path.as_path(),
+			config.content_file_format,
+			config.has_content_file_trailer(),
+		)?;
+		if written {
+			audit::record(AuditAction::ContentFileWrite {
+				path: ctn_file_absolute_path.clone(),
+			});
+			if config.is_metadata_capture_enabled() {
+				if let Err(e) = hashed_lst.write_metadata_manifest(&ctn_file_absolute_path) {
+					tracing::error!("Unable to write the metadata manifest: {e}");
+				}
+			}
+		}
+		// Once the content file has been written, errors are reported without failing the
+		// hashing, otherwise the content file would be left unprotected and the results lost.
+		if let Err(e) = remember_content_file_path(&self.base_dir, &ctn_file_absolute_path) {
+			tracing::error!("Unable to remember the content file's location: {e}");
+		}
+		if !self.case.is_empty() {
+			if let Err(e) = self.case.write_if_changed(&ctn_file_absolute_path) {
+				tracing::error!("Unable to write the case metadata: {e}");
+				hashed_lst.case_write_error = Some(e.to_string());
+			}
+		}
+		if set_ro {
+			if let Err(e) = protect_content_file(
+				&mut protection,
+				&ctn_file_absolute_path,
+				config.lock_directories(),
+			) {
+				tracing::error!("Unable to protect the content file: {e}");
+				hashed_lst.protection_error = Some(e.to_string());
+			}
+		}
+		Ok(hashed_lst)
+	}
+
+	// Calculates the fingerprints without writing the content file nor altering the files, which
+	// is required when the directory is only compared to another one.
+	pub fn fingerprints(
+		&self,
+		config: &Config,
+		hash_func: HashFunc,
+		tx: ExternalEventSender,
+	) -> io::Result<HashedFileList> {
+		let ctn_file_absolute_path = self.get_content_file_absolute_path(config)?;
+		self.hash_files(&ctn_file_absolute_path, hash_func, tx)
+	}
+
+	// Compares the metadata captured when the directory has been loaded, hence before the
+	// protection is applied again, to the ones recorded when the content file has been written.
+	fn get_metadata_changes(
+		&self,
+		ctn_file_absolute_path: &Path,
+	) -> io::Result<Vec<MetadataChange>> {
+		let Some(manifest) = MetadataManifest::load(ctn_file_absolute_path)? else {
+			return Ok(Vec::new());
+		};
+		let mut changes: Vec<MetadataChange> = self
+			.files
+			.values()
+			.filter_map(|f| {
+				f.metadata
+					.as_ref()
+					.map(|m| manifest.get_changes(f.get_relative_path(), m))
+			})
+			.flatten()
+			.collect();
+		changes.sort_by(|a, b| a.get_path().cmp(b.get_path()));
+		Ok(changes)
+	}
+
+	pub fn capture_metadata(&mut self) {
+		for file in self.files.values_mut() {
+			match file
+				.get_absolute_path()
+				.and_then(|p| FileMetadata::from_path(&p))
+			{
+				Ok(metadata) => file.metadata = Some(metadata),
+				Err(e) => tracing::error!(
+					"{}: unable to read metadata: {e}",
+					file.relative_path.display()
+				),
+			}
+		}
+	}
+
+	fn hash_files(
+		&self,
+		ctn_file_absolute_path: &Path,
+		hash_func: HashFunc,
+		tx: ExternalEventSender,
+	) -> io::Result<HashedFileList> {
 		let files: HashMap<FileId, HashedFile> = HashMap::with_capacity(self.files.len());
 		let files_mx = std::sync::Mutex::new(files);
-		let set_ro = config.set_files_as_readonly();
 		self.files
 			.par_iter()
 			.try_for_each(|(k, f)| -> io::Result<()> {
 				let abs_path = f.get_absolute_path()?;
-				if abs_path != ctn_file_absolute_path {
-					if set_ro {
-						set_readonly(abs_path)?;
-					}
+				if !is_content_file_companion(&abs_path, ctn_file_absolute_path) {
 					let file = f.hash(hash_func, tx.clone())?;
 					let mut files_lock = files_mx.lock().unwrap();
 					files_lock.insert(k.clone(), file);
@@ -281,34 +505,9 @@ impl NonHashedFileList {
 				Ok(())
 			})?;
 		let files = files_mx.into_inner().unwrap();
-
-		let mut duplicated_files: HashMap<String, HashSet<FileId>> =
-			HashMap::with_capacity(self.files.len());
-		for (k, f) in files.iter() {
-			match duplicated_files.get_mut(&f.hash) {
-				Some(set) => {
-					set.insert(k.clone());
-				}
-				None => {
-					let mut set = HashSet::with_capacity(1);
-					set.insert(k.clone());
-					duplicated_files.insert(f.hash.clone(), set);
-				}
-			};
-		}
-		duplicated_files.retain(|_, v| v.len() > 1);
-		let hashed_lst = HashedFileList {
-			id: Uuid::new_v4(),
-			base_dir: self.base_dir.clone(),
-			files,
-			duplicated_files,
-			result: CheckResult::None,
-		};
-		hashed_lst
-			.write_content_file_opt(ctn_file_absolute_path.as_path(), config.content_file_format)?;
-		if set_ro {
-			set_readonly(ctn_file_absolute_path)?;
-		}
+		let mut hashed_lst = HashedFileList::from_files(&self.base_dir, files.into_values());
+		hashed_lst.set_explicit_content_file_path(self.get_explicit_content_file_path());
+		hashed_lst.set_case(self.case.clone());
 		Ok(hashed_lst)
 	}
 }
@@ -317,9 +516,21 @@ impl NonHashedFileList {
 pub struct HashedFileList {
 	id: Uuid,
 	base_dir: PathBuf,
+	explicit_content_file_path: Option<PathBuf>,
+	case: CaseMetadata,
 	files: HashMap<FileId, HashedFile>,
 	duplicated_files: HashMap<String, HashSet<FileId>>,
 	result: CheckResult,
+	renamed_files: Vec<RenamedFile>,
+	receipt_coverage: Option<BTreeMap<PathBuf, Vec<PathBuf>>>,
+	parse_warnings: Vec<ReceiptParseError>,
+	known_files: HashMap<FileId, KnownFileMatch>,
+	previous_evidence: HashMap<FileId, PreviousEvidence>,
+	unprotected_files: Vec<PathBuf>,
+	metadata_changes: Vec<MetadataChange>,
+	case_write_error: Option<String>,
+	protection_error: Option<String>,
+	merkle_root: OnceLock<String>,
 }
 
 common_lst_impl!(HashedFileList, HashedFile);
@@ -329,10 +540,67 @@ impl HashedFileList {
 		Self {
 			id: Uuid::new_v4(),
 			base_dir: PathBuf::new(),
+			explicit_content_file_path: None,
+			case: CaseMetadata::default(),
 			files: HashMap::new(),
 			duplicated_files: HashMap::new(),
 			result: CheckResult::None,
+			renamed_files: Vec::new(),
+			receipt_coverage: None,
+			parse_warnings: Vec::new(),
+			known_files: HashMap::new(),
+			previous_evidence: HashMap::new(),
+			unprotected_files: Vec::new(),
+			metadata_changes: Vec::new(),
+			case_write_error: None,
+			protection_error: None,
+			merkle_root: OnceLock::new(),
+		}
+	}
+
+	pub fn from_files<I>(base_dir: &Path, files: I) -> Self
+	where
+		I: IntoIterator<Item = HashedFile>,
+	{
+		let files: HashMap<FileId, HashedFile> =
+			files.into_iter().map(|f| (f.get_id(), f)).collect();
+		let mut duplicated_files: HashMap<String, HashSet<FileId>> =
+			HashMap::with_capacity(files.len());
+		for (k, f) in files.iter() {
+			match duplicated_files.get_mut(&f.hash) {
+				Some(set) => {
+					set.insert(k.clone());
+				}
+				None => {
+					let mut set = HashSet::with_capacity(1);
+					set.insert(k.clone());
+					duplicated_files.insert(f.hash.clone(), set);
+				}
+			};
 		}
+		duplicated_files.retain(|_, v| v.len() > 1);
+		let lst = Self {
+			id: Uuid::new_v4(),
+			base_dir: base_dir.to_path_buf(),
+			explicit_content_file_path: None,
+			case: CaseMetadata::default(),
+			files,
+			duplicated_files,
+			result: CheckResult::None,
+			renamed_files: Vec::new(),
+			receipt_coverage: None,
+			parse_warnings: Vec::new(),
+			known_files: HashMap::new(),
+			previous_evidence: HashMap::new(),
+			unprotected_files: Vec::new(),
+			metadata_changes: Vec::new(),
+			case_write_error: None,
+			protection_error: None,
+			merkle_root: OnceLock::new(),
+		};
+		// The root is displayed on each rendering, so it is computed once the list is complete.
+		lst.get_merkle_root();
+		lst
 	}
 
 	pub fn get_files(&self) -> std::collections::hash_map::Values<'_, FileId, HashedFile> {
@@ -341,6 +609,7 @@ impl HashedFileList {
 
 	pub fn insert_file(&mut self, file: HashedFile) {
 		self.files.insert(file.get_id(), file);
+		self.merkle_root = OnceLock::new();
 	}
 
 	pub fn set_result_ok(&mut self) {
@@ -360,6 +629,144 @@ impl HashedFileList {
 		}
 	}
 
+	pub fn push_renamed_file(&mut self, renamed: RenamedFile) {
+		self.renamed_files.push(renamed);
+	}
+
+	pub fn get_renamed_files(&self) -> &[RenamedFile] {
+		&self.renamed_files
+	}
+
+	pub fn add_receipt_coverage(&mut self, receipt_path: &Path, covered: &[PathBuf]) {
+		let coverage = self.receipt_coverage.get_or_insert_default();
+		for path in covered {
+			coverage
+				.entry(path.to_path_buf())
+				.or_default()
+				.push(receipt_path.to_path_buf());
+		}
+	}
+
+	// Relative paths of the files listed in at least one receipt, along with the receipts' paths.
+	// Returns `None` if the files have not been checked against any receipt.
+	pub fn get_receipt_coverage(&self) -> Option<&BTreeMap<PathBuf, Vec<PathBuf>>> {
+		self.receipt_coverage.as_ref()
+	}
+
+	pub fn push_parse_warning(&mut self, warning: ReceiptParseError) {
+		self.parse_warnings.push(warning);
+	}
+
+	pub fn get_parse_warnings(&self) -> &[ReceiptParseError] {
+		&self.parse_warnings
+	}
+
+	pub fn set_known_files(&mut self, known_files: &KnownFiles) {
+		self.known_files = self
+			.files
+			.iter()
+			.filter_map(|(k, f)| known_files.find(f).map(|m| (k.clone(), m)))
+			.collect();
+	}
+
+	pub fn get_known_file(&self, file: &HashedFile) -> Option<&KnownFileMatch> {
+		self.known_files.get(&file.get_id())
+	}
+
+	pub fn get_known_files(&self) -> Vec<(HashedFile, KnownFileMatch)> {
+		let mut known_files: Vec<(HashedFile, KnownFileMatch)> = self
+			.known_files
+			.iter()
+			.filter_map(|(k, m)| self.files.get(k).map(|f| (f.clone(), m.clone())))
+			.collect();
+		known_files.sort_by(|a, b| a.0.get_relative_path().cmp(b.0.get_relative_path()));
+		known_files
+	}
+
+	pub fn nb_known_files(&self, kind: KnownFileKind) -> usize {
+		self.known_files
+			.values()
+			.filter(|m| m.get_kind() == kind)
+			.count()
+	}
+
+	pub fn set_previous_evidence(&mut self, history: &History, case_id: &str) {
+		self.previous_evidence = self
+			.files
+			.iter()
+			.filter_map(|(k, f)| match history.find(case_id, f) {
+				Ok(prev) => prev.map(|p| (k.clone(), p)),
+				Err(e) => {
+					tracing::error!(
+						"History: unable to look for {}: {e}",
+						f.get_relative_path().display()
+					);
+					None
+				}
+			})
+			.collect();
+	}
+
+	pub fn get_previous_evidence(&self) -> Vec<(HashedFile, PreviousEvidence)> {
+		let mut previous: Vec<(HashedFile, PreviousEvidence)> = self
+			.previous_evidence
+			.iter()
+			.filter_map(|(k, p)| self.files.get(k).map(|f| (f.clone(), p.clone())))
+			.collect();
+		previous.sort_by(|a, b| a.0.get_relative_path().cmp(b.0.get_relative_path()));
+		previous
+	}
+
+	pub fn nb_previous_evidence(&self) -> usize {
+		self.previous_evidence.len()
+	}
+
+	pub fn get_unprotected_files(&self) -> &[PathBuf] {
+		&self.unprotected_files
+	}
+
+	pub fn get_metadata_changes(&self) -> &[MetadataChange] {
+		&self.metadata_changes
+	}
+
+	pub fn get_case_write_error(&self) -> Option<&str> {
+		self.case_write_error.as_deref()
+	}
+
+	pub fn get_protection_error(&self) -> Option<&str> {
+		self.protection_error.as_deref()
+	}
+
+	// The metadata are captured once the files have been protected, since setting them as
+	// read-only alters their mode and change time.
+	// Files whose metadata cannot be read are left out of the manifest, as when they are captured
+	// while loading the directory.
+	pub fn write_metadata_manifest(&self, ctn_file_absolute_path: &Path) -> io::Result<PathBuf> {
+		let mut manifest = match MetadataManifest::load(ctn_file_absolute_path) {
+			Ok(manifest) => manifest.unwrap_or_default(),
+			Err(e) => {
+				tracing::error!("Unable to read the metadata manifest, replacing it: {e}");
+				MetadataManifest::default()
+			}
+		};
+		for file in self.files.values() {
+			match file
+				.get_absolute_path()
+				.and_then(|p| FileMetadata::from_path(&p))
+			{
+				Ok(metadata) => manifest.insert(file.get_relative_path(), metadata),
+				Err(e) => {
+					tracing::error!(
+						"{}: unable to read metadata: {e}",
+						file.get_relative_path().display()
+					);
+					manifest.remove(file.get_relative_path());
+				}
+			}
+		}
+		manifest.write(ctn_file_absolute_path)
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.files.is_empty()
 	}
@@ -382,21 +789,39 @@ impl HashedFileList {
 		}
 		let mut occurrences: Vec<(HashFunc, usize)> =
 			occurrences.iter().map(|(k, v)| (*k, *v)).collect();
-		occurrences.sort_by(|a, b| a.1.cmp(&b.1));
+		occurrences.sort_by_key(|e| e.1);
 		let (hash_func, _) = occurrences.pop().unwrap();
 		hash_func
 	}
 
-	fn write_content_file_opt(
+	pub fn get_merkle_root(&self) -> String {
+		self.merkle_root
+			.get_or_init(|| {
+				let hash_func = if self.is_empty() {
+					HashFunc::default()
+				} else {
+					self.get_main_hashing_function()
+				};
+				merkle_root_files(self.files.values(), hash_func)
+			})
+			.clone()
+	}
+
+	pub fn write_content_file_opt(
 		&self,
 		ctn_file_path: &Path,
 		format: ContentFileFormat,
-	) -> io::Result<()> {
+		with_trailer: bool,
+	) -> io::Result<bool> {
 		if !ctn_file_path.exists() {
+			if let Some(parent) = ctn_file_path.parent() {
+				fs::create_dir_all(parent)?;
+			}
 			let mut f = File::create_new(ctn_file_path)?;
-			return format.write_content_file(&mut f, self);
+			format.write_content_file(&mut f, self, with_trailer)?;
+			return Ok(true);
 		}
-		Ok(())
+		Ok(false)
 	}
 }
 
@@ -436,6 +861,7 @@ pub struct NonHashedFile {
 	size: u64,
 	is_hidden: bool,
 	is_system: bool,
+	metadata: Option<FileMetadata>,
 }
 
 common_file_impl!(NonHashedFile);
@@ -445,6 +871,10 @@ impl NonHashedFile {
 		self.size == 0
 	}
 
+	pub fn get_size(&self) -> u64 {
+		self.size
+	}
+
 	pub fn is_hidden(&self) -> bool {
 		self.is_hidden
 	}
@@ -463,6 +893,7 @@ impl NonHashedFile {
 			size: 0,
 			is_hidden: is_hidden_file(path)?,
 			is_system: is_system_file(path)?,
+			metadata: None,
 		};
 		file.size = file.get_absolute_path()?.metadata()?.len();
 		Ok(file)
@@ -475,6 +906,7 @@ impl NonHashedFile {
 			base_dir: self.base_dir.clone(),
 			relative_path: self.relative_path.clone(),
 			size: self.size,
+			size_tolerance: 0,
 			hash,
 			hash_func,
 		})
@@ -486,6 +918,7 @@ pub struct HashedFile {
 	base_dir: PathBuf,
 	relative_path: PathBuf,
 	size: u64,
+	size_tolerance: u64,
 	hash: String,
 	hash_func: HashFunc,
 }
@@ -517,11 +950,19 @@ impl HashedFile {
 			base_dir: base_dir.as_ref().into(),
 			relative_path: relative_path.as_ref().to_path_buf(),
 			size,
+			size_tolerance: 0,
 			hash: hash.as_ref().into(),
 			hash_func,
 		}
 	}
 
+	// Some receipts only display a rounded size, in which case the actual size may differ from the
+	// stored one by at most the tolerance.
+	pub fn with_size_tolerance(mut self, size_tolerance: u64) -> Self {
+		self.size_tolerance = size_tolerance;
+		self
+	}
+
 	pub fn get_base_dir(&self) -> PathBuf {
 		self.base_dir.clone()
 	}
@@ -530,6 +971,15 @@ impl HashedFile {
 		self.size
 	}
 
+	pub fn get_size_tolerance(&self) -> u64 {
+		self.size_tolerance
+	}
+
+	// A size of 0 means the size is unknown.
+	pub fn size_matches(&self, size: u64) -> bool {
+		self.size == 0 || self.size.abs_diff(size) <= self.size_tolerance
+	}
+
 	pub fn get_hash(&self) -> &str {
 		self.hash.as_str()
 	}
@@ -539,13 +989,26 @@ impl HashedFile {
 	}
 }
 
-#[inline]
-fn set_readonly(path: PathBuf) -> io::Result<()> {
-	let metadata = path.metadata()?;
-	let mut permissions = metadata.permissions();
-	permissions.set_readonly(true);
-	fs::set_permissions(path, permissions)?;
-	Ok(())
+fn protect_content_file(
+	protection: &mut Protection,
+	ctn_file_absolute_path: &Path,
+	lock_directories: bool,
+) -> io::Result<()> {
+	protection.add(ctn_file_absolute_path)?;
+	if lock_directories {
+		protection.add_directories()?;
+	}
+	protection.write(ctn_file_absolute_path)?;
+	protection.apply()
+}
+
+// Files written by loutre next to the content file are never part of the evidence set.
+pub fn is_content_file_companion(path: &Path, ctn_file_absolute_path: &Path) -> bool {
+	path == ctn_file_absolute_path
+		|| is_content_file_archive(path, ctn_file_absolute_path)
+		|| is_case_file(path, ctn_file_absolute_path)
+		|| is_protection_file(path, ctn_file_absolute_path)
+		|| is_metadata_file(path, ctn_file_absolute_path)
 }
 
 #[cfg(unix)]
@@ -90,6 +90,18 @@ macro_rules! blake3_hash_file {
 	}};
 }
 
+macro_rules! alg_hash_bytes {
+	($data: ident, $alg: ident) => {{
+		let mut hasher = $alg::new();
+		hasher.update($data);
+		hasher
+			.finalize()
+			.iter()
+			.map(|b| format!("{:02x}", b))
+			.collect::<String>()
+	}};
+}
+
 #[derive(Copy, Clone, Debug, Default, EnumIter, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub enum HashFunc {
 	#[serde(rename = "sha-256")]
@@ -160,7 +172,14 @@ impl HashFunc {
 	) -> io::Result<String> {
 		let file = file.as_ref();
 		tracing::info!("Calculating the {self} hash of file: {}", file.display());
-		let mut f = File::open(file)?;
+		self.hash_reader(File::open(file)?, tx)
+	}
+
+	pub fn hash_reader<R: Read>(
+		&self,
+		mut f: R,
+		tx: Option<ExternalEventSender>,
+	) -> io::Result<String> {
 		let mut buffer = [0; crate::BUFF_SIZE];
 		match self {
 			Self::Sha256 => alg_hash_file!(f, buffer, tx, Sha256),
@@ -174,4 +193,18 @@ impl HashFunc {
 			Self::Blake3 => blake3_hash_file!(f, buffer, tx, Blake3),
 		}
 	}
+
+	pub fn hash_bytes(&self, data: &[u8]) -> String {
+		match self {
+			Self::Sha256 => alg_hash_bytes!(data, Sha256),
+			Self::Sha384 => alg_hash_bytes!(data, Sha384),
+			Self::Sha512 => alg_hash_bytes!(data, Sha512),
+			Self::Sha3_256 => alg_hash_bytes!(data, Sha3_256),
+			Self::Sha3_384 => alg_hash_bytes!(data, Sha3_384),
+			Self::Sha3_512 => alg_hash_bytes!(data, Sha3_512),
+			Self::Blake2s => alg_hash_bytes!(data, Blake2s256),
+			Self::Blake2b => alg_hash_bytes!(data, Blake2b512),
+			Self::Blake3 => blake3::hash(data).to_hex().to_string(),
+		}
+	}
 }
@@ -0,0 +1,165 @@
+use crate::config::Config;
+use crate::content_file_location::external_dir_name;
+use crate::files::{HashedFile, HashedFileList};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+const HISTORY_FILE_NAME: &str = "history.sqlite";
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS evidence (
+	case_id TEXT NOT NULL,
+	path TEXT NOT NULL,
+	size INTEGER NOT NULL,
+	hash_func TEXT NOT NULL,
+	digest TEXT NOT NULL,
+	recorded_at TEXT NOT NULL,
+	UNIQUE (case_id, path, hash_func, digest)
+);
+CREATE INDEX IF NOT EXISTS evidence_digest ON evidence (hash_func, digest);
+";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreviousEvidence {
+	case_id: String,
+	path: PathBuf,
+	recorded_at: DateTime<Utc>,
+}
+
+impl PreviousEvidence {
+	pub fn get_case_id(&self) -> &str {
+		&self.case_id
+	}
+
+	pub fn get_path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn get_recorded_at(&self) -> DateTime<Utc> {
+		self.recorded_at
+	}
+}
+
+pub struct History {
+	conn: Connection,
+}
+
+impl History {
+	pub fn open() -> rusqlite::Result<Self> {
+		let path = Config::get_config_dir().join(HISTORY_FILE_NAME);
+		tracing::info!("Opening the history database: {}", path.display());
+		Self::from_connection(Connection::open(path)?)
+	}
+
+	fn from_connection(conn: Connection) -> rusqlite::Result<Self> {
+		conn.execute_batch(SCHEMA)?;
+		Ok(Self { conn })
+	}
+
+	// Only the earliest record is returned when a file has been received in several other cases.
+	pub fn find(
+		&self,
+		case_id: &str,
+		file: &HashedFile,
+	) -> rusqlite::Result<Option<PreviousEvidence>> {
+		self.conn
+			.query_row(
+				"SELECT case_id, path, recorded_at FROM evidence
+				WHERE hash_func = ?1 AND digest = ?2 AND case_id <> ?3
+				ORDER BY recorded_at ASC LIMIT 1",
+				params![
+					file.get_hash_func().to_string(),
+					file.get_hash().to_ascii_lowercase(),
+					case_id
+				],
+				|row| {
+					let path: String = row.get(1)?;
+					let recorded_at: String = row.get(2)?;
+					Ok(PreviousEvidence {
+						case_id: row.get(0)?,
+						path: PathBuf::from(path),
+						recorded_at: DateTime::parse_from_rfc3339(&recorded_at)
+							.map(|d| d.with_timezone(&Utc))
+							.unwrap_or_default(),
+					})
+				},
+			)
+			.optional()
+	}
+
+	// Files which have already been recorded for this case are kept with their original date.
+	pub fn record(&mut self, case_id: &str, file_list: &HashedFileList) -> rusqlite::Result<usize> {
+		let recorded_at = Utc::now().to_rfc3339();
+		let tx = self.conn.transaction()?;
+		let mut nb_new = 0;
+		{
+			let mut stmt = tx.prepare(
+				"INSERT OR IGNORE INTO evidence (case_id, path, size, hash_func, digest, recorded_at)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+			)?;
+			for file in file_list.get_files() {
+				nb_new += stmt.execute(params![
+					case_id,
+					file.get_relative_path().display().to_string(),
+					file.get_size() as i64,
+					file.get_hash_func().to_string(),
+					file.get_hash().to_ascii_lowercase(),
+					recorded_at
+				])?;
+			}
+		}
+		tx.commit()?;
+		tracing::info!("History: {nb_new} new files recorded for case {case_id}");
+		Ok(nb_new)
+	}
+}
+
+// Until a case identifier is explicitly set, the evidence directory's name is used, suffixed with
+// a short fingerprint of its full path so unrelated directories sharing the same name are not
+// considered as the same case.
+pub fn default_case_id(base_dir: &Path) -> String {
+	let base_dir = base_dir
+		.canonicalize()
+		.unwrap_or_else(|_| base_dir.to_path_buf());
+	external_dir_name(&base_dir)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::hash::HashFunc;
+
+	const HASH_A: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+	const HASH_B: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+
+	fn get_history() -> History {
+		History::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+	}
+
+	#[test]
+	fn find_in_other_case() {
+		let mut history = get_history();
+		let mut lst = HashedFileList::new();
+		lst.insert_file(HashedFile::new("a.txt", 4, HASH_A, HashFunc::Sha256));
+		assert_eq!(history.record("case-1", &lst).unwrap(), 1);
+		assert_eq!(history.record("case-1", &lst).unwrap(), 0);
+
+		let file = HashedFile::new("other.txt", 4, HASH_A.to_uppercase(), HashFunc::Sha256);
+		let prev = history.find("case-2", &file).unwrap().unwrap();
+		assert_eq!(prev.get_case_id(), "case-1");
+		assert_eq!(prev.get_path(), Path::new("a.txt"));
+		assert!(history.find("case-1", &file).unwrap().is_none());
+
+		let file = HashedFile::new("a.txt", 4, HASH_A, HashFunc::Blake3);
+		assert!(history.find("case-2", &file).unwrap().is_none());
+		let file = HashedFile::new("a.txt", 4, HASH_B, HashFunc::Sha256);
+		assert!(history.find("case-2", &file).unwrap().is_none());
+	}
+
+	#[test]
+	fn case_id() {
+		let case_id = default_case_id(Path::new("/tmp/a/case 42"));
+		assert!(case_id.starts_with("case 42-"));
+		assert_ne!(case_id, default_case_id(Path::new("/tmp/b/case 42")));
+	}
+}
@@ -0,0 +1,291 @@
+use crate::files::HashedFile;
+use crate::hash::HashFunc;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{metadata, read_to_string};
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+const CSV_SEPARATOR: char = ',';
+const CSV_QUOTE: char = '"';
+const COMMENT_PREFIX: char = '#';
+
+static HASH_SETS: Mutex<Option<HashMap<PathBuf, CachedHashSet>>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, Hash, PartialEq)]
+pub enum KnownFileKind {
+	#[serde(rename = "known-good")]
+	Good,
+	#[serde(rename = "known-bad")]
+	Bad,
+}
+
+impl KnownFileKind {
+	pub fn get_value(&self) -> &'static str {
+		match self {
+			Self::Good => "known-good",
+			Self::Bad => "known-bad",
+		}
+	}
+}
+
+impl FromStr for KnownFileKind {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"known-good" => Ok(Self::Good),
+			"known-bad" => Ok(Self::Bad),
+			_ => Err(()),
+		}
+	}
+}
+
+// Hash sets are referenced in the configuration instead of being copied, so an updated set does
+// not have to be imported again.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct KnownHashSet {
+	pub name: String,
+	pub path: PathBuf,
+	pub kind: KnownFileKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KnownFileMatch {
+	kind: KnownFileKind,
+	set_name: String,
+}
+
+impl KnownFileMatch {
+	pub fn get_kind(&self) -> KnownFileKind {
+		self.kind
+	}
+
+	pub fn get_set_name(&self) -> &str {
+		&self.set_name
+	}
+}
+
+struct LoadedHashSet {
+	name: String,
+	kind: KnownFileKind,
+	digests: Arc<HashSet<String>>,
+}
+
+struct CachedHashSet {
+	modified: SystemTime,
+	digests: Arc<HashSet<String>>,
+}
+
+#[derive(Default)]
+pub struct KnownFiles {
+	sets: Vec<LoadedHashSet>,
+}
+
+impl KnownFiles {
+	pub fn load(sets: &[KnownHashSet]) -> Self {
+		let mut known_files = Self::default();
+		for set in sets {
+			match read_hash_set(set) {
+				Ok(digests) => {
+					if digests.is_empty() {
+						tracing::warn!(
+							"{}: no supported fingerprint found in {}",
+							set.name,
+							set.path.display()
+						);
+					} else {
+						tracing::info!("{}: {} known fingerprints", set.name, digests.len());
+					}
+					known_files.sets.push(LoadedHashSet {
+						name: set.name.clone(),
+						kind: set.kind,
+						digests,
+					});
+				}
+				Err(e) => {
+					tracing::error!("{}: unable to read the hash set: {e}", set.path.display())
+				}
+			}
+		}
+		known_files
+	}
+
+	// A file listed in both a known-good and a known-bad set is reported as known-bad.
+	pub fn find(&self, file: &HashedFile) -> Option<KnownFileMatch> {
+		let digest = file.get_hash().to_ascii_lowercase();
+		self.sets
+			.iter()
+			.filter(|s| s.digests.contains(&digest))
+			.max_by_key(|s| s.kind == KnownFileKind::Bad)
+			.map(|s| KnownFileMatch {
+				kind: s.kind,
+				set_name: s.name.clone(),
+			})
+	}
+}
+
+// Large hash sets, such as the NSRL RDS, are slow to parse. Therefore, they are kept in memory and
+// only read again once the file has been modified.
+pub fn read_hash_set(set: &KnownHashSet) -> io::Result<Arc<HashSet<String>>> {
+	let modified = metadata(&set.path)?.modified().ok();
+	if let Some(modified) = modified {
+		let cache = HASH_SETS.lock().unwrap();
+		if let Some(cached) = cache.as_ref().and_then(|c| c.get(&set.path)) {
+			if cached.modified == modified {
+				return Ok(cached.digests.clone());
+			}
+		}
+	}
+	let content = read_to_string(&set.path)?;
+	let digests = Arc::new(parse_hash_set(&content));
+	if let Some(modified) = modified {
+		let mut cache = HASH_SETS.lock().unwrap();
+		cache.get_or_insert_with(HashMap::new).insert(
+			set.path.clone(),
+			CachedHashSet {
+				modified,
+				digests: digests.clone(),
+			},
+		);
+	}
+	Ok(digests)
+}
+
+// Both plain lists, having the digest as the first element of each line, and CSV files with a
+// header, such as the NSRL RDS, are supported. In the latter case, only the columns of supported
+// hashing functions are read.
+pub fn parse_hash_set(content: &str) -> HashSet<String> {
+	let mut lines = content
+		.lines()
+		.map(str::trim)
+		.filter(|l| !l.is_empty() && !l.starts_with(COMMENT_PREFIX));
+	let Some(first_line) = lines.next() else {
+		return HashSet::new();
+	};
+	let header: Vec<String> = split_csv_line(first_line);
+	let hash_columns: Vec<usize> = header
+		.iter()
+		.enumerate()
+		.filter(|(_, name)| HashFunc::from_str(name).is_ok())
+		.map(|(i, _)| i)
+		.collect();
+	if header.len() > 1 && !hash_columns.is_empty() {
+		return lines
+			.flat_map(|line| {
+				let fields = split_csv_line(line);
+				hash_columns
+					.iter()
+					.filter_map(|i| fields.get(*i).and_then(|f| normalize_digest(f)))
+					.collect::<Vec<String>>()
+			})
+			.collect();
+	}
+	std::iter::once(first_line)
+		.chain(lines)
+		.filter_map(|line| line.split_whitespace().next())
+		.filter_map(normalize_digest)
+		.collect()
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+	line.split(CSV_SEPARATOR)
+		.map(|f| f.trim().trim_matches(CSV_QUOTE).to_string())
+		.collect()
+}
+
+fn normalize_digest(digest: &str) -> Option<String> {
+	let digest = digest.trim_start_matches('\\');
+	if matches!(digest.len(), 64 | 96 | 128) && digest.chars().all(|c| c.is_ascii_hexdigit()) {
+		Some(digest.to_ascii_lowercase())
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HASH_A: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+	const HASH_B: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+
+	#[test]
+	fn plain_list() {
+		let content = format!(
+			"# known files\n{}\n\n{HASH_B}  dir/b.txt\nnot a digest\n",
+			HASH_A.to_uppercase()
+		);
+		let set = parse_hash_set(&content);
+		assert_eq!(set.len(), 2);
+		assert!(set.contains(HASH_A));
+		assert!(set.contains(HASH_B));
+	}
+
+	#[test]
+	fn csv() {
+		let content = format!(
+			"\"SHA-1\",\"MD5\",\"SHA-256\",\"FileName\"\n\"A94A8FE5CCB19BA61C4C0873D391E987982FBBD3\",\"098F6BCD4621D373CADE4E832627B4F6\",\"{}\",\"test.txt\"\n\"A94A8FE5CCB19BA61C4C0873D391E987982FBBD3\",\"098F6BCD4621D373CADE4E832627B4F6\",\"\",\"x.txt\"\n",
+			HASH_A.to_uppercase()
+		);
+		let set = parse_hash_set(&content);
+		assert_eq!(set.len(), 1);
+		assert!(set.contains(HASH_A));
+	}
+
+	#[test]
+	fn csv_without_supported_function() {
+		let content = "\"SHA-1\",\"MD5\",\"FileName\"\n\"A94A8FE5CCB19BA61C4C0873D391E987982FBBD3\",\"098F6BCD4621D373CADE4E832627B4F6\",\"test.txt\"\n";
+		assert!(parse_hash_set(content).is_empty());
+	}
+
+	#[test]
+	fn cached_set() {
+		let path = std::env::temp_dir().join(format!("loutre-{}.txt", uuid::Uuid::new_v4()));
+		let set = KnownHashSet {
+			name: "test".to_string(),
+			path: path.clone(),
+			kind: KnownFileKind::Good,
+		};
+		std::fs::write(&path, format!("{HASH_A}\n")).unwrap();
+		let digests = read_hash_set(&set).unwrap();
+		assert_eq!(digests.len(), 1);
+		assert!(Arc::ptr_eq(&digests, &read_hash_set(&set).unwrap()));
+
+		std::fs::write(&path, format!("{HASH_A}\n{HASH_B}\n")).unwrap();
+		let file = std::fs::File::options().write(true).open(&path).unwrap();
+		file.set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+			.unwrap();
+		assert_eq!(read_hash_set(&set).unwrap().len(), 2);
+		std::fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn find() {
+		let mut known_files = KnownFiles::default();
+		known_files.sets.push(LoadedHashSet {
+			name: "good".to_string(),
+			kind: KnownFileKind::Good,
+			digests: Arc::new([HASH_A.to_string(), HASH_B.to_string()].into()),
+		});
+		known_files.sets.push(LoadedHashSet {
+			name: "bad".to_string(),
+			kind: KnownFileKind::Bad,
+			digests: Arc::new([HASH_B.to_string()].into()),
+		});
+		let file = HashedFile::new("a.txt", 4, HASH_A, HashFunc::Sha256);
+		let m = known_files.find(&file).unwrap();
+		assert_eq!(m.get_kind(), KnownFileKind::Good);
+		assert_eq!(m.get_set_name(), "good");
+		let file = HashedFile::new("b.txt", 4, HASH_B, HashFunc::Sha256);
+		assert_eq!(
+			known_files.find(&file).unwrap().get_kind(),
+			KnownFileKind::Bad
+		);
+		let file = HashedFile::new("c.txt", 4, "00", HashFunc::Sha256);
+		assert!(known_files.find(&file).is_none());
+	}
+}
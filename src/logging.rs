@@ -0,0 +1,167 @@
+use crate::config::Config;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{copy, read_dir};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use strum::EnumIter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+
+const LOG_DIR_NAME: &str = "logs";
+const LOG_FILE_PREFIX: &str = "loutre";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+
+#[derive(Copy, Clone, Debug, Default, EnumIter, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+	Error,
+	Warn,
+	#[default]
+	Info,
+	Debug,
+	Trace,
+}
+
+impl fmt::Display for LogLevel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = match self {
+			Self::Error => "error",
+			Self::Warn => "warn",
+			Self::Info => "info",
+			Self::Debug => "debug",
+			Self::Trace => "trace",
+		};
+		write!(f, "{s}")
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseLogLevelError;
+
+impl FromStr for LogLevel {
+	type Err = ParseLogLevelError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"error" => Ok(Self::Error),
+			"warn" => Ok(Self::Warn),
+			"info" => Ok(Self::Info),
+			"debug" => Ok(Self::Debug),
+			"trace" => Ok(Self::Trace),
+			_ => Err(ParseLogLevelError),
+		}
+	}
+}
+
+impl From<LogLevel> for LevelFilter {
+	fn from(level: LogLevel) -> Self {
+		match level {
+			LogLevel::Error => LevelFilter::ERROR,
+			LogLevel::Warn => LevelFilter::WARN,
+			LogLevel::Info => LevelFilter::INFO,
+			LogLevel::Debug => LevelFilter::DEBUG,
+			LogLevel::Trace => LevelFilter::TRACE,
+		}
+	}
+}
+
+pub fn get_log_dir() -> PathBuf {
+	Config::get_config_dir().join(LOG_DIR_NAME)
+}
+
+// The returned guard flushes the pending logs when dropped and must therefore be kept alive until
+// the application exits.
+pub fn init(level: LogLevel) -> Option<WorkerGuard> {
+	let appender = RollingFileAppender::builder()
+		.rotation(Rotation::DAILY)
+		.filename_prefix(LOG_FILE_PREFIX)
+		.filename_suffix(LOG_FILE_SUFFIX)
+		.max_log_files(MAX_LOG_FILES)
+		.build(get_log_dir());
+	let appender = match appender {
+		Ok(appender) => appender,
+		Err(e) => {
+			eprintln!("Unable to create the log file: {e}");
+			return None;
+		}
+	};
+	let (writer, guard) = tracing_appender::non_blocking(appender);
+	let res = tracing_subscriber::fmt()
+		.with_writer(writer)
+		.with_ansi(false)
+		.with_max_level(LevelFilter::from(level))
+		.try_init();
+	if let Err(e) = res {
+		eprintln!("Unable to install the log subscriber: {e}");
+		return None;
+	}
+	Some(guard)
+}
+
+fn is_log_file(path: &Path) -> bool {
+	match path.file_name().and_then(|n| n.to_str()) {
+		Some(name) => {
+			name.starts_with(&format!("{LOG_FILE_PREFIX}."))
+				&& name.ends_with(&format!(".{LOG_FILE_SUFFIX}"))
+		}
+		None => false,
+	}
+}
+
+// Log files are named after their date, hence the most recent one comes first once sorted in
+// reverse order.
+pub fn list_log_files() -> io::Result<Vec<PathBuf>> {
+	let dir = get_log_dir();
+	if !dir.is_dir() {
+		return Ok(Vec::new());
+	}
+	let mut files: Vec<PathBuf> = read_dir(dir)?
+		.filter_map(|e| e.ok())
+		.map(|e| e.path())
+		.filter(|p| p.is_file() && is_log_file(p))
+		.collect();
+	files.sort_by(|a, b| b.cmp(a));
+	Ok(files)
+}
+
+pub fn export_log_files(dest_dir: &Path) -> io::Result<usize> {
+	let files = list_log_files()?;
+	for file in &files {
+		if let Some(name) = file.file_name() {
+			copy(file, dest_dir.join(name))?;
+		}
+	}
+	tracing::info!(
+		"{} log files exported to {}",
+		files.len(),
+		dest_dir.display()
+	);
+	Ok(files.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use strum::IntoEnumIterator;
+
+	#[test]
+	fn log_level() {
+		for level in LogLevel::iter() {
+			assert_eq!(LogLevel::from_str(&level.to_string()), Ok(level));
+		}
+		assert_eq!(LogLevel::from_str("WARN"), Ok(LogLevel::Warn));
+		assert_eq!(LogLevel::from_str("verbose"), Err(ParseLogLevelError));
+		assert_eq!(LevelFilter::from(LogLevel::default()), LevelFilter::INFO);
+	}
+
+	#[test]
+	fn log_file() {
+		assert!(is_log_file(Path::new("/tmp/logs/loutre.2026-10-19.log")));
+		assert!(!is_log_file(Path::new("/tmp/logs/loutre.2026-10-19.txt")));
+		assert!(!is_log_file(Path::new("/tmp/logs/other.2026-10-19.log")));
+	}
+}
@@ -2,19 +2,35 @@
 
 mod analyse_hash;
 mod app;
+mod audit;
+mod case;
 mod check;
 mod clipboard;
+mod compare;
 mod components;
 mod config;
+mod content_file_conversion;
 mod content_file_format;
+mod content_file_location;
+mod content_file_trailer;
+mod content_file_update;
+mod copy;
 mod events;
+mod file_metadata;
 mod files;
 mod hash;
+mod history;
 mod i18n;
+mod known_files;
+mod logging;
+mod merkle;
 mod nb_repr;
 mod notifications;
 mod parsers;
+mod path_matching;
 mod progress;
+mod protection;
+mod quick_verify;
 mod receipt;
 mod serializers;
 mod templates;
@@ -38,6 +54,10 @@ const CONFIG_FILE_SUBDIR: &str = "loutre";
 const CONFIG_FILE_NAME: &str = "config.toml";
 #[cfg(feature = "nightly")]
 const CONFIG_FILE_NAME: &str = "config.nightly.toml";
+#[cfg(not(feature = "nightly"))]
+const CONTENT_FILE_ASSOCIATIONS_FILE_NAME: &str = "content_files.toml";
+#[cfg(feature = "nightly")]
+const CONTENT_FILE_ASSOCIATIONS_FILE_NAME: &str = "content_files.nightly.toml";
 
 const DEFAULT_CLIPBOARD_THRESHOLD: usize = 42;
 
@@ -45,6 +65,7 @@ const WIN_WIDTH: u32 = 820;
 const WIN_HEIGHT: u32 = 560;
 
 fn main() {
+	let _log_guard = logging::init(config::Config::init().get_log_level());
 	tracing::info!("starting app");
 
 	let raw_ico = include_bytes!("../assets/icon_rgba8.bin").to_vec();
@@ -0,0 +1,175 @@
+use crate::files::HashedFile;
+use crate::hash::HashFunc;
+use std::path::{Component, Path};
+use unicode_normalization::UnicodeNormalization;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+// The Merkle root is computed over the files sorted by their relative path. Each leaf is the
+// fingerprint of `0x00 || path || 0x00 || size || 0x00 || digest`, where the path uses `/` as a
+// separator and is normalized to NFC, the size is written in decimal and the digest in lowercase hexadecimal. Each node is
+// the fingerprint of `0x01 || left || right` where `left` and `right` are the hexadecimal
+// fingerprints of its children. A node without sibling is promoted as is to the upper level.
+pub fn merkle_root<'a, I>(files: I, hash_func: HashFunc) -> String
+where
+	I: IntoIterator<Item = (&'a Path, u64, &'a str)>,
+{
+	let mut entries: Vec<(String, u64, String)> = files
+		.into_iter()
+		.map(|(path, size, hash)| (canonical_path(path), size, hash.to_ascii_lowercase()))
+		.collect();
+	entries.sort();
+	let mut level: Vec<String> = entries
+		.iter()
+		.map(|(path, size, hash)| {
+			let mut leaf = vec![LEAF_PREFIX];
+			leaf.extend_from_slice(path.as_bytes());
+			leaf.push(LEAF_PREFIX);
+			leaf.extend_from_slice(size.to_string().as_bytes());
+			leaf.push(LEAF_PREFIX);
+			leaf.extend_from_slice(hash.as_bytes());
+			hash_func.hash_bytes(&leaf)
+		})
+		.collect();
+	if level.is_empty() {
+		return hash_func.hash_bytes(&[]);
+	}
+	while level.len() > 1 {
+		level = level
+			.chunks(2)
+			.map(|pair| match pair {
+				[left, right] => {
+					let mut node = vec![NODE_PREFIX];
+					node.extend_from_slice(left.as_bytes());
+					node.extend_from_slice(right.as_bytes());
+					hash_func.hash_bytes(&node)
+				}
+				[single] => single.to_owned(),
+				_ => unreachable!(),
+			})
+			.collect();
+	}
+	level.pop().unwrap()
+}
+
+pub fn merkle_root_files<'a, I>(files: I, hash_func: HashFunc) -> String
+where
+	I: IntoIterator<Item = &'a HashedFile>,
+{
+	merkle_root(
+		files
+			.into_iter()
+			.map(|f| (f.get_relative_path(), f.get_size(), f.get_hash())),
+		hash_func,
+	)
+}
+
+fn canonical_path(path: &Path) -> String {
+	path.components()
+		.filter_map(|c| match c {
+			Component::Normal(s) => Some(s.to_string_lossy().nfc().collect()),
+			_ => None,
+		})
+		.collect::<Vec<String>>()
+		.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	const HASH_1: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+	const HASH_2: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+	#[test]
+	fn empty() {
+		let root = merkle_root(Vec::new(), HashFunc::Sha256);
+		assert_eq!(root, HASH_2);
+	}
+
+	#[test]
+	fn single_file() {
+		let path = PathBuf::from("test.txt");
+		let root = merkle_root(vec![(path.as_path(), 4, HASH_1)], HashFunc::Sha256);
+		let leaf = format!("\0test.txt\04\0{HASH_1}");
+		assert_eq!(root, HashFunc::Sha256.hash_bytes(leaf.as_bytes()));
+	}
+
+	#[test]
+	fn order_independent() {
+		let p1 = PathBuf::from("a.txt");
+		let p2 = PathBuf::from("dir/b.txt");
+		let p3 = PathBuf::from("c.txt");
+		let root_1 = merkle_root(
+			vec![
+				(p1.as_path(), 4, HASH_1),
+				(p2.as_path(), 0, HASH_2),
+				(p3.as_path(), 0, HASH_2),
+			],
+			HashFunc::Sha256,
+		);
+		let root_2 = merkle_root(
+			vec![
+				(p3.as_path(), 0, HASH_2),
+				(p1.as_path(), 4, HASH_1),
+				(p2.as_path(), 0, HASH_2),
+			],
+			HashFunc::Sha256,
+		);
+		assert_eq!(root_1, root_2);
+	}
+
+	#[test]
+	fn three_files() {
+		let p1 = PathBuf::from("a.txt");
+		let p2 = PathBuf::from("b.txt");
+		let p3 = PathBuf::from("c.txt");
+		let root = merkle_root(
+			vec![
+				(p1.as_path(), 4, HASH_1),
+				(p2.as_path(), 0, HASH_2),
+				(p3.as_path(), 0, HASH_2),
+			],
+			HashFunc::Sha256,
+		);
+		let h = |s: String| HashFunc::Sha256.hash_bytes(s.as_bytes());
+		let l1 = h(format!("\0a.txt\04\0{HASH_1}"));
+		let l2 = h(format!("\0b.txt\00\0{HASH_2}"));
+		let l3 = h(format!("\0c.txt\00\0{HASH_2}"));
+		let n1 = h(format!("\x01{l1}{l2}"));
+		let expected = h(format!("\x01{n1}{l3}"));
+		assert_eq!(root, expected);
+	}
+
+	#[test]
+	fn content_changes() {
+		let path = PathBuf::from("test.txt");
+		let root_1 = merkle_root(vec![(path.as_path(), 4, HASH_1)], HashFunc::Sha256);
+		let root_2 = merkle_root(vec![(path.as_path(), 5, HASH_1)], HashFunc::Sha256);
+		let root_3 = merkle_root(vec![(path.as_path(), 4, HASH_2)], HashFunc::Sha256);
+		assert_ne!(root_1, root_2);
+		assert_ne!(root_1, root_3);
+		assert_ne!(root_2, root_3);
+	}
+
+	#[test]
+	fn case_insensitive_digest() {
+		let path = PathBuf::from("test.txt");
+		let upper = HASH_1.to_ascii_uppercase();
+		let root_1 = merkle_root(vec![(path.as_path(), 4, HASH_1)], HashFunc::Sha256);
+		let root_2 = merkle_root(vec![(path.as_path(), 4, upper.as_str())], HashFunc::Sha256);
+		assert_eq!(root_1, root_2);
+	}
+
+	#[test]
+	fn unicode_normalization() {
+		let nfc = PathBuf::from("dir/\u{e9}t\u{e9}.txt");
+		let nfd = PathBuf::from("dir/e\u{301}te\u{301}.txt");
+		assert_eq!(
+			merkle_root(vec![(nfc.as_path(), 4, HASH_1)], HashFunc::Sha256),
+			merkle_root(vec![(nfd.as_path(), 4, HASH_1)], HashFunc::Sha256)
+		);
+	}
+}
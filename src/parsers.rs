@@ -3,6 +3,11 @@ mod cksum_bsd;
 mod cksum_gnu;
 mod cnil_content_file;
 mod cnil_platform_email;
+mod custom;
+mod eml;
+mod error;
+mod lines;
+mod pdf;
 
 pub use bool::parse_bool;
 pub use cksum_bsd::cksum_bsd_get_files;
@@ -12,3 +17,18 @@ pub use cnil_platform_email::{
 	cnil_platform_email_get_files_v1, cnil_platform_email_get_files_v2,
 	cnil_platform_email_get_files_v3,
 };
+pub use custom::{custom_get_files, CustomReceiptFormat};
+pub use error::{nom_remaining_input, ParseErrorReason, ReceiptError, ReceiptParseError};
+pub use lines::{parse_lines, ParseMode};
+pub use pdf::pdf_get_files;
+
+use crate::files::HashedFileList;
+
+// The number of lines is the number of lines which were expected to describe a file, whether they
+// could be parsed or not. Headers, markers and the surrounding free text are not included.
+#[derive(Debug)]
+pub struct ParserOutput {
+	pub files: HashedFileList,
+	pub warnings: Vec<ReceiptParseError>,
+	pub nb_lines: usize,
+}
@@ -1,25 +1,59 @@
+use crate::content_file_trailer::strip_trailer;
 use crate::files::{HashedFile, HashedFileList};
 use crate::hash::HashFunc;
+use crate::parsers::{
+	nom_remaining_input, parse_lines, ParseErrorReason, ParseMode, ParserOutput, ReceiptParseError,
+};
+use crate::receipt::ReceiptFormat;
 use nom::character::complete::{alphanumeric1, char, hex_digit1, line_ending, none_of};
 use nom::combinator::{eof, fail, opt};
 use nom::multi::{fold_many0, many0};
 use nom::{IResult, Parser};
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-pub fn cksum_bsd_get_files(path: &Path, _default_hash: HashFunc) -> Result<HashedFileList, ()> {
+const FORMAT: ReceiptFormat = ReceiptFormat::CksumBsd;
+
+pub fn cksum_bsd_get_files(
+	path: &Path,
+	_default_hash: HashFunc,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
+	let raw = std::fs::read(path)
+		.map_err(|e| ReceiptParseError::new(FORMAT, ParseErrorReason::Io(e.to_string())))?;
+	let content = strip_trailer(&raw)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidTrailer))?;
+	let content = std::str::from_utf8(content)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidEncoding))?;
+	get_files_from_str(content, mode)
+}
+
+pub fn get_files_from_str(
+	content: &str,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	let mut files = HashedFileList::new();
-	let rcpt_file = std::fs::File::open(path).map_err(|_| ())?;
-	for line in BufReader::new(rcpt_file).lines() {
-		let file = get_file(&line.map_err(|_| ())?)?;
+	let parsed = parse_lines(content.lines(), 1, mode, get_file)?;
+	for file in parsed.entries {
 		files.insert_file(file);
 	}
-	Ok(files)
+	Ok(ParserOutput {
+		files,
+		warnings: parsed.warnings,
+		nb_lines: parsed.nb_lines,
+	})
 }
 
-fn get_file(content: &str) -> Result<HashedFile, ()> {
-	let (_, file) = parse_line(content).map_err(|_| ())?;
+fn get_file(content: &str, line_nb: usize) -> Result<HashedFile, ReceiptParseError> {
+	let (_, file) = parse_line(content).map_err(|e| {
+		ReceiptParseError::at_position(
+			FORMAT,
+			ParseErrorReason::InvalidLine,
+			line_nb,
+			content,
+			nom_remaining_input(&e),
+		)
+	})?;
 	Ok(file)
 }
 
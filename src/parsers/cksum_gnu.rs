@@ -1,32 +1,71 @@
 use crate::analyse_hash::analyse_hash;
+use crate::content_file_trailer::strip_trailer;
 use crate::files::{HashedFile, HashedFileList};
 use crate::hash::HashFunc;
+use crate::parsers::{
+	nom_remaining_input, parse_lines, ParseErrorReason, ParseMode, ParserOutput, ReceiptParseError,
+};
+use crate::receipt::ReceiptFormat;
 use nom::character::complete::{alphanumeric1, char, not_line_ending, one_of};
 use nom::combinator::fail;
 use nom::{IResult, Parser};
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-pub fn cksum_gnu_get_files(path: &Path, default_hash: HashFunc) -> Result<HashedFileList, ()> {
+const FORMAT: ReceiptFormat = ReceiptFormat::CksumGnu;
+
+pub fn cksum_gnu_get_files(
+	path: &Path,
+	default_hash: HashFunc,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
+	let raw = std::fs::read(path)
+		.map_err(|e| ReceiptParseError::new(FORMAT, ParseErrorReason::Io(e.to_string())))?;
+	let content = strip_trailer(&raw)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidTrailer))?;
+	let content = std::str::from_utf8(content)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidEncoding))?;
+	get_files_from_str(content, default_hash, mode)
+}
+
+pub fn get_files_from_str(
+	content: &str,
+	default_hash: HashFunc,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	let mut files = HashedFileList::new();
 	let mut hash_func = None;
-	let rcpt_file = std::fs::File::open(path).map_err(|_| ())?;
-	for line in BufReader::new(rcpt_file).lines() {
-		let file = get_file(&line.map_err(|_| ())?, hash_func, default_hash)?;
+	let parsed = parse_lines(content.lines(), 1, mode, |line, line_nb| {
+		let file = get_file(line, line_nb, hash_func, default_hash)?;
 		if hash_func.is_none() {
 			hash_func = Some(file.get_hash_func());
 		}
+		Ok(file)
+	})?;
+	for file in parsed.entries {
 		files.insert_file(file);
 	}
-	Ok(files)
+	Ok(ParserOutput {
+		files,
+		warnings: parsed.warnings,
+		nb_lines: parsed.nb_lines,
+	})
 }
 
 fn get_file(
 	content: &str,
+	line_nb: usize,
 	hash_func: Option<HashFunc>,
 	default_hash: HashFunc,
-) -> Result<HashedFile, ()> {
-	let (_, file) = parse_line(content, hash_func, default_hash).map_err(|_| ())?;
+) -> Result<HashedFile, ReceiptParseError> {
+	let (_, file) = parse_line(content, hash_func, default_hash).map_err(|e| {
+		ReceiptParseError::at_position(
+			FORMAT,
+			ParseErrorReason::InvalidLine,
+			line_nb,
+			content,
+			nom_remaining_input(&e),
+		)
+	})?;
 	Ok(file)
 }
 
@@ -1,31 +1,68 @@
+use crate::content_file_trailer::strip_trailer;
 use crate::files::{HashedFile, HashedFileList};
 use crate::hash::HashFunc;
+use crate::parsers::lines::is_ignored_line;
+use crate::parsers::{
+	nom_remaining_input, parse_lines, ParseErrorReason, ParseMode, ParserOutput, ReceiptParseError,
+};
+use crate::receipt::ReceiptFormat;
 use nom::character::complete::{hex_digit1, line_ending, none_of, tab, u64 as parse_u64};
 use nom::combinator::{eof, fail, opt};
 use nom::multi::many1;
 use nom::{IResult, Parser};
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+const FORMAT: ReceiptFormat = ReceiptFormat::CnilContentFile;
+
 pub fn cnil_content_file_get_files(
 	path: &Path,
 	_default_hash: HashFunc,
-) -> Result<HashedFileList, ()> {
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	let mut files = HashedFileList::new();
-	let rcpt_file = std::fs::File::open(path).map_err(|_| ())?;
-	let mut all_lines = BufReader::new(rcpt_file).lines();
-	let first_line = all_lines.next().ok_or(())?.map_err(|_| ())?;
-	let hash_func = get_header(&first_line)?;
-	for line in all_lines {
-		let file = get_file(&line.map_err(|_| ())?, hash_func)?;
+	let raw = std::fs::read(path)
+		.map_err(|e| ReceiptParseError::new(FORMAT, ParseErrorReason::Io(e.to_string())))?;
+	let content = strip_trailer(&raw)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidTrailer))?;
+	let content = std::str::from_utf8(content)
+		.map_err(|_| ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidEncoding))?;
+	let mut all_lines = content.lines().enumerate();
+	let (header_idx, header) = match mode {
+		ParseMode::Strict => all_lines.next(),
+		ParseMode::Lenient => all_lines.find(|(_, l)| !is_ignored_line(l)),
+	}
+	.ok_or(ReceiptParseError::new(
+		FORMAT,
+		ParseErrorReason::MissingHeader,
+	))?;
+	let hash_func = get_header(header, header_idx + 1)?;
+	let parsed = parse_lines(
+		all_lines.map(|(_, l)| l),
+		header_idx + 2,
+		mode,
+		|line, line_nb| get_file(line, line_nb, hash_func),
+	)?;
+	for file in parsed.entries {
 		files.insert_file(file);
 	}
-	Ok(files)
+	Ok(ParserOutput {
+		files,
+		warnings: parsed.warnings,
+		nb_lines: parsed.nb_lines,
+	})
 }
 
-fn get_header(content: &str) -> Result<HashFunc, ()> {
-	let (_, hash_func) = parse_header(content).map_err(|_| ())?;
+fn get_header(content: &str, line_nb: usize) -> Result<HashFunc, ReceiptParseError> {
+	let (_, hash_func) = parse_header(content).map_err(|e| {
+		ReceiptParseError::at_position(
+			FORMAT,
+			ParseErrorReason::InvalidHeader,
+			line_nb,
+			content,
+			nom_remaining_input(&e),
+		)
+	})?;
 	Ok(hash_func)
 }
 
@@ -56,8 +93,20 @@ fn parse_hash_func(input: &str) -> IResult<&str, HashFunc> {
 	Ok((input, hash_func))
 }
 
-fn get_file(content: &str, hash_func: HashFunc) -> Result<HashedFile, ()> {
-	let (_, file) = parse_line(content, hash_func).map_err(|_| ())?;
+fn get_file(
+	content: &str,
+	line_nb: usize,
+	hash_func: HashFunc,
+) -> Result<HashedFile, ReceiptParseError> {
+	let (_, file) = parse_line(content, hash_func).map_err(|e| {
+		ReceiptParseError::at_position(
+			FORMAT,
+			ParseErrorReason::InvalidLine,
+			line_nb,
+			content,
+			nom_remaining_input(&e),
+		)
+	})?;
 	Ok(file)
 }
 
@@ -1,39 +1,84 @@
 use crate::files::{HashedFile, HashedFileList};
 use crate::hash::HashFunc;
+use crate::parsers::eml::read_eml_body;
+use crate::parsers::{ParseErrorReason, ParseMode, ParserOutput, ReceiptParseError};
+use crate::receipt::ReceiptFormat;
 use msg_parser::Outlook;
+use nom::bytes::complete::take_till1;
 use nom::character::complete::{alpha1, alphanumeric1, char, digit1, space0, space1};
-use nom::combinator::opt;
+use nom::combinator::{fail, opt, recognize};
 use nom::{IResult, Parser};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use unicode_normalization::UnicodeNormalization;
 
 const DEFAULT_HASH: HashFunc = HashFunc::Sha256;
 const CNIL_V2_LST_BEGIN: &str = "Chaque empreinte";
 const CNIL_V2_LST_END: &str = "Pour toute question";
 const CNIL_V3_PREFIX: &str = "*\t";
+const SIZE_UNITS: &[(&str, i32)] = &[
+	("o", 0),
+	("b", 0),
+	("ko", 1),
+	("kb", 1),
+	("mo", 2),
+	("mb", 2),
+	("go", 3),
+	("gb", 3),
+	("to", 4),
+	("tb", 4),
+];
+
+// Both Outlook and RFC 5322 messages are accepted.
+pub(super) fn read_body(path: &Path, format: ReceiptFormat) -> Result<String, ReceiptParseError> {
+	let body = match Outlook::from_path(path) {
+		Ok(outlook) => outlook.body,
+		Err(_) => read_eml_body(path).ok_or(ReceiptParseError::new(
+			format,
+			ParseErrorReason::InvalidMessage,
+		))?,
+	};
+	Ok(body.nfkc().collect())
+}
 
 pub fn cnil_platform_email_get_files_v3(
 	path: &Path,
 	_default_hash: HashFunc,
-) -> Result<HashedFileList, ()> {
+	_mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	tracing::debug!("Testing receipt type: CNILv3");
-	if let Ok(outlook) = Outlook::from_path(path) {
-		let body: String = outlook.body.nfkc().collect();
-		let mut files = HashedFileList::new();
-		for line in body.lines() {
-			if line.starts_with(CNIL_V3_PREFIX) {
-				tracing::debug!("line: {line}");
-				if let Some(file) = parse_line_v3(line) {
-					files.insert_file(file);
-				}
+	let body = read_body(path, ReceiptFormat::CnilPlatformEmailV3)?;
+	get_files_v3(&body)
+}
+
+pub fn get_files_v3(body: &str) -> Result<ParserOutput, ReceiptParseError> {
+	const FORMAT: ReceiptFormat = ReceiptFormat::CnilPlatformEmailV3;
+	let mut files = HashedFileList::new();
+	let mut warnings = Vec::new();
+	let mut nb_lines = 0;
+	for (i, line) in body.lines().enumerate() {
+		if line.starts_with(CNIL_V3_PREFIX) {
+			tracing::debug!("line: {line}");
+			nb_lines += 1;
+			match parse_line_v3(line) {
+				Some(file) => files.insert_file(file),
+				None => warnings.push(ReceiptParseError::at_line(
+					FORMAT,
+					ParseErrorReason::InvalidLine,
+					i + 1,
+				)),
 			}
 		}
-		if !files.is_empty() {
-			tracing::debug!("Found {} files.", files.len(None));
-			return Ok(files);
-		}
 	}
-	Err(())
+	if !files.is_empty() {
+		tracing::debug!("Found {} files.", files.len(None));
+		return Ok(ParserOutput {
+			files,
+			warnings,
+			nb_lines,
+		});
+	}
+	Err(ReceiptParseError::new(FORMAT, ParseErrorReason::NoFile))
 }
 
 fn parse_line_v3(line: &str) -> Option<HashedFile> {
@@ -52,70 +97,79 @@ fn parse_inner_line_v3(input: &str) -> IResult<&str, HashedFile> {
 	let (input, hash) = alphanumeric1(input)?;
 	let (input, _) = space1(input)?;
 	let (input, _) = char(':')(input)?;
-	let (input, _) = digit1(input)?;
-	let (input, _) = char('-')(input)?;
-	let (input, _) = alpha1(input)?;
+	let (input, hash_func) = parse_rev_hash_func(input)?;
 	let (input, _) = space1(input)?;
 	let (input, _) = char(',')(input)?;
-	let (input, _) = alpha1(input)?;
-	let (input, _) = space1(input)?;
-	let (input, _) = digit1(input)?;
-	let (input, _) = opt(char(',')).parse(input)?;
-	let (input, _) = opt(digit1).parse(input)?;
+	let (input, (size, size_tolerance)) = parse_rev_size(input, ',')?;
 	let (input, _) = char('(')(input)?;
 	let (input, _) = space1(input)?;
 	let hash = rev_str(hash);
 	let path = PathBuf::from(rev_str(input));
-	let file = HashedFile::new(path, 0, hash, DEFAULT_HASH);
+	let file = HashedFile::new(path, size, hash, hash_func).with_size_tolerance(size_tolerance);
 	Ok((input, file))
 }
 
 pub fn cnil_platform_email_get_files_v2(
 	path: &Path,
 	_default_hash: HashFunc,
-) -> Result<HashedFileList, ()> {
+	_mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	tracing::debug!("Testing receipt type: CNILv2");
-	if let Ok(outlook) = Outlook::from_path(path) {
-		let body: String = outlook.body.nfkc().collect();
-		let mut files = HashedFileList::new();
-		let mut is_lst = false;
-		let mut last_file = None;
-		for line in body.lines() {
-			if line.is_empty() {
-				continue;
-			}
-			if line.contains(CNIL_V2_LST_BEGIN) {
-				is_lst = true;
-				continue;
-			}
-			if line.contains(CNIL_V2_LST_END) {
-				break;
-			}
-			if is_lst {
-				match last_file {
-					Some(name) => {
-						let hash = clean_v2_hash(line)?;
-						let file = HashedFile::new(name, 0, hash, DEFAULT_HASH);
-						files.insert_file(file);
-						last_file = None;
-					}
-					None => {
-						last_file = Some(clean_v2_name(line)?);
-					}
+	let body = read_body(path, ReceiptFormat::CnilPlatformEmailV2)?;
+	get_files_v2(&body)
+}
+
+pub fn get_files_v2(body: &str) -> Result<ParserOutput, ReceiptParseError> {
+	const FORMAT: ReceiptFormat = ReceiptFormat::CnilPlatformEmailV2;
+	let mut files = HashedFileList::new();
+	let mut is_lst = false;
+	let mut last_file = None;
+	// Each file is described by two lines, its name and its fingerprint, which are counted once.
+	let mut nb_lines = 0;
+	for (i, line) in body.lines().enumerate() {
+		if line.is_empty() {
+			continue;
+		}
+		if line.contains(CNIL_V2_LST_BEGIN) {
+			is_lst = true;
+			continue;
+		}
+		if line.contains(CNIL_V2_LST_END) {
+			break;
+		}
+		if is_lst {
+			match last_file {
+				Some(name) => {
+					nb_lines += 1;
+					let hash = clean_v2_hash(line).map_err(|_| {
+						ReceiptParseError::at_line(FORMAT, ParseErrorReason::InvalidLine, i + 1)
+					})?;
+					let file = HashedFile::new(name, 0, hash, DEFAULT_HASH);
+					files.insert_file(file);
+					last_file = None;
+				}
+				None => {
+					last_file = Some(clean_v2_name(line).map_err(|_| {
+						ReceiptParseError::at_line(FORMAT, ParseErrorReason::InvalidLine, i + 1)
+					})?);
 				}
 			}
 		}
-		if !files.is_empty() {
-			tracing::debug!("Found {} files.", files.len(None));
-			return Ok(files);
-		}
 	}
-	Err(())
+	if !files.is_empty() {
+		tracing::debug!("Found {} files.", files.len(None));
+		return Ok(ParserOutput {
+			files,
+			warnings: Vec::new(),
+			nb_lines,
+		});
+	}
+	Err(ReceiptParseError::new(FORMAT, ParseErrorReason::NoFile))
 }
 
 fn clean_v2_name(input: &str) -> Result<String, ()> {
 	let (input, _) = input.rsplit_once('(').ok_or(())?;
-	let len = 0.max(input.len() - 1);
+	let len = input.len().saturating_sub(1);
 	let mut s = input.to_string();
 	s.truncate(len);
 	Ok(s)
@@ -129,22 +183,40 @@ fn clean_v2_hash(input: &str) -> Result<String, ()> {
 pub fn cnil_platform_email_get_files_v1(
 	path: &Path,
 	_default_hash: HashFunc,
-) -> Result<HashedFileList, ()> {
+	_mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
 	tracing::debug!("Testing receipt type: CNILv1");
-	if let Ok(outlook) = Outlook::from_path(path) {
-		let body: String = outlook.body.nfkc().collect();
-		let mut files = HashedFileList::new();
-		for line in body.lines() {
-			if let Some(file) = parse_line_v1(line) {
-				files.insert_file(file);
+	let body = read_body(path, ReceiptFormat::CnilPlatformEmailV1)?;
+	get_files_v1(&body)
+}
+
+pub fn get_files_v1(body: &str) -> Result<ParserOutput, ReceiptParseError> {
+	const FORMAT: ReceiptFormat = ReceiptFormat::CnilPlatformEmailV1;
+	let mut files = HashedFileList::new();
+	let mut warnings = Vec::new();
+	let mut nb_lines = 0;
+	for (i, line) in body.lines().enumerate() {
+		if line.starts_with('*') {
+			nb_lines += 1;
+			match parse_line_v1(line) {
+				Some(file) => files.insert_file(file),
+				None => warnings.push(ReceiptParseError::at_line(
+					FORMAT,
+					ParseErrorReason::InvalidLine,
+					i + 1,
+				)),
 			}
 		}
-		if !files.is_empty() {
-			tracing::debug!("Found {} files.", files.len(None));
-			return Ok(files);
-		}
 	}
-	Err(())
+	if !files.is_empty() {
+		tracing::debug!("Found {} files.", files.len(None));
+		return Ok(ParserOutput {
+			files,
+			warnings,
+			nb_lines,
+		});
+	}
+	Err(ReceiptParseError::new(FORMAT, ParseErrorReason::NoFile))
 }
 
 fn parse_line_v1(line: &str) -> Option<HashedFile> {
@@ -163,31 +235,64 @@ fn parse_inner_line_v1(input: &str) -> IResult<&str, HashedFile> {
 	let (input, hash) = alphanumeric1(input)?;
 	let (input, _) = space1(input)?;
 	let (input, _) = char(':')(input)?;
-	let (input, _) = digit1(input)?;
-	let (input, _) = char('-')(input)?;
-	let (input, _) = alpha1(input)?;
+	let (input, hash_func) = parse_rev_hash_func(input)?;
 	let (input, _) = space1(input)?;
 	let (input, _) = char(',')(input)?;
-	let (input, _) = alpha1(input)?;
-	let (input, _) = space1(input)?;
-	let (input, _) = digit1(input)?;
-	let (input, _) = opt(char('.')).parse(input)?;
-	let (input, _) = opt(digit1).parse(input)?;
+	let (input, (size, size_tolerance)) = parse_rev_size(input, '.')?;
 	let (input, _) = char('(')(input)?;
 	let (input, _) = space1(input)?;
 	let hash = rev_str(hash);
 	let path = PathBuf::from(rev_str(input));
-	let file = HashedFile::new(path, 0, hash, DEFAULT_HASH);
+	let file = HashedFile::new(path, size, hash, hash_func).with_size_tolerance(size_tolerance);
 	Ok((input, file))
 }
 
+fn parse_rev_hash_func(input: &str) -> IResult<&str, HashFunc> {
+	let (input, name) = take_till1(char::is_whitespace)(input)?;
+	match HashFunc::from_str(&rev_str(name)) {
+		Ok(hash_func) => Ok((input, hash_func)),
+		Err(_) => fail().parse(input),
+	}
+}
+
+fn parse_rev_size(input: &str, decimal_sep: char) -> IResult<&str, (u64, u64)> {
+	let (input, unit) = alpha1(input)?;
+	let (input, _) = space1(input)?;
+	let (input, number) = recognize((digit1, opt((char(decimal_sep), digit1)))).parse(input)?;
+	match approximate_size(&rev_str(number), &rev_str(unit), decimal_sep) {
+		Some(size) => Ok((input, size)),
+		None => fail().parse(input),
+	}
+}
+
+// Sizes are rounded and it is unknown whether the unit is a power of 1000 or of 1024, hence the
+// size is returned along with the maximal difference with the actual size.
+fn approximate_size(number: &str, unit: &str, decimal_sep: char) -> Option<(u64, u64)> {
+	let exp = SIZE_UNITS
+		.iter()
+		.find(|(u, _)| unit.eq_ignore_ascii_case(u))
+		.map(|(_, e)| *e)?;
+	let (int_part, dec_part) = number.split_once(decimal_sep).unwrap_or((number, ""));
+	if exp == 0 && dec_part.is_empty() {
+		return Some((int_part.parse().ok()?, 0));
+	}
+	let value: f64 = format!("{int_part}.{dec_part}0").parse().ok()?;
+	let half_step = 0.5 * 10f64.powi(-(dec_part.len() as i32));
+	let low = (value - half_step).max(0.0) * 1000f64.powi(exp);
+	let high = (value + half_step) * 1024f64.powi(exp);
+	let size = ((low + high) / 2.0).round() as u64;
+	let tolerance = ((high - low) / 2.0).ceil() as u64;
+	Some((size, tolerance))
+}
+
 fn rev_str(s: &str) -> String {
 	s.chars().rev().collect()
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{parse_line_v3, DEFAULT_HASH};
+	use super::{approximate_size, get_files_v3, parse_line_v1, parse_line_v3};
+	use crate::hash::HashFunc;
 	use std::path::Path;
 
 	const TEST_LINES_V3: &[(&str, &str, &str)] = &[
@@ -207,9 +312,52 @@ mod tests {
 			assert!(res.is_some());
 			let f = res.unwrap();
 			let path = Path::new(file_name);
-			assert_eq!(f.get_hash_func(), DEFAULT_HASH);
+			assert_eq!(f.get_hash_func(), HashFunc::Sha256);
 			assert_eq!(f.get_hash(), *hash);
 			assert_eq!(f.get_relative_path(), path);
+			assert_ne!(f.get_size(), 0);
 		}
 	}
+
+	#[test]
+	fn test_v3_size_and_hash_func() {
+		let line = "*\ttest.txt (12,5 Mo, SHA-512: 0ea83f),";
+		let f = parse_line_v3(line).unwrap();
+		assert_eq!(f.get_hash_func(), HashFunc::Sha512);
+		assert!(f.size_matches(12_500_000));
+		assert!(f.size_matches(12 * 1024 * 1024 + 512 * 1024));
+		assert!(!f.size_matches(14_000_000));
+		let line = "*\ttest.txt (0,4 Ko, SHA3-256: 0ea83f),";
+		let f = parse_line_v3(line).unwrap();
+		assert_eq!(f.get_hash_func(), HashFunc::Sha3_256);
+		assert!(parse_line_v3("*\ttest.txt (0,4 Ko, MD5: 0ea83f),").is_none());
+	}
+
+	#[test]
+	fn test_v3_nb_lines() {
+		let body = "Bonjour,\n\nVoici la liste des fichiers :\n*\ttest.txt (0,4 Ko, SHA-256: 0ea83f),\n*\tinvalide\nCordialement,\n";
+		let output = get_files_v3(body).unwrap();
+		assert_eq!(output.files.len(None), 1);
+		assert_eq!(output.warnings.len(), 1);
+		assert_eq!(output.nb_lines, 2);
+	}
+
+	#[test]
+	fn test_v1() {
+		let line = "* test.txt (1.2 Ko, SHA-256: 0ea83f)";
+		let f = parse_line_v1(line).unwrap();
+		assert_eq!(f.get_relative_path(), Path::new("test.txt"));
+		assert_eq!(f.get_hash(), "0ea83f");
+		assert!(f.size_matches(1200));
+		assert!(f.size_matches(1250));
+		assert!(!f.size_matches(1000));
+	}
+
+	#[test]
+	fn sizes() {
+		assert_eq!(approximate_size("42", "o", ','), Some((42, 0)));
+		assert_eq!(approximate_size("0,4", "Ko", ','), Some((405, 56)));
+		assert_eq!(approximate_size("3", "KB", '.'), Some((3042, 542)));
+		assert_eq!(approximate_size("3", "parsecs", '.'), None);
+	}
 }
@@ -0,0 +1,275 @@
+use super::{cnil_platform_email, pdf};
+use crate::files::{HashedFile, HashedFileList};
+use crate::hash::HashFunc;
+use crate::parsers::lines::is_ignored_line;
+use crate::parsers::{ParseErrorReason, ParseMode, ParserOutput, ReceiptParseError};
+use crate::receipt::ReceiptFormat;
+use regex::{Captures, Regex};
+use serde_derive::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+const CAPTURE_PATH: &str = "path";
+const CAPTURE_HASH: &str = "hash";
+const CAPTURE_SIZE: &str = "size";
+const CAPTURE_ALGORITHM: &str = "algorithm";
+const SIZE_SEPARATORS: &[char] = &[' ', ',', '.', '\'', '\u{a0}', '\u{202f}'];
+
+// Receipt formats declared by the user in the configuration. Each line of the receipt is matched
+// against a regular expression which must at least capture the file's path and fingerprint.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CustomReceiptFormat {
+	pub name: String,
+	pub line_regex: String,
+	pub begin_marker: Option<String>,
+	pub end_marker: Option<String>,
+	pub hash_function: Option<HashFunc>,
+}
+
+impl CustomReceiptFormat {
+	pub fn get_regex(&self) -> Result<Regex, ParseErrorReason> {
+		let regex = Regex::new(&self.line_regex).map_err(|e| {
+			tracing::warn!("{}: invalid regular expression: {e}", self.name);
+			ParseErrorReason::InvalidGrammar
+		})?;
+		let names: Vec<&str> = regex.capture_names().flatten().collect();
+		if !names.contains(&CAPTURE_PATH) || !names.contains(&CAPTURE_HASH) {
+			tracing::warn!(
+				"{}: the regular expression must capture both `{CAPTURE_PATH}` and `{CAPTURE_HASH}`",
+				self.name
+			);
+			return Err(ParseErrorReason::InvalidGrammar);
+		}
+		Ok(regex)
+	}
+}
+
+pub fn custom_get_files(
+	path: &Path,
+	default_hash: HashFunc,
+	mode: ParseMode,
+	custom_format: &CustomReceiptFormat,
+	format: ReceiptFormat,
+) -> Result<ParserOutput, ReceiptParseError> {
+	tracing::debug!("Testing receipt type: {}", custom_format.name);
+	let text = read_text(path, format)?;
+	get_files_from_text(&text, default_hash, mode, custom_format, format)
+}
+
+// Custom formats may describe plain text files as well as the content of emails or PDF documents.
+// A PDF document which cannot be read is reported as such instead of being parsed as text.
+fn read_text(path: &Path, format: ReceiptFormat) -> Result<String, ReceiptParseError> {
+	let raw = std::fs::read(path)
+		.map_err(|e| ReceiptParseError::new(format, ParseErrorReason::Io(e.to_string())))?;
+	if pdf::is_pdf(path, &raw) {
+		return pdf::extract_text(&raw)
+			.map_err(|_| ReceiptParseError::new(format, ParseErrorReason::InvalidDocument));
+	}
+	if let Ok(body) = cnil_platform_email::read_body(path, format) {
+		return Ok(body);
+	}
+	let text = String::from_utf8(raw)
+		.map_err(|_| ReceiptParseError::new(format, ParseErrorReason::InvalidEncoding))?;
+	Ok(text.nfkc().collect())
+}
+
+// Without markers, lines which do not match are ignored since the list of files is usually
+// surrounded by free text. Between markers, such lines are considered malformed.
+pub fn get_files_from_text(
+	text: &str,
+	default_hash: HashFunc,
+	mode: ParseMode,
+	custom_format: &CustomReceiptFormat,
+	format: ReceiptFormat,
+) -> Result<ParserOutput, ReceiptParseError> {
+	let regex = custom_format
+		.get_regex()
+		.map_err(|reason| ReceiptParseError::new(format, reason))?;
+	let has_markers = custom_format.begin_marker.is_some();
+	let default_hash = custom_format.hash_function.unwrap_or(default_hash);
+	let mut in_list = !has_markers;
+	let mut files = HashedFileList::new();
+	let mut warnings = Vec::new();
+	let mut nb_lines = 0;
+	for (i, line) in text.lines().enumerate() {
+		if !in_list {
+			if let Some(marker) = &custom_format.begin_marker {
+				in_list = line.contains(marker.as_str());
+			}
+			continue;
+		}
+		if let Some(marker) = &custom_format.end_marker {
+			if line.contains(marker.as_str()) {
+				break;
+			}
+		}
+		let file = match regex.captures(line) {
+			Some(caps) => get_file(&caps, default_hash),
+			None if has_markers && !is_ignored_line(line) => None,
+			None => continue,
+		};
+		nb_lines += 1;
+		match file {
+			Some(file) => files.insert_file(file),
+			None => {
+				let e = ReceiptParseError::at_line(format, ParseErrorReason::InvalidLine, i + 1);
+				match mode {
+					ParseMode::Strict => return Err(e),
+					ParseMode::Lenient => {
+						tracing::warn!("{e}");
+						warnings.push(e);
+					}
+				}
+			}
+		}
+	}
+	if files.is_empty() {
+		return Err(match warnings.into_iter().next() {
+			Some(e) => e,
+			None => ReceiptParseError::new(format, ParseErrorReason::NoFile),
+		});
+	}
+	Ok(ParserOutput {
+		files,
+		warnings,
+		nb_lines,
+	})
+}
+
+fn get_file(caps: &Captures, default_hash: HashFunc) -> Option<HashedFile> {
+	let path = caps.name(CAPTURE_PATH)?.as_str().trim();
+	let hash = caps.name(CAPTURE_HASH)?.as_str().trim();
+	if path.is_empty() || hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	let hash_func = match caps.name(CAPTURE_ALGORITHM) {
+		Some(m) => HashFunc::from_str(m.as_str().trim()).ok()?,
+		None => default_hash,
+	};
+	let size = match caps.name(CAPTURE_SIZE) {
+		Some(m) => m.as_str().replace(SIZE_SEPARATORS, "").parse().ok()?,
+		None => 0,
+	};
+	Some(HashedFile::new(path, size, hash.to_lowercase(), hash_func))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HASH: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+	const FORMAT: ReceiptFormat = ReceiptFormat::Custom(0);
+
+	fn custom_format(line_regex: &str) -> CustomReceiptFormat {
+		CustomReceiptFormat {
+			name: "test".to_string(),
+			line_regex: line_regex.to_string(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn invalid_regex() {
+		assert_eq!(
+			custom_format("(?P<path>.+").get_regex().err(),
+			Some(ParseErrorReason::InvalidGrammar)
+		);
+		assert_eq!(
+			custom_format("(?P<path>.+) (?P<fingerprint>.+)")
+				.get_regex()
+				.err(),
+			Some(ParseErrorReason::InvalidGrammar)
+		);
+		assert!(custom_format("(?P<path>.+) (?P<hash>.+)")
+			.get_regex()
+			.is_ok());
+	}
+
+	#[test]
+	fn all_captures() {
+		let fmt = custom_format(
+			r"^- (?P<path>.+) \((?P<size>[\d ]+) bytes\) (?P<algorithm>\S+): (?P<hash>[0-9a-fA-F]+)$",
+		);
+		let text = format!(
+			"Hello,\n\n- dir/a.txt (1 024 bytes) SHA-512: {}\n- b.txt (3 bytes) sha256: {HASH}\n\nBye",
+			HASH.to_uppercase()
+		);
+		let ParserOutput {
+			files, warnings, ..
+		} = get_files_from_text(&text, HashFunc::Sha256, ParseMode::Strict, &fmt, FORMAT).unwrap();
+		assert!(warnings.is_empty());
+		let mut files: Vec<&HashedFile> = files.get_files().collect();
+		files.sort_by_key(|f| f.get_relative_path().to_path_buf());
+		assert_eq!(files.len(), 2);
+		assert_eq!(files[0].get_relative_path(), Path::new("b.txt"));
+		assert_eq!(files[0].get_size(), 3);
+		assert_eq!(files[1].get_relative_path(), Path::new("dir/a.txt"));
+		assert_eq!(files[1].get_size(), 1024);
+		assert_eq!(files[1].get_hash(), HASH);
+		assert_eq!(files[1].get_hash_func(), HashFunc::Sha512);
+	}
+
+	#[test]
+	fn markers() {
+		let fmt = CustomReceiptFormat {
+			begin_marker: Some("BEGIN".to_string()),
+			end_marker: Some("END".to_string()),
+			hash_function: Some(HashFunc::Blake3),
+			..custom_format(r"^(?P<hash>\S+)  (?P<path>.+)$")
+		};
+		let text = format!(
+			"{HASH}  before.txt\nBEGIN\n{HASH}  a.txt\n\n{HASH}  b.txt\nEND\n{HASH}  after.txt"
+		);
+		let ParserOutput { files, .. } =
+			get_files_from_text(&text, HashFunc::Sha256, ParseMode::Strict, &fmt, FORMAT).unwrap();
+		assert_eq!(files.get_files().count(), 2);
+		assert!(files
+			.get_files()
+			.all(|f| f.get_hash_func() == HashFunc::Blake3));
+	}
+
+	#[test]
+	fn malformed_between_markers() {
+		let fmt = CustomReceiptFormat {
+			begin_marker: Some("BEGIN".to_string()),
+			..custom_format(r"^(?P<hash>\S+)  (?P<path>.+)$")
+		};
+		let text = format!("BEGIN\n{HASH}  a.txt\nnot a file\n");
+		let err = get_files_from_text(&text, HashFunc::Sha256, ParseMode::Strict, &fmt, FORMAT)
+			.unwrap_err();
+		assert_eq!(err.get_line(), Some(3));
+		let ParserOutput {
+			files, warnings, ..
+		} = get_files_from_text(&text, HashFunc::Sha256, ParseMode::Lenient, &fmt, FORMAT).unwrap();
+		assert_eq!(files.get_files().count(), 1);
+		assert_eq!(warnings.len(), 1);
+	}
+
+	#[test]
+	fn no_file() {
+		let fmt = custom_format(r"^(?P<hash>\S+)  (?P<path>.+)$");
+		let err = get_files_from_text(
+			"nothing here",
+			HashFunc::Sha256,
+			ParseMode::Strict,
+			&fmt,
+			FORMAT,
+		)
+		.unwrap_err();
+		assert_eq!(err.get_reason(), &ParseErrorReason::NoFile);
+	}
+
+	#[test]
+	fn invalid_pdf() {
+		let path = std::env::temp_dir().join(format!("loutre-{}.pdf", uuid::Uuid::new_v4()));
+		std::fs::write(&path, format!("{HASH}  a.txt\n")).unwrap();
+		let fmt = custom_format(r"^(?P<hash>\S+)  (?P<path>.+)$");
+		let err =
+			custom_get_files(&path, HashFunc::Sha256, ParseMode::Strict, &fmt, FORMAT).unwrap_err();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(err.get_reason(), &ParseErrorReason::InvalidDocument);
+		assert_eq!(err.get_format(), FORMAT);
+	}
+}
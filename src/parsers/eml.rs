@@ -0,0 +1,49 @@
+use mail_parser::MessageParser;
+use std::path::Path;
+
+pub fn read_eml_body(path: &Path) -> Option<String> {
+	let raw = std::fs::read(path).ok()?;
+	parse_eml_body(&raw)
+}
+
+// The MIME parser accepts almost anything as a message body, hence the requirement for a sender.
+// When the message has no plain text part, its HTML part is converted to text.
+fn parse_eml_body(raw: &[u8]) -> Option<String> {
+	let message = MessageParser::default().parse(raw)?;
+	message.from()?;
+	Some(message.body_text(0)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::parse_eml_body;
+
+	#[test]
+	fn plain_text() {
+		let raw = "From: CNIL <noreply@cnil.fr>\r\nTo: test@example.org\r\nSubject: Accusé de réception\r\nContent-Type: text/plain; charset=utf-8\r\n\r\nBonjour,\r\n*\ttest.txt (0,4 Ko, SHA-256: 0ea83f),\r\n";
+		let body = parse_eml_body(raw.as_bytes()).unwrap();
+		assert!(body.contains("*\ttest.txt (0,4 Ko, SHA-256: 0ea83f),"));
+	}
+
+	#[test]
+	fn quoted_printable() {
+		let raw = "From: noreply@cnil.fr\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: quoted-printable\r\n\r\nd=C3=A9but.txt\r\n";
+		let body = parse_eml_body(raw.as_bytes()).unwrap();
+		assert!(body.contains("début.txt"));
+	}
+
+	#[test]
+	fn html_only() {
+		let raw = "From: noreply@cnil.fr\r\nContent-Type: text/html; charset=utf-8\r\n\r\n<html><body><p>test.txt</p></body></html>\r\n";
+		let body = parse_eml_body(raw.as_bytes()).unwrap();
+		assert!(body.contains("test.txt"));
+		assert!(!body.contains("<p>"));
+	}
+
+	#[test]
+	fn not_an_email() {
+		let raw =
+			"SHA256 (test) = 1c527c669fdb2cdb8a7d3e75137bbb71b5e53ddad14e9fe6c0691f8fafb6893d\n";
+		assert!(parse_eml_body(raw.as_bytes()).is_none());
+	}
+}
@@ -0,0 +1,231 @@
+use crate::receipt::ReceiptFormat;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorReason {
+	Io(String),
+	InvalidEncoding,
+	InvalidTrailer,
+	MissingHeader,
+	InvalidHeader,
+	InvalidLine,
+	InvalidMessage,
+	InvalidDocument,
+	InvalidGrammar,
+	NoFile,
+}
+
+impl ParseErrorReason {
+	pub fn get_tid_key(&self) -> &'static str {
+		match self {
+			Self::Io(_) => "parse_err_reason_io",
+			Self::InvalidEncoding => "parse_err_reason_encoding",
+			Self::InvalidTrailer => "parse_err_reason_trailer",
+			Self::MissingHeader => "parse_err_reason_missing_header",
+			Self::InvalidHeader => "parse_err_reason_header",
+			Self::InvalidLine => "parse_err_reason_line",
+			Self::InvalidMessage => "parse_err_reason_message",
+			Self::InvalidDocument => "parse_err_reason_document",
+			Self::InvalidGrammar => "parse_err_reason_grammar",
+			Self::NoFile => "parse_err_reason_no_file",
+		}
+	}
+}
+
+impl fmt::Display for ParseErrorReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "I/O error: {e}"),
+			Self::InvalidEncoding => write!(f, "invalid UTF-8 content"),
+			Self::InvalidTrailer => write!(f, "invalid self-verification trailer"),
+			Self::MissingHeader => write!(f, "missing header"),
+			Self::InvalidHeader => write!(f, "invalid header"),
+			Self::InvalidLine => write!(f, "invalid line"),
+			Self::InvalidMessage => write!(f, "invalid email"),
+			Self::InvalidDocument => write!(f, "invalid PDF document"),
+			Self::InvalidGrammar => write!(f, "invalid format definition"),
+			Self::NoFile => write!(f, "no file found"),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptParseError {
+	format: ReceiptFormat,
+	line: Option<usize>,
+	column: Option<usize>,
+	reason: ParseErrorReason,
+}
+
+impl ReceiptParseError {
+	pub fn new(format: ReceiptFormat, reason: ParseErrorReason) -> Self {
+		Self {
+			format,
+			line: None,
+			column: None,
+			reason,
+		}
+	}
+
+	pub fn at_line(format: ReceiptFormat, reason: ParseErrorReason, line_nb: usize) -> Self {
+		Self {
+			format,
+			line: Some(line_nb),
+			column: None,
+			reason,
+		}
+	}
+
+	// `remaining` is the part of the line which has not been consumed by the parser. Line and
+	// column numbers start at 1.
+	pub fn at_position(
+		format: ReceiptFormat,
+		reason: ParseErrorReason,
+		line_nb: usize,
+		line: &str,
+		remaining: &str,
+	) -> Self {
+		let offset = line.len().saturating_sub(remaining.len());
+		let column = line
+			.get(..offset)
+			.map(|s| s.chars().count())
+			.unwrap_or_default();
+		Self {
+			format,
+			line: Some(line_nb),
+			column: Some(column + 1),
+			reason,
+		}
+	}
+
+	pub fn with_format(self, format: ReceiptFormat) -> Self {
+		Self { format, ..self }
+	}
+
+	pub fn get_format(&self) -> ReceiptFormat {
+		self.format
+	}
+
+	pub fn get_line(&self) -> Option<usize> {
+		self.line
+	}
+
+	pub fn get_column(&self) -> Option<usize> {
+		self.column
+	}
+
+	pub fn get_reason(&self) -> &ParseErrorReason {
+		&self.reason
+	}
+
+	// A parser which failed further in the file is more likely to be the right one.
+	fn progress(&self) -> (usize, usize) {
+		(self.line.unwrap_or(0), self.column.unwrap_or(0))
+	}
+}
+
+impl fmt::Display for ReceiptParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.format)?;
+		if let Some(line) = self.line {
+			write!(f, ": line {line}")?;
+		}
+		if let Some(column) = self.column {
+			write!(f, ", column {column}")?;
+		}
+		write!(f, ": {}", self.reason)
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReceiptError {
+	attempts: Vec<ReceiptParseError>,
+}
+
+impl ReceiptError {
+	pub fn push(&mut self, error: ReceiptParseError) {
+		self.attempts.push(error);
+	}
+
+	pub fn get_attempts(&self) -> &[ReceiptParseError] {
+		&self.attempts
+	}
+
+	pub fn most_plausible(&self) -> Option<&ReceiptParseError> {
+		self.attempts.iter().rev().max_by_key(|e| e.progress())
+	}
+}
+
+impl fmt::Display for ReceiptError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.most_plausible() {
+			Some(e) => write!(f, "{e}"),
+			None => write!(f, "no parser available"),
+		}
+	}
+}
+
+pub fn nom_remaining_input<'a>(e: &nom::Err<nom::error::Error<&'a str>>) -> &'a str {
+	match e {
+		nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+		nom::Err::Incomplete(_) => "",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn column() {
+		let line = "SHA256 (é.txt) = xyz";
+		let err = ReceiptParseError::at_position(
+			ReceiptFormat::CksumBsd,
+			ParseErrorReason::InvalidLine,
+			3,
+			line,
+			"xyz",
+		);
+		assert_eq!(err.get_line(), Some(3));
+		assert_eq!(err.get_column(), Some(18));
+	}
+
+	#[test]
+	fn most_plausible() {
+		let mut err = ReceiptError::default();
+		assert!(err.most_plausible().is_none());
+		err.push(ReceiptParseError::new(
+			ReceiptFormat::CksumBsd,
+			ParseErrorReason::InvalidEncoding,
+		));
+		err.push(ReceiptParseError::at_position(
+			ReceiptFormat::CksumGnu,
+			ParseErrorReason::InvalidLine,
+			4,
+			"abc",
+			"c",
+		));
+		err.push(ReceiptParseError::at_line(
+			ReceiptFormat::CnilContentFile,
+			ParseErrorReason::InvalidHeader,
+			1,
+		));
+		let e = err.most_plausible().unwrap();
+		assert_eq!(e.get_format(), ReceiptFormat::CksumGnu);
+	}
+
+	#[test]
+	fn most_plausible_tie() {
+		let mut err = ReceiptError::default();
+		err.push(ReceiptParseError::new(
+			ReceiptFormat::CksumBsd,
+			ParseErrorReason::InvalidEncoding,
+		));
+		err.push(ReceiptParseError::new(
+			ReceiptFormat::CnilPlatformEmailV3,
+			ParseErrorReason::InvalidMessage,
+		));
+		let e = err.most_plausible().unwrap();
+		assert_eq!(e.get_format(), ReceiptFormat::CksumBsd);
+	}
+}
@@ -0,0 +1,122 @@
+use crate::parsers::ReceiptParseError;
+
+const COMMENT_PREFIX: char = '#';
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParseMode {
+	#[default]
+	Strict,
+	Lenient,
+}
+
+pub struct ParsedLines<T> {
+	pub entries: Vec<T>,
+	pub warnings: Vec<ReceiptParseError>,
+	pub nb_lines: usize,
+}
+
+// In strict mode, the first malformed line is an error. In lenient mode, blank lines and comments
+// are skipped and malformed lines are collected as warnings, unless no line at all could be
+// parsed, which means the file is not in the expected format.
+pub fn parse_lines<'a, I, T, F>(
+	lines: I,
+	first_line_nb: usize,
+	mode: ParseMode,
+	mut parse: F,
+) -> Result<ParsedLines<T>, ReceiptParseError>
+where
+	I: Iterator<Item = &'a str>,
+	F: FnMut(&'a str, usize) -> Result<T, ReceiptParseError>,
+{
+	let mut entries = Vec::new();
+	let mut warnings = Vec::new();
+	let mut nb_lines = 0;
+	for (i, line) in lines.enumerate() {
+		let line_nb = first_line_nb + i;
+		if mode == ParseMode::Lenient && is_ignored_line(line) {
+			continue;
+		}
+		nb_lines += 1;
+		match parse(line, line_nb) {
+			Ok(entry) => entries.push(entry),
+			Err(e) => match mode {
+				ParseMode::Strict => return Err(e),
+				ParseMode::Lenient => {
+					tracing::warn!("{e}");
+					warnings.push(e);
+				}
+			},
+		}
+	}
+	if entries.is_empty() && !warnings.is_empty() {
+		return Err(warnings.swap_remove(0));
+	}
+	Ok(ParsedLines {
+		entries,
+		warnings,
+		nb_lines,
+	})
+}
+
+pub fn is_ignored_line(line: &str) -> bool {
+	let line = line.trim();
+	line.is_empty() || line.starts_with(COMMENT_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parsers::ParseErrorReason;
+	use crate::receipt::ReceiptFormat;
+
+	fn parse_digit(line: &str, line_nb: usize) -> Result<u32, ReceiptParseError> {
+		line.parse().map_err(|_| {
+			ReceiptParseError::at_line(
+				ReceiptFormat::CksumBsd,
+				ParseErrorReason::InvalidLine,
+				line_nb,
+			)
+		})
+	}
+
+	#[test]
+	fn strict() {
+		let res = parse_lines("1\n2\n3".lines(), 1, ParseMode::Strict, parse_digit);
+		assert_eq!(res.unwrap().entries, vec![1, 2, 3]);
+		let res = parse_lines("1\n\n3".lines(), 1, ParseMode::Strict, parse_digit);
+		assert_eq!(res.err().unwrap().get_line(), Some(2));
+	}
+
+	#[test]
+	fn lenient() {
+		let content = "# comment\n1\n\n  \nx\n3\n  # indented comment";
+		let res = parse_lines(content.lines(), 1, ParseMode::Lenient, parse_digit).unwrap();
+		assert_eq!(res.entries, vec![1, 3]);
+		assert_eq!(res.warnings.len(), 1);
+		assert_eq!(res.warnings[0].get_line(), Some(5));
+		assert_eq!(res.nb_lines, 3);
+	}
+
+	#[test]
+	fn lenient_first_line_nb() {
+		let res = parse_lines("1\nx".lines(), 2, ParseMode::Lenient, parse_digit).unwrap();
+		assert_eq!(res.warnings[0].get_line(), Some(3));
+	}
+
+	#[test]
+	fn lenient_nothing_valid() {
+		let res = parse_lines("x\ny".lines(), 1, ParseMode::Lenient, parse_digit);
+		assert_eq!(res.err().unwrap().get_line(), Some(1));
+	}
+
+	#[test]
+	fn lenient_empty() {
+		let res = parse_lines(
+			"# only a comment".lines(),
+			1,
+			ParseMode::Lenient,
+			parse_digit,
+		);
+		assert!(res.unwrap().entries.is_empty());
+	}
+}
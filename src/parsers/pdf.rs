@@ -0,0 +1,218 @@
+use super::{cksum_bsd, cksum_gnu, cnil_platform_email};
+use crate::hash::HashFunc;
+use crate::parsers::{ParseErrorReason, ParseMode, ParserOutput, ReceiptError, ReceiptParseError};
+use crate::receipt::ReceiptFormat;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+const FORMAT: ReceiptFormat = ReceiptFormat::Pdf;
+const PDF_MAGIC: &[u8] = b"%PDF-";
+const LIST_PREFIX: char = '*';
+
+type TextParser = dyn Fn(&str, HashFunc, ParseMode) -> Result<ParserOutput, ReceiptParseError>;
+
+const TEXT_PARSERS: &[&TextParser] = &[
+	&|text, _, mode| cksum_bsd::get_files_from_str(text, mode),
+	&|text, default_hash, mode| cksum_gnu::get_files_from_str(text, default_hash, mode),
+	&|text, _, _| cnil_platform_email::get_files_v3(text),
+	&|text, _, _| cnil_platform_email::get_files_v2(text),
+	&|text, _, _| cnil_platform_email::get_files_v1(text),
+];
+
+pub fn pdf_get_files(
+	path: &Path,
+	default_hash: HashFunc,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
+	tracing::debug!("Testing receipt type: PDF");
+	let raw = std::fs::read(path)
+		.map_err(|e| ReceiptParseError::new(FORMAT, ParseErrorReason::Io(e.to_string())))?;
+	let text = extract_text(&raw)?;
+	get_files_from_text(&text, default_hash, mode)
+}
+
+// Documents are recognized by their signature or, for damaged ones, by their extension.
+pub(super) fn is_pdf(path: &Path, raw: &[u8]) -> bool {
+	raw.starts_with(PDF_MAGIC)
+		|| path
+			.extension()
+			.is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+pub(super) fn extract_text(raw: &[u8]) -> Result<String, ReceiptParseError> {
+	if !raw.starts_with(PDF_MAGIC) {
+		return Err(ReceiptParseError::new(
+			FORMAT,
+			ParseErrorReason::InvalidDocument,
+		));
+	}
+	// The text extraction panics on a number of documents it does not support instead of
+	// returning an error.
+	let text = panic::catch_unwind(AssertUnwindSafe(|| pdf_extract::extract_text_from_mem(raw)))
+		.map_err(|_| "the text extraction panicked".to_string())
+		.and_then(|res| res.map_err(|e| e.to_string()))
+		.map_err(|e| {
+			tracing::debug!("Unable to extract text from PDF: {e}");
+			ReceiptParseError::new(FORMAT, ParseErrorReason::InvalidDocument)
+		})?;
+	Ok(normalize_text(&text))
+}
+
+// Text extraction inserts blank lines between text blocks and does not preserve tabulations,
+// which the list of files in the platform's emails relies on.
+fn normalize_text(text: &str) -> String {
+	let text: String = text.nfkc().collect();
+	let mut lines = Vec::new();
+	for line in text.lines().map(str::trim_end) {
+		if line.is_empty() {
+			continue;
+		}
+		match line.strip_prefix(LIST_PREFIX) {
+			Some(item) if item.starts_with(char::is_whitespace) => {
+				lines.push(format!("{LIST_PREFIX}\t{}", item.trim_start()));
+			}
+			_ => lines.push(line.to_string()),
+		}
+	}
+	lines.join("\n")
+}
+
+fn get_files_from_text(
+	text: &str,
+	default_hash: HashFunc,
+	mode: ParseMode,
+) -> Result<ParserOutput, ReceiptParseError> {
+	let mut errors = ReceiptError::default();
+	for parser in TEXT_PARSERS {
+		match parser(text, default_hash, mode) {
+			Ok(files) => return Ok(files),
+			Err(e) => errors.push(e),
+		}
+	}
+	let err = match errors.most_plausible() {
+		Some(e) => e.clone().with_format(FORMAT),
+		None => ReceiptParseError::new(FORMAT, ParseErrorReason::NoFile),
+	};
+	Err(err)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pdf_extract::content::{Content, Operation};
+	use pdf_extract::{dictionary, Dictionary, Document, Object, Stream};
+
+	const HASH: &str = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+
+	fn build_pdf(lines: &[&str]) -> Vec<u8> {
+		build_pdf_with_font(
+			lines,
+			dictionary! {
+				"Type" => "Font",
+				"Subtype" => "Type1",
+				"BaseFont" => "Courier",
+			},
+		)
+	}
+
+	fn build_pdf_with_font(lines: &[&str], font: Dictionary) -> Vec<u8> {
+		let mut doc = Document::with_version("1.5");
+		let pages_id = doc.new_object_id();
+		let font_id = doc.add_object(font);
+		let resources_id = doc.add_object(dictionary! {
+			"Font" => dictionary! { "F1" => font_id },
+		});
+		let mut operations = vec![
+			Operation::new("BT", vec![]),
+			Operation::new("Tf", vec!["F1".into(), 8.into()]),
+			Operation::new("TL", vec![12.into()]),
+			Operation::new("Td", vec![20.into(), 800.into()]),
+		];
+		for line in lines {
+			operations.push(Operation::new("Tj", vec![Object::string_literal(*line)]));
+			operations.push(Operation::new("T*", vec![]));
+		}
+		operations.push(Operation::new("ET", vec![]));
+		let content = Content { operations };
+		let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+		let page_id = doc.add_object(dictionary! {
+			"Type" => "Page",
+			"Parent" => pages_id,
+			"Contents" => content_id,
+		});
+		let pages = dictionary! {
+			"Type" => "Pages",
+			"Kids" => vec![page_id.into()],
+			"Count" => 1,
+			"Resources" => resources_id,
+			"MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+		};
+		doc.objects.insert(pages_id, Object::Dictionary(pages));
+		let catalog_id = doc.add_object(dictionary! {
+			"Type" => "Catalog",
+			"Pages" => pages_id,
+		});
+		doc.trailer.set("Root", catalog_id);
+		let mut raw = Vec::new();
+		doc.save_to(&mut raw).unwrap();
+		raw
+	}
+
+	#[test]
+	fn not_a_pdf() {
+		let err = extract_text(b"SHA256 (a.txt) = 0ea83f").unwrap_err();
+		assert_eq!(err.get_reason(), &ParseErrorReason::InvalidDocument);
+	}
+
+	#[test]
+	fn unsupported_font_encoding() {
+		let font = dictionary! {
+			"Type" => "Font",
+			"Subtype" => "Type1",
+			"BaseFont" => "Courier",
+			"Encoding" => "UnknownEncoding",
+		};
+		let raw = build_pdf_with_font(&["SHA256 (test.txt) = 0ea83f"], font);
+		let err = extract_text(&raw).unwrap_err();
+		assert_eq!(err.get_reason(), &ParseErrorReason::InvalidDocument);
+	}
+
+	#[test]
+	fn normalization() {
+		let text = "Bonjour,\n\n*   test.txt (0,4 Ko)  \n*test\n";
+		assert_eq!(
+			normalize_text(text),
+			"Bonjour,\n*\ttest.txt (0,4 Ko)\n*test"
+		);
+	}
+
+	#[test]
+	fn platform_email_text() {
+		let text = format!("Bonjour,\n* test.txt (0,4 Ko, SHA-256: {HASH}),\n");
+		let ParserOutput { files, .. } =
+			get_files_from_text(&normalize_text(&text), HashFunc::Sha256, ParseMode::Strict)
+				.unwrap();
+		let file = files.get_files().next().unwrap();
+		assert_eq!(file.get_relative_path(), Path::new("test.txt"));
+		assert_eq!(file.get_hash(), HASH);
+	}
+
+	#[test]
+	fn no_file() {
+		let err = get_files_from_text("Bonjour,", HashFunc::Sha256, ParseMode::Strict).unwrap_err();
+		assert_eq!(err.get_format(), FORMAT);
+	}
+
+	#[test]
+	fn cksum_document() {
+		let line = format!("SHA256 (test.txt) = {HASH}");
+		let raw = build_pdf(&[&line]);
+		let text = extract_text(&raw).unwrap();
+		let ParserOutput { files, .. } =
+			get_files_from_text(&text, HashFunc::Sha256, ParseMode::Strict).unwrap();
+		let file = files.get_files().next().unwrap();
+		assert_eq!(file.get_relative_path(), Path::new("test.txt"));
+		assert_eq!(file.get_hash(), HASH);
+	}
+}
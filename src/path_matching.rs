@@ -0,0 +1,99 @@
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+const SEPARATOR: char = '/';
+const WINDOWS_SEPARATOR: char = '\\';
+
+// Receipts may have been produced on another operating system than the one the evidence is checked
+// on: paths may then use other separators, another Unicode normalization form (macOS uses NFD) or
+// differ only by case on case-insensitive file systems.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PathMatchingOptions {
+	pub normalize_separators: bool,
+	pub normalize_unicode: bool,
+	pub case_insensitive: bool,
+}
+
+impl Default for PathMatchingOptions {
+	fn default() -> Self {
+		Self {
+			normalize_separators: true,
+			normalize_unicode: true,
+			case_insensitive: false,
+		}
+	}
+}
+
+impl PathMatchingOptions {
+	pub fn get_key(&self, path: &Path) -> String {
+		let path = path.to_string_lossy();
+		let path = if self.normalize_separators {
+			path.replace(WINDOWS_SEPARATOR, &SEPARATOR.to_string())
+		} else {
+			path.replace(std::path::MAIN_SEPARATOR, &SEPARATOR.to_string())
+		};
+		let path = path
+			.split(SEPARATOR)
+			.filter(|c| !c.is_empty() && *c != ".")
+			.collect::<Vec<&str>>()
+			.join(&SEPARATOR.to_string());
+		let path = if self.normalize_unicode {
+			path.nfc().collect()
+		} else {
+			path
+		};
+		if self.case_insensitive {
+			path.to_lowercase()
+		} else {
+			path
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn strict() -> PathMatchingOptions {
+		PathMatchingOptions {
+			normalize_separators: false,
+			normalize_unicode: false,
+			case_insensitive: false,
+		}
+	}
+
+	#[test]
+	fn separators() {
+		let opt = PathMatchingOptions::default();
+		assert_eq!(opt.get_key(Path::new("dir\\sub\\a.txt")), "dir/sub/a.txt");
+		assert_eq!(opt.get_key(Path::new("./dir/a.txt")), "dir/a.txt");
+		assert_eq!(strict().get_key(Path::new("dir/a.txt")), "dir/a.txt");
+		#[cfg(unix)]
+		assert_eq!(strict().get_key(Path::new("dir\\a.txt")), "dir\\a.txt");
+	}
+
+	#[test]
+	fn unicode() {
+		let nfd = "e\u{301}te\u{301}.txt";
+		let nfc = "\u{e9}t\u{e9}.txt";
+		let opt = PathMatchingOptions::default();
+		assert_eq!(opt.get_key(Path::new(nfd)), opt.get_key(Path::new(nfc)));
+		assert_ne!(
+			strict().get_key(Path::new(nfd)),
+			strict().get_key(Path::new(nfc))
+		);
+	}
+
+	#[test]
+	fn case() {
+		let opt = PathMatchingOptions {
+			case_insensitive: true,
+			..PathMatchingOptions::default()
+		};
+		assert_eq!(opt.get_key(Path::new("Dir/ÉTÉ.TXT")), "dir/été.txt");
+		assert_eq!(
+			PathMatchingOptions::default().get_key(Path::new("Dir/A.txt")),
+			"Dir/A.txt"
+		);
+	}
+}
@@ -0,0 +1,281 @@
+use crate::audit::{self, AuditAction};
+use serde_derive::{Deserialize, Serialize};
+use std::fs::{self, read_to_string, File, Metadata, Permissions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const PROTECTION_FILE_SUFFIX: &str = ".protection.toml";
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ProtectedEntry {
+	path: PathBuf,
+	is_dir: bool,
+	readonly: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	mode: Option<u32>,
+}
+
+impl ProtectedEntry {
+	fn new(path: PathBuf, metadata: &Metadata) -> Self {
+		Self {
+			path,
+			is_dir: metadata.is_dir(),
+			readonly: metadata.permissions().readonly(),
+			mode: get_mode(metadata),
+		}
+	}
+
+	#[cfg(unix)]
+	fn original_permissions(&self, mut permissions: Permissions) -> Permissions {
+		use std::os::unix::fs::PermissionsExt;
+		match self.mode {
+			Some(mode) => permissions.set_mode(mode),
+			None => permissions.set_readonly(self.readonly),
+		}
+		permissions
+	}
+
+	#[cfg(not(unix))]
+	fn original_permissions(&self, mut permissions: Permissions) -> Permissions {
+		permissions.set_readonly(self.readonly);
+		permissions
+	}
+}
+
+// The original permissions are stored next to the content file so they can be restored once the
+// case is closed. Paths are relative to the evidence directory, except for files located outside
+// of it, such as an external content file.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct Protection {
+	#[serde(skip)]
+	base_dir: PathBuf,
+	#[serde(rename = "entry")]
+	entries: Vec<ProtectedEntry>,
+}
+
+impl Protection {
+	pub fn load(base_dir: &Path, ctn_file_path: &Path) -> io::Result<Self> {
+		let path = protection_file_path(ctn_file_path);
+		let mut protection = if path.is_file() {
+			let content = read_to_string(&path)?;
+			tracing::info!("Protection record loaded: {}", path.display());
+			toml::from_str(&content).map_err(io::Error::other)?
+		} else {
+			Self::default()
+		};
+		protection.base_dir = base_dir.to_path_buf();
+		Ok(protection)
+	}
+
+	pub fn nb_entries(&self) -> usize {
+		self.entries.len()
+	}
+
+	fn get_absolute_path(&self, entry: &ProtectedEntry) -> PathBuf {
+		self.base_dir.join(&entry.path)
+	}
+
+	// Paths which are already protected keep their recorded permissions, which are the original
+	// ones.
+	pub fn add(&mut self, path: &Path) -> io::Result<()> {
+		let path = path
+			.strip_prefix(&self.base_dir)
+			.unwrap_or(path)
+			.to_path_buf();
+		if self.entries.iter().any(|e| e.path == path) {
+			return Ok(());
+		}
+		let metadata = self.base_dir.join(&path).metadata()?;
+		self.entries.push(ProtectedEntry::new(path, &metadata));
+		Ok(())
+	}
+
+	pub fn add_directories(&mut self) -> io::Result<()> {
+		let dirs: Vec<PathBuf> = WalkDir::new(&self.base_dir)
+			.into_iter()
+			.filter_map(|e| e.ok())
+			.filter(|e| e.file_type().is_dir())
+			.map(|e| e.into_path())
+			.collect();
+		for dir in dirs {
+			self.add(&dir)?;
+		}
+		Ok(())
+	}
+
+	pub fn apply(&self) -> io::Result<()> {
+		for entry in &self.entries {
+			set_readonly(&self.get_absolute_path(entry))?;
+		}
+		Ok(())
+	}
+
+	pub fn write(&self, ctn_file_path: &Path) -> io::Result<PathBuf> {
+		let path = protection_file_path(ctn_file_path);
+		let content = toml::to_string(self).map_err(io::Error::other)?;
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let mut f = File::create(&path)?;
+		f.write_all(content.as_bytes())?;
+		tracing::info!("Protection record written: {}", path.display());
+		Ok(path)
+	}
+
+	// Files which have been removed are not listed, the check already reports them as missing.
+	pub fn get_unprotected_files(&self) -> Vec<PathBuf> {
+		let mut files: Vec<PathBuf> = self
+			.entries
+			.iter()
+			.filter(|e| match self.get_absolute_path(e).metadata() {
+				Ok(metadata) => !metadata.permissions().readonly(),
+				Err(_) => false,
+			})
+			.map(|e| e.path.clone())
+			.collect();
+		files.sort();
+		files
+	}
+
+	// Directories are restored first so the protection record can be removed afterward.
+	pub fn restore(&self) -> io::Result<()> {
+		let (dirs, files): (Vec<&ProtectedEntry>, Vec<&ProtectedEntry>) =
+			self.entries.iter().partition(|e| e.is_dir);
+		for entry in dirs.into_iter().chain(files) {
+			let path = self.get_absolute_path(entry);
+			match path.metadata() {
+				Ok(metadata) => {
+					fs::set_permissions(&path, entry.original_permissions(metadata.permissions()))?
+				}
+				Err(e) if e.kind() == io::ErrorKind::NotFound => {
+					tracing::warn!("Protected file not found: {}", path.display());
+				}
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(())
+	}
+}
+
+pub fn protection_file_path(ctn_file_path: &Path) -> PathBuf {
+	let mut name = ctn_file_path.file_name().unwrap_or_default().to_os_string();
+	name.push(PROTECTION_FILE_SUFFIX);
+	ctn_file_path.with_file_name(name)
+}
+
+pub fn is_protection_file(path: &Path, ctn_file_path: &Path) -> bool {
+	path == protection_file_path(ctn_file_path)
+}
+
+pub fn is_protected(ctn_file_path: &Path) -> bool {
+	protection_file_path(ctn_file_path).is_file()
+}
+
+pub fn unlock(base_dir: &Path, ctn_file_path: &Path, audit_log_path: &Path) -> io::Result<usize> {
+	let protection = Protection::load(base_dir, ctn_file_path)?;
+	protection.restore()?;
+	fs::remove_file(protection_file_path(ctn_file_path))?;
+	tracing::info!(
+		"Protection removed from {} files in {}",
+		protection.nb_entries(),
+		base_dir.display()
+	);
+	audit::record_in(
+		audit_log_path,
+		AuditAction::Unlock {
+			path: base_dir.to_path_buf(),
+			nb_files: protection.nb_entries(),
+		},
+	);
+	Ok(protection.nb_entries())
+}
+
+fn set_readonly(path: &Path) -> io::Result<()> {
+	let mut permissions = path.metadata()?.permissions();
+	if !permissions.readonly() {
+		permissions.set_readonly(true);
+		fs::set_permissions(path, permissions)?;
+	}
+	Ok(())
+}
+
+#[cfg(unix)]
+fn get_mode(metadata: &Metadata) -> Option<u32> {
+	use std::os::unix::fs::PermissionsExt;
+	Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn get_mode(_metadata: &Metadata) -> Option<u32> {
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use uuid::Uuid;
+
+	#[test]
+	fn protection_file() {
+		let ctn_file = Path::new("/tmp/evidence/contenu.txt");
+		assert_eq!(
+			protection_file_path(ctn_file),
+			PathBuf::from("/tmp/evidence/contenu.txt.protection.toml")
+		);
+		assert!(is_protection_file(
+			Path::new("/tmp/evidence/contenu.txt.protection.toml"),
+			ctn_file
+		));
+		assert!(!is_protection_file(ctn_file, ctn_file));
+	}
+
+	#[test]
+	fn lock_and_restore() {
+		let base_dir = std::env::temp_dir().join(format!("loutre-{}", Uuid::new_v4()));
+		fs::create_dir_all(base_dir.join("sub")).unwrap();
+		let file = base_dir.join("sub").join("a.txt");
+		fs::write(&file, "test").unwrap();
+		let ctn_file = base_dir.join("contenu.txt");
+		fs::write(&ctn_file, "").unwrap();
+
+		let mut protection = Protection::load(&base_dir, &ctn_file).unwrap();
+		assert_eq!(protection.nb_entries(), 0);
+		protection.add(&file).unwrap();
+		protection.add(&file).unwrap();
+		protection.add_directories().unwrap();
+		assert_eq!(protection.nb_entries(), 3);
+		protection.write(&ctn_file).unwrap();
+		protection.apply().unwrap();
+		assert!(file.metadata().unwrap().permissions().readonly());
+		assert!(base_dir.metadata().unwrap().permissions().readonly());
+
+		let protection = Protection::load(&base_dir, &ctn_file).unwrap();
+		assert_eq!(protection.nb_entries(), 3);
+		assert!(protection.get_unprotected_files().is_empty());
+		let mut permissions = file.metadata().unwrap().permissions();
+		#[allow(clippy::permissions_set_readonly_false)]
+		permissions.set_readonly(false);
+		fs::set_permissions(&file, permissions).unwrap();
+		assert_eq!(
+			protection.get_unprotected_files(),
+			[PathBuf::from("sub").join("a.txt")]
+		);
+
+		let audit_log = base_dir.with_extension("jsonl");
+		assert_eq!(unlock(&base_dir, &ctn_file, &audit_log).unwrap(), 3);
+		assert!(!is_protected(&ctn_file));
+		assert!(!base_dir.metadata().unwrap().permissions().readonly());
+		let log = audit::AuditLog::load(&audit_log).unwrap();
+		assert_eq!(
+			log.get_entries()[0].get_action(),
+			&AuditAction::Unlock {
+				path: base_dir.clone(),
+				nb_files: 3
+			}
+		);
+		fs::remove_file(&audit_log).unwrap();
+		fs::remove_dir_all(&base_dir).unwrap();
+	}
+}
@@ -0,0 +1,170 @@
+use crate::files::{HashedFile, HashedFileList};
+use crate::hash::{HashFunc, CHARS_TO_REMOVE};
+use crate::receipt::Receipt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MatchSource {
+	Evidence,
+	Receipt(PathBuf),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DigestMatch {
+	source: MatchSource,
+	file: HashedFile,
+}
+
+impl DigestMatch {
+	pub fn get_source(&self) -> &MatchSource {
+		&self.source
+	}
+
+	pub fn get_file(&self) -> &HashedFile {
+		&self.file
+	}
+}
+
+// Digests written in letters are often split into groups or prefixed by the algorithm's name.
+pub fn normalize_digest(input: &str) -> Option<String> {
+	let input = input.trim();
+	let digest = match input.rsplit_once([':', '=']) {
+		Some((_, digest)) => digest,
+		None => input,
+	};
+	let digest: String = digest
+		.chars()
+		.filter(|c| !c.is_whitespace() && !CHARS_TO_REMOVE.contains(c))
+		.collect::<String>()
+		.to_ascii_lowercase();
+	if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	Some(digest)
+}
+
+pub fn used_hash_functions(
+	file_list: Option<&HashedFileList>,
+	receipts: &[Receipt],
+) -> Vec<HashFunc> {
+	let mut hash_funcs = Vec::new();
+	for file in entries(file_list, receipts).map(|(_, f)| f) {
+		if !hash_funcs.contains(&file.get_hash_func()) {
+			hash_funcs.push(file.get_hash_func());
+		}
+	}
+	hash_funcs
+}
+
+// When the hashing function is unknown, which is the case for a pasted digest, entries computed
+// with any function are considered.
+pub fn find_digest(
+	digest: &str,
+	hash_func: Option<HashFunc>,
+	file_list: Option<&HashedFileList>,
+	receipts: &[Receipt],
+) -> Vec<DigestMatch> {
+	let mut matches: Vec<DigestMatch> = entries(file_list, receipts)
+		.filter(|(_, f)| hash_func.is_none_or(|h| h == f.get_hash_func()))
+		.filter(|(_, f)| f.get_hash().eq_ignore_ascii_case(digest))
+		.map(|(source, f)| DigestMatch {
+			source,
+			file: f.clone(),
+		})
+		.collect();
+	matches.sort_by(|a, b| a.file.get_relative_path().cmp(b.file.get_relative_path()));
+	matches
+}
+
+pub fn find_file(
+	path: &Path,
+	file_list: Option<&HashedFileList>,
+	receipts: &[Receipt],
+) -> io::Result<Vec<DigestMatch>> {
+	let mut matches = Vec::new();
+	for hash_func in used_hash_functions(file_list, receipts) {
+		let digest = hash_func.hash_file(path, None)?;
+		matches.extend(find_digest(&digest, Some(hash_func), file_list, receipts));
+	}
+	Ok(matches)
+}
+
+fn entries<'a>(
+	file_list: Option<&'a HashedFileList>,
+	receipts: &'a [Receipt],
+) -> impl Iterator<Item = (MatchSource, &'a HashedFile)> {
+	let evidence = file_list
+		.into_iter()
+		.flat_map(|lst| lst.get_files())
+		.map(|f| (MatchSource::Evidence, f));
+	let receipts = receipts.iter().flat_map(|r| {
+		r.get_file_list()
+			.get_files()
+			.map(|f| (MatchSource::Receipt(r.get_path().to_path_buf()), f))
+	});
+	evidence.chain(receipts)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+	fn file_list() -> HashedFileList {
+		let mut lst = HashedFileList::new();
+		lst.insert_file(HashedFile::new("a.txt", 4, HASH, HashFunc::Sha256));
+		lst.insert_file(HashedFile::new("b.txt", 4, HASH, HashFunc::Blake3));
+		lst.insert_file(HashedFile::new(
+			"c.txt",
+			2,
+			"0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c",
+			HashFunc::Sha256,
+		));
+		lst
+	}
+
+	#[test]
+	fn normalize() {
+		assert_eq!(normalize_digest(HASH), Some(HASH.to_string()));
+		assert_eq!(
+			normalize_digest(
+				"SHA-256: 9F86D081 884C7D65 9A2FEAA0 C55AD015 A3BF4F1B 2B0B822C D15D6C15 B0F00A08"
+			),
+			Some(HASH.to_string())
+		);
+		assert_eq!(
+			normalize_digest(&format!("SHA256 (a.txt) = {HASH}")),
+			Some(HASH.to_string())
+		);
+		assert_eq!(normalize_digest("not a digest"), None);
+		assert_eq!(normalize_digest("  "), None);
+	}
+
+	#[test]
+	fn digest() {
+		let lst = file_list();
+		let matches = find_digest(HASH, None, Some(&lst), &[]);
+		assert_eq!(matches.len(), 2);
+		assert_eq!(matches[0].get_source(), &MatchSource::Evidence);
+		assert_eq!(
+			matches[0].get_file().get_relative_path(),
+			Path::new("a.txt")
+		);
+		let matches = find_digest(HASH, Some(HashFunc::Blake3), Some(&lst), &[]);
+		assert_eq!(matches.len(), 1);
+		assert_eq!(
+			matches[0].get_file().get_relative_path(),
+			Path::new("b.txt")
+		);
+		assert!(find_digest(HASH, None, None, &[]).is_empty());
+	}
+
+	#[test]
+	fn hash_functions() {
+		let lst = file_list();
+		assert_eq!(used_hash_functions(Some(&lst), &[]).len(), 2);
+		assert!(used_hash_functions(None, &[]).is_empty());
+	}
+}
@@ -1,43 +1,347 @@
+use crate::content_file_format::ContentFileFormat;
 use crate::files::HashedFileList;
 use crate::hash::HashFunc;
 use crate::parsers::{
 	cksum_bsd_get_files, cksum_gnu_get_files, cnil_content_file_get_files,
 	cnil_platform_email_get_files_v1, cnil_platform_email_get_files_v2,
-	cnil_platform_email_get_files_v3,
+	cnil_platform_email_get_files_v3, custom_get_files, pdf_get_files, CustomReceiptFormat,
+	ParseMode, ParserOutput, ReceiptError, ReceiptParseError,
 };
+use crate::path_matching::PathMatchingOptions;
+use std::collections::HashMap;
 use std::fmt;
 use std::path::{Path, PathBuf};
+use strum::{EnumIter, IntoEnumIterator};
 
-type ReceiptParser = dyn Fn(&Path, HashFunc) -> Result<HashedFileList, ()>;
+const CUSTOM_FORMAT_PREFIX: &str = "custom-";
 
-const PARSERS: &[&ReceiptParser] = &[
-	&cksum_bsd_get_files,
-	&cksum_gnu_get_files,
-	&cnil_content_file_get_files,
-	&cnil_platform_email_get_files_v3,
-	&cnil_platform_email_get_files_v2,
-	&cnil_platform_email_get_files_v1,
+type ReceiptParser = dyn Fn(&Path, HashFunc, ParseMode) -> Result<ParserOutput, ReceiptParseError>;
+
+const PARSERS: &[(ReceiptFormat, &ReceiptParser)] = &[
+	(ReceiptFormat::CksumBsd, &cksum_bsd_get_files),
+	(ReceiptFormat::CksumGnu, &cksum_gnu_get_files),
+	(ReceiptFormat::CnilContentFile, &cnil_content_file_get_files),
+	(
+		ReceiptFormat::CnilPlatformEmailV3,
+		&cnil_platform_email_get_files_v3,
+	),
+	(
+		ReceiptFormat::CnilPlatformEmailV2,
+		&cnil_platform_email_get_files_v2,
+	),
+	(
+		ReceiptFormat::CnilPlatformEmailV1,
+		&cnil_platform_email_get_files_v1,
+	),
+	(ReceiptFormat::Pdf, &pdf_get_files),
 ];
 
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq)]
+pub enum ReceiptFormat {
+	CksumBsd,
+	CksumGnu,
+	CnilContentFile,
+	CnilPlatformEmailV3,
+	CnilPlatformEmailV2,
+	CnilPlatformEmailV1,
+	Pdf,
+	// Index of the format in the user-defined formats.
+	#[strum(disabled)]
+	Custom(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseReceiptFormatError;
+
+impl std::str::FromStr for ReceiptFormat {
+	type Err = ParseReceiptFormatError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(idx) = s.strip_prefix(CUSTOM_FORMAT_PREFIX) {
+			return idx
+				.parse()
+				.map(Self::Custom)
+				.map_err(|_| ParseReceiptFormatError);
+		}
+		Self::iter()
+			.find(|f| f.get_value() == s)
+			.ok_or(ParseReceiptFormatError)
+	}
+}
+
+impl ReceiptFormat {
+	pub fn get_value(&self) -> String {
+		match self {
+			Self::CksumBsd => "cksum-bsd".to_string(),
+			Self::CksumGnu => "cksum-gnu".to_string(),
+			Self::CnilContentFile => "cnil-content-file".to_string(),
+			Self::CnilPlatformEmailV3 => "cnil-platform-email-v3".to_string(),
+			Self::CnilPlatformEmailV2 => "cnil-platform-email-v2".to_string(),
+			Self::CnilPlatformEmailV1 => "cnil-platform-email-v1".to_string(),
+			Self::Pdf => "pdf".to_string(),
+			Self::Custom(idx) => format!("{CUSTOM_FORMAT_PREFIX}{idx}"),
+		}
+	}
+
+	pub fn get_tid_key(&self) -> &'static str {
+		match self {
+			Self::CksumBsd => "receipt_format_cksum_bsd",
+			Self::CksumGnu => "receipt_format_cksum_gnu",
+			Self::CnilContentFile => "receipt_format_cnil_content_file",
+			Self::CnilPlatformEmailV3 => "receipt_format_cnil_platform_email_v3",
+			Self::CnilPlatformEmailV2 => "receipt_format_cnil_platform_email_v2",
+			Self::CnilPlatformEmailV1 => "receipt_format_cnil_platform_email_v1",
+			Self::Pdf => "receipt_format_pdf",
+			Self::Custom(_) => "receipt_format_custom",
+		}
+	}
+
+	// Receipts which are also content files may be written again using the same format.
+	pub fn get_content_file_format(&self) -> Option<ContentFileFormat> {
+		match self {
+			Self::CksumBsd => Some(ContentFileFormat::CksumBsd),
+			Self::CksumGnu => Some(ContentFileFormat::CksumGnu),
+			Self::CnilContentFile => Some(ContentFileFormat::Cnil),
+			_ => None,
+		}
+	}
+
+	pub fn get_custom_name<'a>(&self, custom_formats: &'a [CustomReceiptFormat]) -> &'a str {
+		match self {
+			Self::Custom(idx) => custom_formats
+				.get(*idx)
+				.map(|f| f.name.as_str())
+				.unwrap_or_default(),
+			_ => "",
+		}
+	}
+}
+
+impl fmt::Display for ReceiptFormat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = match self {
+			Self::CksumBsd => "cksum (BSD)",
+			Self::CksumGnu => "cksum (GNU)",
+			Self::CnilContentFile => "CNIL content file",
+			Self::CnilPlatformEmailV3 => "CNIL platform email (v3)",
+			Self::CnilPlatformEmailV2 => "CNIL platform email (v2)",
+			Self::CnilPlatformEmailV1 => "CNIL platform email (v1)",
+			Self::Pdf => "PDF document",
+			Self::Custom(idx) => return write!(f, "custom format #{idx}"),
+		};
+		write!(f, "{s}")
+	}
+}
+
+// A parser's confidence is the share of the lines it considered as describing a file which it has
+// been able to parse. Headers, markers and free text are not taken into account, hence a content
+// file or an email may be fully parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FormatCandidate {
+	format: ReceiptFormat,
+	nb_files: usize,
+	nb_warnings: usize,
+	nb_lines: usize,
+}
+
+impl FormatCandidate {
+	fn new(format: ReceiptFormat, output: &ParserOutput) -> Self {
+		let nb_files = output.files.get_files().count();
+		let nb_warnings = output.warnings.len();
+		Self {
+			format,
+			nb_files,
+			nb_warnings,
+			nb_lines: output.nb_lines.max(nb_files + nb_warnings),
+		}
+	}
+
+	pub fn get_format(&self) -> ReceiptFormat {
+		self.format
+	}
+
+	pub fn get_confidence(&self) -> usize {
+		if self.nb_lines == 0 {
+			return 0;
+		}
+		self.nb_files * 100 / self.nb_lines
+	}
+
+	// Every line describing a file has been parsed, no other format may do better.
+	fn is_unambiguous(&self) -> bool {
+		self.get_confidence() == 100
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct Receipt {
 	path: PathBuf,
+	default_hash: HashFunc,
+	mode: ParseMode,
 	files: HashedFileList,
+	warnings: Vec<ReceiptParseError>,
+	format: FormatCandidate,
+	candidates: Vec<FormatCandidate>,
+	is_forced: bool,
+	path_matching: PathMatchingOptions,
+	custom_formats: Vec<CustomReceiptFormat>,
 }
 
 impl Receipt {
-	pub fn new(path: &Path, default_hash: HashFunc) -> Result<Self, ()> {
-		let files = get_files(path, default_hash)?;
+	// User-defined formats are tried after the built-in ones. The detection stops as soon as a
+	// format parses every line describing a file.
+	pub fn new(
+		path: &Path,
+		default_hash: HashFunc,
+		mode: ParseMode,
+		custom_formats: &[CustomReceiptFormat],
+	) -> Result<Self, ReceiptError> {
+		let mut errors = ReceiptError::default();
+		let mut best: Option<(FormatCandidate, ParserOutput)> = None;
+		let mut candidates = Vec::new();
+		let formats = PARSERS
+			.iter()
+			.map(|(f, _)| *f)
+			.chain((0..custom_formats.len()).map(ReceiptFormat::Custom));
+		for format in formats {
+			let Some(res) = parse(path, default_hash, mode, format, custom_formats) else {
+				continue;
+			};
+			match res {
+				Ok(output) => {
+					let candidate = FormatCandidate::new(format, &output);
+					tracing::debug!(
+						"{}: {format} matches with a confidence of {}%",
+						path.display(),
+						candidate.get_confidence()
+					);
+					candidates.push(candidate);
+					let is_better = match &best {
+						Some((c, _)) => candidate.get_confidence() > c.get_confidence(),
+						None => true,
+					};
+					if is_better {
+						best = Some((candidate, output));
+					}
+					if candidate.is_unambiguous() {
+						break;
+					}
+				}
+				Err(e) => {
+					tracing::debug!("{}: {e}", path.display());
+					errors.push(e);
+				}
+			}
+		}
+		let Some((format, output)) = best else {
+			return Err(errors);
+		};
+		tracing::info!("{}: detected format: {}", path.display(), format.format);
+		candidates.sort_by_key(|c| std::cmp::Reverse(c.get_confidence()));
 		Ok(Self {
 			path: path.to_owned(),
-			files,
+			default_hash,
+			mode,
+			files: output.files,
+			warnings: output.warnings,
+			format,
+			candidates,
+			is_forced: false,
+			path_matching: PathMatchingOptions::default(),
+			custom_formats: custom_formats.to_vec(),
 		})
 	}
 
+	pub fn with_format(
+		path: &Path,
+		default_hash: HashFunc,
+		mode: ParseMode,
+		format: ReceiptFormat,
+		custom_formats: &[CustomReceiptFormat],
+	) -> Result<Self, ReceiptError> {
+		let mut errors = ReceiptError::default();
+		let Some(res) = parse(path, default_hash, mode, format, custom_formats) else {
+			return Err(errors);
+		};
+		let output = res.map_err(|e| {
+			errors.push(e);
+			errors
+		})?;
+		tracing::info!("{}: forced format: {format}", path.display());
+		let candidate = FormatCandidate::new(format, &output);
+		Ok(Self {
+			path: path.to_owned(),
+			default_hash,
+			mode,
+			files: output.files,
+			warnings: output.warnings,
+			format: candidate,
+			candidates: vec![candidate],
+			is_forced: true,
+			path_matching: PathMatchingOptions::default(),
+			custom_formats: custom_formats.to_vec(),
+		})
+	}
+
+	// Parses the same file again, either with the given format or using auto-detection.
+	pub fn reload(&self, format: Option<ReceiptFormat>) -> Result<Self, ReceiptError> {
+		let mut receipt = match format {
+			Some(format) => Self::with_format(
+				&self.path,
+				self.default_hash,
+				self.mode,
+				format,
+				&self.custom_formats,
+			)?,
+			None => Self::new(
+				&self.path,
+				self.default_hash,
+				self.mode,
+				&self.custom_formats,
+			)?,
+		};
+		receipt.path_matching = self.path_matching;
+		Ok(receipt)
+	}
+
+	pub fn get_path(&self) -> &Path {
+		&self.path
+	}
+
 	pub fn get_file_list(&self) -> &HashedFileList {
 		&self.files
 	}
 
+	pub fn get_warnings(&self) -> &[ReceiptParseError] {
+		&self.warnings
+	}
+
+	pub fn get_format(&self) -> &FormatCandidate {
+		&self.format
+	}
+
+	pub fn get_other_candidates(&self) -> impl Iterator<Item = &FormatCandidate> {
+		self.candidates
+			.iter()
+			.filter(|c| c.format != self.format.format)
+	}
+
+	pub fn get_custom_formats(&self) -> &[CustomReceiptFormat] {
+		&self.custom_formats
+	}
+
+	pub fn is_format_forced(&self) -> bool {
+		self.is_forced
+	}
+
+	pub fn get_path_matching(&self) -> &PathMatchingOptions {
+		&self.path_matching
+	}
+
+	pub fn set_path_matching(&mut self, path_matching: PathMatchingOptions) {
+		self.path_matching = path_matching;
+	}
+
 	pub fn get_main_hashing_function(&self) -> HashFunc {
 		self.files.get_main_hashing_function()
 	}
@@ -49,11 +353,181 @@ impl fmt::Display for Receipt {
 	}
 }
 
-fn get_files(path: &Path, default_hash: HashFunc) -> Result<HashedFileList, ()> {
-	for parser in PARSERS {
-		if let Ok(files) = parser(path, default_hash) {
-			return Ok(files);
+fn parse(
+	path: &Path,
+	default_hash: HashFunc,
+	mode: ParseMode,
+	format: ReceiptFormat,
+	custom_formats: &[CustomReceiptFormat],
+) -> Option<Result<ParserOutput, ReceiptParseError>> {
+	match format {
+		ReceiptFormat::Custom(idx) => custom_formats
+			.get(idx)
+			.map(|custom| custom_get_files(path, default_hash, mode, custom, format)),
+		_ => PARSERS
+			.iter()
+			.find(|(f, _)| *f == format)
+			.map(|(_, parser)| parser(path, default_hash, mode)),
+	}
+}
+
+// Evidence may be sent in several uploads, each one having its own notice of receipt. A file listed
+// in several receipts with different fingerprints computed using the same hashing function is a
+// conflict.
+pub fn find_conflicts<'a, I>(file_lists: I) -> Vec<PathBuf>
+where
+	I: IntoIterator<Item = &'a HashedFileList>,
+{
+	let path_matching = PathMatchingOptions::default();
+	let mut fingerprints: HashMap<(String, HashFunc), &str> = HashMap::new();
+	let mut conflicts = Vec::new();
+	for file_list in file_lists {
+		for file in file_list.get_files() {
+			let key = (
+				path_matching.get_key(file.get_relative_path()),
+				file.get_hash_func(),
+			);
+			match fingerprints.get(&key) {
+				Some(hash) if !hash.eq_ignore_ascii_case(file.get_hash()) => {
+					tracing::warn!(
+						"{}: conflicting fingerprints in notices of receipt",
+						file.get_relative_path().display()
+					);
+					conflicts.push(file.get_relative_path().to_path_buf());
+				}
+				Some(_) => {}
+				None => {
+					fingerprints.insert(key, file.get_hash());
+				}
+			}
 		}
 	}
-	Err(())
+	conflicts.sort();
+	conflicts.dedup();
+	conflicts
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::files::HashedFile;
+	use std::str::FromStr;
+
+	fn output(nb_files: usize, nb_warnings: usize, nb_lines: usize) -> ParserOutput {
+		let mut files = HashedFileList::new();
+		for i in 0..nb_files {
+			files.insert_file(HashedFile::new(
+				format!("{i}.txt"),
+				0,
+				"00",
+				HashFunc::Sha256,
+			));
+		}
+		let warnings = (0..nb_warnings)
+			.map(|i| {
+				ReceiptParseError::at_line(
+					ReceiptFormat::CksumGnu,
+					crate::parsers::ParseErrorReason::InvalidLine,
+					i + 1,
+				)
+			})
+			.collect();
+		ParserOutput {
+			files,
+			warnings,
+			nb_lines,
+		}
+	}
+
+	#[test]
+	fn confidence() {
+		let c = FormatCandidate::new(ReceiptFormat::CksumGnu, &output(3, 1, 4));
+		assert_eq!(c.get_confidence(), 75);
+		let c = FormatCandidate::new(ReceiptFormat::CksumGnu, &output(2, 0, 2));
+		assert_eq!(c.get_confidence(), 100);
+		assert!(c.is_unambiguous());
+		let c = FormatCandidate::new(ReceiptFormat::CksumGnu, &output(0, 0, 0));
+		assert_eq!(c.get_confidence(), 0);
+		let c = FormatCandidate::new(ReceiptFormat::Custom(0), &output(2, 0, 8));
+		assert_eq!(c.get_confidence(), 25);
+		assert!(!c.is_unambiguous());
+		let c = FormatCandidate::new(ReceiptFormat::CksumGnu, &output(3, 1, 2));
+		assert_eq!(c.get_confidence(), 75);
+	}
+
+	#[test]
+	fn content_file_confidence() {
+		let dir = std::env::temp_dir().join(format!("loutre-{}", uuid::Uuid::new_v4()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let mut files = HashedFileList::new();
+		files.insert_file(HashedFile::new(
+			"a.txt",
+			4,
+			"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+			HashFunc::Sha256,
+		));
+		let path = dir.join("contenu.txt");
+		let mut file = std::fs::File::create_new(&path).unwrap();
+		ContentFileFormat::Cnil
+			.write_content_file(&mut file, &files, true)
+			.unwrap();
+		let receipt = Receipt::new(&path, HashFunc::Sha256, ParseMode::Strict, &[]).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+		assert_eq!(
+			receipt.get_format().get_format(),
+			ReceiptFormat::CnilContentFile
+		);
+		assert_eq!(receipt.get_format().get_confidence(), 100);
+		assert_eq!(receipt.get_other_candidates().count(), 0);
+	}
+
+	#[test]
+	fn format_value() {
+		for format in ReceiptFormat::iter() {
+			assert_eq!(ReceiptFormat::from_str(&format.get_value()), Ok(format));
+		}
+		assert_eq!(
+			ReceiptFormat::from_str("custom-2"),
+			Ok(ReceiptFormat::Custom(2))
+		);
+		assert!(ReceiptFormat::from_str("unknown").is_err());
+		assert!(ReceiptFormat::from_str("custom-x").is_err());
+	}
+
+	#[test]
+	fn content_file_format() {
+		assert_eq!(
+			ReceiptFormat::CksumGnu.get_content_file_format(),
+			Some(ContentFileFormat::CksumGnu)
+		);
+		assert_eq!(
+			ReceiptFormat::CnilContentFile.get_content_file_format(),
+			Some(ContentFileFormat::Cnil)
+		);
+		assert_eq!(ReceiptFormat::Pdf.get_content_file_format(), None);
+	}
+
+	#[test]
+	fn conflicts() {
+		let hash_a = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+		let hash_b = "0ea83f243ec71af1d50285617e8da3962a602a41266e8069d24de53b3c9d606c";
+		let mut first = HashedFileList::new();
+		first.insert_file(HashedFile::new("a.txt", 0, hash_a, HashFunc::Sha256));
+		first.insert_file(HashedFile::new("b.txt", 0, hash_a, HashFunc::Sha256));
+		let mut second = HashedFileList::new();
+		second.insert_file(HashedFile::new(
+			"a.txt",
+			0,
+			hash_a.to_uppercase(),
+			HashFunc::Sha256,
+		));
+		second.insert_file(HashedFile::new("dir\\b.txt", 0, hash_b, HashFunc::Sha256));
+		second.insert_file(HashedFile::new("b.txt", 0, hash_b, HashFunc::Sha256));
+		second.insert_file(HashedFile::new("c.txt", 0, hash_b, HashFunc::Sha256));
+		assert_eq!(
+			find_conflicts([&first, &second]),
+			vec![PathBuf::from("b.txt")]
+		);
+		assert!(find_conflicts([&first]).is_empty());
+	}
 }
@@ -1,8 +1,10 @@
 use crate::files::{HashedFile, HashedFileList};
-use std::fs::File;
 use std::io::{self, Write};
 
-pub fn ctn_file_cksum_bsd(ctn_file: &mut File, hashed_list: &HashedFileList) -> io::Result<()> {
+pub fn ctn_file_cksum_bsd<W: Write>(
+	ctn_file: &mut W,
+	hashed_list: &HashedFileList,
+) -> io::Result<()> {
 	for file in hashed_list.get_files() {
 		let line = format_line(file);
 		ctn_file.write_all(line.as_bytes())?;
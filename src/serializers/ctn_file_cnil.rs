@@ -1,5 +1,4 @@
 use crate::files::HashedFileList;
-use std::fs::File;
 use std::io::{self, Write};
 
 macro_rules! write_line {
@@ -9,7 +8,7 @@ macro_rules! write_line {
 	};
 }
 
-pub fn ctn_file_cnil(ctn_file: &mut File, hashed_list: &HashedFileList) -> io::Result<()> {
+pub fn ctn_file_cnil<W: Write>(ctn_file: &mut W, hashed_list: &HashedFileList) -> io::Result<()> {
 	write_line!(
 		ctn_file,
 		"Nom du document",
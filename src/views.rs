@@ -1,27 +1,53 @@
+mod audit_log;
 mod check_errors;
+mod compare;
 mod config_clipboard;
 mod config_clipboard_template;
 mod config_ctn_file_name;
 mod config_files;
 mod config_hash;
+mod config_known_files;
 mod config_messages;
+mod config_receipt_formats;
+mod convert_content_file;
+mod copy_and_hash;
 #[cfg(feature = "nightly")]
 mod debug;
 mod duplicated_files;
 mod empty_files;
 mod excluded_files;
+mod known_files;
+mod logs;
 mod main;
+mod metadata_changes;
+mod quick_verify;
+mod receipt_coverage;
+mod tools;
+mod update_content_file;
 
+pub use audit_log::AuditLog;
 pub use check_errors::CheckErrors;
+pub use compare::Compare;
 pub use config_clipboard::ClipboardConfig;
 pub use config_clipboard_template::ClipboardTemplateConfig;
 pub use config_ctn_file_name::ContentFileNameConfig;
 pub use config_files::FilesConfig;
 pub use config_hash::HashConfig;
+pub use config_known_files::KnownFilesConfig;
 pub use config_messages::MessagesConfig;
+pub use config_receipt_formats::ReceiptFormatsConfig;
+pub use convert_content_file::ConvertContentFile;
+pub use copy_and_hash::CopyAndHash;
 #[cfg(feature = "nightly")]
 pub use debug::Debug;
 pub use duplicated_files::DuplicatedFiles;
 pub use empty_files::EmptyFiles;
 pub use excluded_files::ExcludedFiles;
+pub use known_files::KnownFiles;
+pub use logs::Logs;
 pub use main::Main;
+pub use metadata_changes::MetadataChanges;
+pub use quick_verify::QuickVerify;
+pub use receipt_coverage::ReceiptCoverage;
+pub use tools::Tools;
+pub use update_content_file::UpdateContentFile;
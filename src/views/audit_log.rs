@@ -0,0 +1,179 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::audit::{self, AuditAction, AuditEntry, ChainErrorReason};
+use crate::components::{Button, Header, MainSection, Notification, Root};
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+
+#[component]
+pub fn AuditLog() -> Element {
+	let mut log =
+		use_signal(|| audit::AuditLog::load(&audit::get_file_path()).map_err(|e| e.to_string()));
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Tools {}),
+				h1 {
+					{ tid!("view_audit_log_title") }
+				}
+				p {
+					{ tid!("view_audit_log_intro", path: audit::get_file_path().display().to_string()) }
+				}
+				Button {
+					icon: "ri-refresh-line",
+					onclick: move |_event| {
+						log.set(audit::AuditLog::load(&audit::get_file_path()).map_err(|e| e.to_string()));
+					},
+					{ tid!("view_audit_log_verify") }
+				}
+				match log() {
+					Ok(log) => rsx! {
+						match log.get_error() {
+							Some(e) => rsx! {
+								Notification {
+									id: "view-audit-log-chain-err",
+									level: NotificationLevel::Error,
+									title: tid!("view_audit_log_chain_err_title"),
+									p {
+										{ tid!(
+											"view_audit_log_chain_err",
+											line: e.get_line(),
+											reason: match e.get_reason() {
+												ChainErrorReason::Parse => tid!("view_audit_log_chain_err_parse"),
+												ChainErrorReason::Sequence => tid!("view_audit_log_chain_err_sequence"),
+												ChainErrorReason::PreviousHash => tid!("view_audit_log_chain_err_prev_hash"),
+												ChainErrorReason::Hash => tid!("view_audit_log_chain_err_hash"),
+											}
+										) }
+									}
+								}
+							},
+							None => rsx! {
+								Notification {
+									id: "view-audit-log-chain-ok",
+									level: NotificationLevel::Success,
+									title: tid!("view_audit_log_chain_ok_title"),
+									p { { tid!("view_audit_log_chain_ok", nb: log.get_entries().len()) } }
+								}
+							},
+						}
+						EntryList { entries: log.get_entries().iter().rev().cloned().collect::<Vec<AuditEntry>>() }
+					},
+					Err(msg) => rsx! {
+						Notification {
+							id: "view-audit-log-io-err",
+							level: NotificationLevel::Error,
+							title: tid!("view_audit_log_title"),
+							p { { tid!("view_audit_log_io_err", msg: msg) } }
+						}
+					},
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn EntryList(entries: Vec<AuditEntry>) -> Element {
+	rsx! {
+		if !entries.is_empty() {
+			table {
+				class: "view-audit-log-entries",
+				tr {
+					th { "#" }
+					th { { tid!("view_audit_log_date") } }
+					th { { tid!("view_audit_log_user") } }
+					th { { tid!("view_audit_log_action") } }
+					th { { tid!("view_audit_log_hash") } }
+				}
+				for entry in entries {
+					tr {
+						td { "{entry.get_seq()}" }
+						td { "{entry.get_timestamp()}" }
+						td { "{entry.get_user()}" }
+						td { { action_description(entry.get_action()) } }
+						td {
+							code {
+								title: "{entry.get_hash()}",
+								{ entry.get_hash().chars().take(12).collect::<String>() }
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+fn action_description(action: &AuditAction) -> String {
+	match action {
+		AuditAction::DirectoryLoad { path, nb_files } => tid!(
+			"view_audit_log_action_directory_load",
+			path: path.display().to_string(),
+			nb: nb_files
+		),
+		AuditAction::Exclusion { path, nb_files } => tid!(
+			"view_audit_log_action_exclusion",
+			path: path.display().to_string(),
+			nb: nb_files
+		),
+		AuditAction::Hashing {
+			path,
+			hash_func,
+			nb_files,
+			merkle_root,
+		} => tid!(
+			"view_audit_log_action_hashing",
+			path: path.display().to_string(),
+			hash_func: hash_func,
+			nb: nb_files,
+			root: merkle_root
+		),
+		AuditAction::ContentFileWrite { path } => tid!(
+			"view_audit_log_action_ctn_file_write",
+			path: path.display().to_string()
+		),
+		AuditAction::ReadOnly { path, nb_files } => tid!(
+			"view_audit_log_action_readonly",
+			path: path.display().to_string(),
+			nb: nb_files
+		),
+		AuditAction::Unlock { path, nb_files } => tid!(
+			"view_audit_log_action_unlock",
+			path: path.display().to_string(),
+			nb: nb_files
+		),
+		AuditAction::Copy {
+			source,
+			destination,
+			nb_files,
+			nb_mismatches,
+		} => tid!(
+			"view_audit_log_action_copy",
+			source: source.display().to_string(),
+			destination: destination.display().to_string(),
+			nb: nb_files,
+			nb_mismatches: nb_mismatches
+		),
+		AuditAction::Check {
+			path,
+			reference,
+			nb_errors,
+		} => tid!(
+			"view_audit_log_action_check",
+			path: path.display().to_string(),
+			reference: reference.display().to_string(),
+			nb: nb_errors
+		),
+		AuditAction::ClipboardCopy { path, content } => tid!(
+			"view_audit_log_action_clipboard",
+			path: path.display().to_string(),
+			content: content
+		),
+		AuditAction::Recovery { line } => tid!("view_audit_log_action_recovery", line: line),
+	}
+}
@@ -29,12 +29,45 @@ pub fn CheckErrors() -> Element {
 					{ tid!("view_check_errors_title") }
 				}
 				if let FileList::Hashed(lst) = file_list {
+					if !lst.get_renamed_files().is_empty() {
+						h2 {
+							{ tid!("view_check_errors_renamed_title") }
+						}
+						p {
+							{ tid!("view_check_errors_renamed_intro") }
+						}
+						dl {
+							class: "view-check-errors-renamed",
+							for r in lst.get_renamed_files() {
+								Error {
+									path: "{r.get_receipt_path().display()}",
+									message: tid!("view_check_errors_renamed_local", path: r.get_local_path().display().to_string()),
+								}
+							}
+						}
+					}
 					if let CheckResult::Error(errors) = lst.get_result() {
 						if errors.contains(&CheckResultError::ContentFileParseError) {
 							p {
 								{ tid!("view_check_errors_ctn_file_parse") }
 							}
 						}
+						if errors.contains(&CheckResultError::ContentFileTrailerMismatch) {
+							p {
+								{ tid!("view_check_errors_ctn_file_trailer") }
+							}
+						}
+						if errors.contains(&CheckResultError::ContentFileMerkleRootMismatch) {
+							p {
+								{ tid!("view_check_errors_ctn_file_merkle_root") }
+							}
+						}
+						if !lst.is_empty() {
+							p {
+								class: "view-check-errors-merkle-root",
+								{ tid!("view_main_merkle_root", hash_func: lst.get_main_hashing_function().to_string(), root: lst.get_merkle_root()) }
+							}
+						}
 						dl {
 							class: "view-check-errors-err",
 							for path in filter_err_type!(errors, ContentFileMissingFile) {
@@ -61,6 +94,24 @@ pub fn CheckErrors() -> Element {
 									message: tid!("view_check_errors_receipt_match"),
 								}
 							}
+							for path in filter_err_type!(errors, ReceiptConflict) {
+								Error {
+									path: "{path}",
+									message: tid!("view_check_errors_receipt_conflict"),
+								}
+							}
+							for path in filter_err_type!(errors, ReceiptNotChecked) {
+								Error {
+									path: "{path}",
+									message: tid!("view_check_errors_receipt_not_checked"),
+								}
+							}
+							for path in filter_err_type!(errors, PathCollision) {
+								Error {
+									path: "{path}",
+									message: tid!("view_check_errors_path_collision"),
+								}
+							}
 						}
 					}
 				}
@@ -0,0 +1,268 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::compare::{compare_sources, CompareError, CompareSource, Comparison};
+use crate::components::{Button, FileButton, Header, MainSection, Notification, ProgressBar, Root};
+use crate::config::Config;
+use crate::events::ExternalEventSender;
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+use std::thread;
+use tokio::runtime::Handle;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CompareStatus {
+	Pending,
+	Done(Result<Comparison, CompareError>),
+}
+
+#[component]
+pub fn Compare() -> Element {
+	let cfg_sig = use_context::<Signal<Config>>();
+	let tx_sig = use_context::<Signal<ExternalEventSender>>();
+	let mut left = use_signal(|| None::<CompareSource>);
+	let mut right = use_signal(|| None::<CompareSource>);
+	let mut status = use_signal(|| None::<CompareStatus>);
+	let mut export_result = use_signal(|| None::<Result<PathBuf, String>>);
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Tools {}),
+				h1 {
+					{ tid!("view_compare_title") }
+				}
+				p {
+					{ tid!("view_compare_intro") }
+				}
+				div {
+					class: "view-compare-sources",
+					SourceSelector {
+						side: "left",
+						title: tid!("view_compare_left"),
+						source: left(),
+						onselect: move |src| {
+							left.set(Some(src));
+							status.set(None);
+						},
+					}
+					SourceSelector {
+						side: "right",
+						title: tid!("view_compare_right"),
+						source: right(),
+						onselect: move |src| {
+							right.set(Some(src));
+							status.set(None);
+						},
+					}
+				}
+				if let (Some(l), Some(r)) = (left(), right()) {
+					if status() != Some(CompareStatus::Pending) {
+						Button {
+							icon: "ri-git-compare-line",
+							onclick: move |_event| {
+								let (l, r) = (l.clone(), r.clone());
+								let config = cfg_sig();
+								let tx = tx_sig();
+								status.set(Some(CompareStatus::Pending));
+								export_result.set(None);
+								let handle = Handle::current();
+								let (res_tx, res_rx) = tokio::sync::oneshot::channel();
+								thread::spawn(move || {
+									let res = handle.block_on(compare_sources(l, r, &config, tx));
+									if let Err(ref e) = res {
+										tracing::error!("Unable to compare: {e}");
+									}
+									let _ = res_tx.send(res);
+								});
+								spawn(async move {
+									if let Ok(res) = res_rx.await {
+										status.set(Some(CompareStatus::Done(res)));
+									}
+								});
+							},
+							{ tid!("view_compare_submit") }
+						}
+					}
+				}
+				ProgressBar {}
+				match status() {
+					Some(CompareStatus::Pending) => rsx! {
+						p { { tid!("view_compare_pending") } }
+					},
+					Some(CompareStatus::Done(Ok(cmp))) => rsx! {
+						ComparisonResult { comparison: cmp.clone() }
+						FileButton {
+							icon: "ri-file-download-line",
+							accept: "",
+							multiple: false,
+							directory: true,
+							name: "view-compare-btn-export",
+							onchange: move |event: FormEvent| {
+								if let Some(f) = event.files().first() {
+									let res = cmp.write_report(&f.path()).map_err(|e| {
+										tracing::error!("Unable to write the comparison report: {e}");
+										e.to_string()
+									});
+									export_result.set(Some(res));
+								}
+							},
+							{ tid!("view_compare_export") }
+						}
+					},
+					Some(CompareStatus::Done(Err(e))) => rsx! {
+						Notification {
+							id: "view-compare-err-{e}",
+							level: NotificationLevel::Error,
+							title: tid!("view_compare_title"),
+							p {
+								match e {
+									CompareError::HashFunctionMismatch(l, r) => tid!("view_compare_err_hash_func", left: l.to_string(), right: r.to_string()),
+									CompareError::Io(msg) => tid!("view_compare_err_io", msg: msg),
+									CompareError::Parse(p) => tid!("view_compare_err_parse", path: p.display().to_string()),
+								}
+							}
+						}
+					},
+					None => rsx! {},
+				}
+				match export_result() {
+					Some(Ok(path)) => rsx! {
+						Notification {
+							id: "view-compare-export-ok",
+							level: NotificationLevel::Success,
+							title: tid!("view_compare_title"),
+							p { { tid!("view_compare_export_ok", path: path.display().to_string()) } }
+						}
+					},
+					Some(Err(msg)) => rsx! {
+						Notification {
+							id: "view-compare-export-err",
+							level: NotificationLevel::Error,
+							title: tid!("view_compare_title"),
+							p { { tid!("view_compare_export_err", msg: msg) } }
+						}
+					},
+					None => rsx! {},
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn SourceSelector(
+	side: String,
+	title: String,
+	source: Option<CompareSource>,
+	onselect: EventHandler<CompareSource>,
+) -> Element {
+	rsx! {
+		div {
+			class: "view-compare-source",
+			h2 { "{title}" }
+			FileButton {
+				icon: "ri-folder-line",
+				accept: "",
+				multiple: false,
+				directory: true,
+				name: "view-compare-btn-dir-{side}",
+				onchange: move |event: FormEvent| {
+					if let Some(f) = event.files().first() {
+						onselect.call(CompareSource::Directory(f.path()));
+					}
+				},
+				{ tid!("view_compare_select_dir") }
+			}
+			FileButton {
+				icon: "ri-file-list-3-line",
+				accept: "",
+				multiple: false,
+				directory: false,
+				name: "view-compare-btn-file-{side}",
+				onchange: move |event: FormEvent| {
+					if let Some(f) = event.files().first() {
+						onselect.call(CompareSource::ContentFile(f.path()));
+					}
+				},
+				{ tid!("view_compare_select_ctn_file") }
+			}
+			if let Some(src) = source {
+				p {
+					class: "component-file-list",
+					span {
+						class: match src {
+							CompareSource::Directory(_) => "component-file-list-icon ri-folder-line",
+							CompareSource::ContentFile(_) => "component-file-list-icon ri-file-list-3-line",
+						},
+					}
+					span {
+						class: "component-file-list-content",
+						"{src.get_path().display()}"
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn ComparisonResult(comparison: Comparison) -> Element {
+	rsx! {
+		if comparison.has_differences() {
+			Notification {
+				id: "view-compare-differences",
+				level: NotificationLevel::Warning,
+				title: tid!("view_compare_title"),
+				p { { tid!("view_compare_differences", hash_func: comparison.get_hash_func().to_string()) } }
+			}
+		} else {
+			Notification {
+				id: "view-compare-identical",
+				level: NotificationLevel::Success,
+				title: tid!("view_compare_title"),
+				p { { tid!("view_compare_no_difference", nb: comparison.get_identical().len(), hash_func: comparison.get_hash_func().to_string()) } }
+			}
+		}
+		FileSet {
+			title: tid!("view_compare_modified", nb: comparison.get_modified().len()),
+			files: comparison.get_modified().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+		}
+		FileSet {
+			title: tid!("view_compare_moved", nb: comparison.get_moved().len()),
+			files: comparison.get_moved().iter().map(|r| format!("{} → {}", r.get_receipt_path().display(), r.get_local_path().display())).collect::<Vec<String>>(),
+		}
+		FileSet {
+			title: tid!("view_compare_only_left", nb: comparison.get_only_left().len()),
+			files: comparison.get_only_left().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+		}
+		FileSet {
+			title: tid!("view_compare_only_right", nb: comparison.get_only_right().len()),
+			files: comparison.get_only_right().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+		}
+		FileSet {
+			title: tid!("view_compare_identical", nb: comparison.get_identical().len()),
+			files: comparison.get_identical().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+		}
+	}
+}
+
+#[component]
+fn FileSet(title: String, files: Vec<String>) -> Element {
+	rsx! {
+		if !files.is_empty() {
+			div {
+				class: "view-compare-file-set",
+				h2 { "{title}" }
+				ul {
+					for f in files {
+						li { "{f}" }
+					}
+				}
+			}
+		}
+	}
+}
@@ -112,6 +112,12 @@ fn gen_preview(input: String) -> String {
 		hash_func => "SHA-256",
 		nb_evidences => 3,
 		nb_start => 1,
+		case => context!(
+			case_id => "24-001",
+			organisation => "CNIL",
+			operator => "Jane Doe",
+			notes => "",
+		),
 		evidence => context!(
 			name => "test.txt",
 			size => 42,
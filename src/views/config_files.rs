@@ -2,11 +2,16 @@
 
 use crate::app::Route;
 use crate::components::config::{ConfigElement, ConfigMenu, ConfigMenuHighlight};
-use crate::components::{ApplyConfig, Checkbox, Header, MainSection, Root};
+use crate::components::{
+	ApplyConfig, Button, Checkbox, FileButton, Header, MainSection, Root, Select, SelectOption,
+};
 use crate::config::Config;
+use crate::logging::LogLevel;
 use crate::parsers::parse_bool;
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 #[component]
 pub fn FilesConfig() -> Element {
@@ -14,6 +19,14 @@ pub fn FilesConfig() -> Element {
 	let mut include_hidden_files = use_signal(|| cfg_sig().include_hidden_files());
 	let mut include_system_files = use_signal(|| cfg_sig().include_system_files());
 	let mut set_files_readonly = use_signal(|| cfg_sig().set_files_as_readonly());
+	let mut lock_directories = use_signal(|| cfg_sig().lock_directories());
+	let mut capture_metadata = use_signal(|| cfg_sig().is_metadata_capture_enabled());
+	let mut enable_history = use_signal(|| cfg_sig().is_history_enabled());
+	let mut content_file_dir = use_signal(|| cfg_sig().content_file_dir);
+	let log_level_opts = LogLevel::iter()
+		.map(|l| SelectOption::new(l.to_string(), l.to_string()))
+		.collect();
+	let mut log_level = use_signal(|| cfg_sig().get_log_level());
 
 	rsx! {
 		Root {
@@ -64,17 +77,117 @@ pub fn FilesConfig() -> Element {
 							},
 						}
 					}
+					// Lock directories
+					if cfg!(unix) {
+						ConfigElement {
+							id: "cfg_main_lock_directories",
+							label: tid!("view_config_main_msg_lock_directories"),
+							Checkbox {
+								id: "cfg_main_lock_directories",
+								name: "cfg_main_lock_directories",
+								checked: lock_directories(),
+								onchange: move |event: FormEvent| {
+									lock_directories.set(parse_bool(&event.data.value()));
+								},
+							}
+						}
+					}
+					// Metadata
+					ConfigElement {
+						id: "cfg_main_capture_metadata",
+						label: tid!("view_config_main_msg_capture_metadata"),
+						Checkbox {
+							id: "cfg_main_capture_metadata",
+							name: "cfg_main_capture_metadata",
+							checked: capture_metadata(),
+							onchange: move |event: FormEvent| {
+								capture_metadata.set(parse_bool(&event.data.value()));
+							},
+						}
+					}
+					// History
+					ConfigElement {
+						id: "cfg_main_enable_history",
+						label: tid!("view_config_main_msg_enable_history"),
+						Checkbox {
+							id: "cfg_main_enable_history",
+							name: "cfg_main_enable_history",
+							checked: enable_history(),
+							onchange: move |event: FormEvent| {
+								enable_history.set(parse_bool(&event.data.value()));
+							},
+						}
+					}
+					// Log level
+					ConfigElement {
+						id: "cfg_main_log_level",
+						label: tid!("view_config_main_msg_log_level"),
+						Select {
+							id: "cfg_main_log_level",
+							name: "cfg_main_log_level",
+							options: log_level_opts,
+							selected_option: log_level().to_string(),
+							onchange: move |event: FormEvent| {
+								if let Ok(new_value) = LogLevel::from_str(&event.data.value()) {
+									log_level.set(new_value);
+								}
+							},
+						}
+					}
+					// Content file directory
+					ConfigElement {
+						id: "cfg_main_content_file_dir",
+						label: tid!("view_config_main_msg_content_file_dir"),
+						input {
+							id: "cfg_main_content_file_dir",
+							name: "cfg_main_content_file_dir",
+							value: match content_file_dir() {
+								Some(dir) => dir.display().to_string(),
+								None => tid!("view_config_main_msg_content_file_dir_default"),
+							},
+							disabled: true,
+						}
+						FileButton {
+							icon: "ri-folder-3-line",
+							accept: "",
+							multiple: false,
+							directory: true,
+							name: "cfg_main_content_file_dir_select",
+							onchange: move |event: FormEvent| {
+								if let Some(f) = event.files().first() {
+									content_file_dir.set(Some(f.path()));
+								}
+							},
+							{ tid!("view_config_main_msg_content_file_dir_select") }
+						}
+						Button {
+							onclick: move |_event| {
+								content_file_dir.set(None);
+							},
+							{ tid!("view_config_main_msg_content_file_dir_reset") }
+						}
+					}
 				}
 				ApplyConfig {
 					onclick: move |_event| {
 						let new_include_hidden_files = include_hidden_files();
 						let new_include_system_files = include_system_files();
 						let new_set_files_readonly = set_files_readonly();
+						let new_lock_directories = lock_directories();
+						let new_capture_metadata = capture_metadata();
+						let new_enable_history = enable_history();
+						let new_content_file_dir = content_file_dir();
+						let new_log_level = log_level();
 						spawn(async move {
 							let mut cfg = cfg_sig();
 							cfg.include_hidden_files = Some(new_include_hidden_files);
 							cfg.include_system_files = Some(new_include_system_files);
 							cfg.set_files_as_readonly = Some(new_set_files_readonly);
+							cfg.lock_directories = Some(new_lock_directories);
+							cfg.capture_metadata = Some(new_capture_metadata);
+							cfg.enable_history = Some(new_enable_history);
+							cfg.content_file_dir = new_content_file_dir;
+							cfg.log_level = Some(new_log_level);
 							cfg.write_to_file();
 							cfg_sig.set(cfg);
 						});
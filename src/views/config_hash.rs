@@ -2,10 +2,13 @@
 
 use crate::app::Route;
 use crate::components::config::{ConfigElement, ConfigMenu, ConfigMenuHighlight};
-use crate::components::{ApplyConfig, Button, Header, MainSection, Root, Select, SelectOption};
+use crate::components::{
+	ApplyConfig, Button, Checkbox, Header, MainSection, Root, Select, SelectOption,
+};
 use crate::config::Config;
 use crate::content_file_format::ContentFileFormat;
 use crate::hash::HashFunc;
+use crate::parsers::parse_bool;
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
 use std::str::FromStr;
@@ -22,6 +25,9 @@ pub fn HashConfig() -> Element {
 		.collect();
 	let mut hash_function = use_signal(|| cfg_sig().hash_function);
 	let mut content_file_format = use_signal(|| cfg_sig().content_file_format);
+	let mut content_file_trailer = use_signal(|| cfg_sig().has_content_file_trailer());
+	let mut content_file_lenient_parsing =
+		use_signal(|| cfg_sig().has_content_file_lenient_parsing());
 
 	rsx! {
 		Root {
@@ -86,15 +92,45 @@ pub fn HashConfig() -> Element {
 							{ tid!("view_config_hash_msg_customize_ctn_file_name") }
 						}
 					}
+					// Content file trailer
+					ConfigElement {
+						id: "cfg_hash_content_file_trailer",
+						label: tid!("view_config_hash_msg_content_file_trailer"),
+						Checkbox {
+							id: "cfg_hash_content_file_trailer",
+							name: "cfg_hash_content_file_trailer",
+							checked: content_file_trailer(),
+							onchange: move |event: FormEvent| {
+								content_file_trailer.set(parse_bool(&event.data.value()));
+							},
+						}
+					}
+					// Lenient content file parsing
+					ConfigElement {
+						id: "cfg_hash_content_file_lenient_parsing",
+						label: tid!("view_config_hash_msg_content_file_lenient_parsing"),
+						Checkbox {
+							id: "cfg_hash_content_file_lenient_parsing",
+							name: "cfg_hash_content_file_lenient_parsing",
+							checked: content_file_lenient_parsing(),
+							onchange: move |event: FormEvent| {
+								content_file_lenient_parsing.set(parse_bool(&event.data.value()));
+							},
+						}
+					}
 				}
 				ApplyConfig {
 					onclick: move |_event| {
 						let new_hash_function = hash_function();
 						let new_content_file_format = content_file_format();
+						let new_content_file_trailer = content_file_trailer();
+						let new_content_file_lenient_parsing = content_file_lenient_parsing();
 						spawn(async move {
 							let mut cfg = cfg_sig();
 							cfg.hash_function = new_hash_function;
 							cfg.content_file_format = new_content_file_format;
+							cfg.content_file_trailer = Some(new_content_file_trailer);
+							cfg.content_file_lenient_parsing = Some(new_content_file_lenient_parsing);
 							cfg.write_to_file();
 							cfg_sig.set(cfg);
 						});
@@ -0,0 +1,258 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::config::{ConfigElement, ConfigMenu, ConfigMenuHighlight};
+use crate::components::{
+	Button, FileButton, Header, MainSection, Notification, Root, Select, SelectOption,
+};
+use crate::config::Config;
+use crate::known_files::{read_hash_set, KnownFileKind, KnownHashSet};
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+
+#[derive(Clone, Debug, PartialEq)]
+enum ImportResult {
+	Ok(String, usize),
+	Empty,
+	Io(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SetStatus {
+	Ok(usize),
+	Empty,
+	Io(String),
+}
+
+#[component]
+pub fn KnownFilesConfig() -> Element {
+	let mut cfg_sig = use_context::<Signal<Config>>();
+	let mut name = use_signal(String::new);
+	let mut kind = use_signal(|| KnownFileKind::Good);
+	let mut path = use_signal(|| None::<PathBuf>);
+	let mut result = use_signal(|| None::<ImportResult>);
+	let mut set_status = use_signal(HashMap::<PathBuf, SetStatus>::new);
+	use_effect(move || {
+		let sets = cfg_sig().known_hash_sets;
+		let (tx, rx) = tokio::sync::oneshot::channel();
+		thread::spawn(move || {
+			let status: HashMap<PathBuf, SetStatus> = sets
+				.into_iter()
+				.map(|set| {
+					let status = match read_hash_set(&set) {
+						Ok(digests) if digests.is_empty() => SetStatus::Empty,
+						Ok(digests) => SetStatus::Ok(digests.len()),
+						Err(e) => SetStatus::Io(e.to_string()),
+					};
+					(set.path, status)
+				})
+				.collect();
+			let _ = tx.send(status);
+		});
+		spawn(async move {
+			if let Ok(status) = rx.await {
+				set_status.set(status);
+			}
+		});
+	});
+	let kind_opts = vec![
+		SelectOption::new(
+			tid!("view_config_known_files_kind_good"),
+			KnownFileKind::Good.get_value().to_string(),
+		),
+		SelectOption::new(
+			tid!("view_config_known_files_kind_bad"),
+			KnownFileKind::Bad.get_value().to_string(),
+		),
+	];
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_config_title") }
+				}
+				ConfigMenu { hl: ConfigMenuHighlight::KnownFiles }
+				p {
+					{ tid!("view_config_known_files_intro") }
+				}
+				ul {
+					class: "view-config-known-files-list",
+					for (idx, set) in cfg_sig().known_hash_sets.into_iter().enumerate() {
+						li {
+							span {
+								class: "view-config-known-files-name",
+								"{set.name}"
+							}
+							" "
+							match set.kind {
+								KnownFileKind::Good => tid!("view_config_known_files_kind_good"),
+								KnownFileKind::Bad => tid!("view_config_known_files_kind_bad"),
+							}
+							code { "{set.path.display()}" }
+							match set_status().get(&set.path) {
+								Some(SetStatus::Ok(nb)) => rsx! {
+									span {
+										class: "view-config-known-files-status",
+										{ tid!("view_config_known_files_status_ok", nb: nb) }
+									}
+								},
+								Some(SetStatus::Empty) => rsx! {
+									span {
+										class: "view-config-known-files-status view-config-known-files-status-error",
+										{ tid!("view_config_known_files_status_empty") }
+									}
+								},
+								Some(SetStatus::Io(msg)) => rsx! {
+									span {
+										class: "view-config-known-files-status view-config-known-files-status-error",
+										{ tid!("view_config_known_files_status_io", msg: msg) }
+									}
+								},
+								None => rsx! {},
+							}
+							span {
+								class: "ri-delete-bin-line view-config-known-files-action",
+								title: tid!("view_config_known_files_delete"),
+								onclick: move |_| {
+									let mut cfg = cfg_sig();
+									if idx < cfg.known_hash_sets.len() {
+										cfg.known_hash_sets.remove(idx);
+										cfg.write_to_file();
+										cfg_sig.set(cfg);
+									}
+								},
+							}
+						}
+					}
+				}
+				h2 {
+					{ tid!("view_config_known_files_import_title") }
+				}
+				form {
+					ConfigElement {
+						id: "cfg_known_files_name",
+						label: tid!("view_config_known_files_name"),
+						input {
+							id: "cfg_known_files_name",
+							name: "cfg_known_files_name",
+							value: "{name}",
+							oninput: move |event| name.set(event.value()),
+						}
+					}
+					ConfigElement {
+						id: "cfg_known_files_kind",
+						label: tid!("view_config_known_files_kind"),
+						Select {
+							id: "cfg_known_files_kind",
+							name: "cfg_known_files_kind",
+							options: kind_opts,
+							selected_option: kind().get_value(),
+							onchange: move |event: FormEvent| {
+								if let Ok(new_kind) = KnownFileKind::from_str(&event.data.value()) {
+									kind.set(new_kind);
+								}
+							},
+						}
+					}
+					ConfigElement {
+						id: "cfg_known_files_path",
+						label: tid!("view_config_known_files_path"),
+						input {
+							id: "cfg_known_files_path",
+							name: "cfg_known_files_path",
+							value: path().map(|p| p.display().to_string()).unwrap_or_default(),
+							disabled: true,
+						}
+						FileButton {
+							icon: "ri-file-list-3-line",
+							accept: ".txt,.csv",
+							multiple: false,
+							directory: false,
+							name: "cfg_known_files_path_select",
+							onchange: move |event: FormEvent| {
+								if let Some(f) = event.files().first() {
+									let new_path = f.path();
+									if name().is_empty() {
+										if let Some(file_name) = new_path.file_name() {
+											name.set(file_name.to_string_lossy().to_string());
+										}
+									}
+									path.set(Some(new_path));
+									result.set(None);
+								}
+							},
+							{ tid!("view_config_known_files_select") }
+						}
+					}
+				}
+				if let Some(new_path) = path() {
+					Button {
+						icon: "ri-file-add-line",
+						onclick: move |_event| {
+							let set = KnownHashSet {
+								name: name().trim().to_string(),
+								path: new_path.clone(),
+								kind: kind(),
+							};
+							let res = match read_hash_set(&set) {
+								Ok(digests) if digests.is_empty() => ImportResult::Empty,
+								Ok(digests) => {
+									tracing::info!("Importing hash set {}: {} fingerprints", set.path.display(), digests.len());
+									let res = ImportResult::Ok(set.name.clone(), digests.len());
+									let mut cfg = cfg_sig();
+									cfg.known_hash_sets.push(set);
+									cfg.write_to_file();
+									cfg_sig.set(cfg);
+									name.set(String::new());
+									path.set(None);
+									res
+								}
+								Err(e) => {
+									tracing::error!("Unable to read hash set {}: {e}", set.path.display());
+									ImportResult::Io(e.to_string())
+								}
+							};
+							result.set(Some(res));
+						},
+						{ tid!("view_config_known_files_import") }
+					}
+				}
+				match result() {
+					Some(ImportResult::Ok(set_name, nb)) => rsx! {
+						Notification {
+							id: "view-config-known-files-ok-{set_name}",
+							level: NotificationLevel::Success,
+							title: tid!("view_config_known_files_import_title"),
+							p { { tid!("view_config_known_files_import_ok", name: set_name, nb: nb) } }
+						}
+					},
+					Some(ImportResult::Empty) => rsx! {
+						Notification {
+							id: "view-config-known-files-empty",
+							level: NotificationLevel::Error,
+							title: tid!("view_config_known_files_import_title"),
+							p { { tid!("view_config_known_files_import_empty") } }
+						}
+					},
+					Some(ImportResult::Io(msg)) => rsx! {
+						Notification {
+							id: "view-config-known-files-io",
+							level: NotificationLevel::Error,
+							title: tid!("view_config_known_files_import_title"),
+							p { { tid!("view_config_known_files_import_io", msg: msg) } }
+						}
+					},
+					None => rsx! {},
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,340 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::config::{ConfigElement, ConfigMenu, ConfigMenuHighlight};
+use crate::components::{
+	Button, FileButton, Header, MainSection, Notification, Root, Select, SelectOption,
+};
+use crate::config::Config;
+use crate::files::HashedFile;
+use crate::hash::HashFunc;
+use crate::notifications::NotificationLevel;
+use crate::parsers::{custom_get_files, CustomReceiptFormat, ReceiptParseError};
+use crate::receipt::ReceiptFormat;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+use strum::IntoEnumIterator;
+
+const MAX_SAMPLE_FILES: usize = 20;
+
+type SampleOutput = (Vec<HashedFile>, usize);
+
+#[component]
+pub fn ReceiptFormatsConfig() -> Element {
+	let mut cfg_sig = use_context::<Signal<Config>>();
+	let mut editing = use_signal(|| None::<usize>);
+	let mut draft = use_signal(CustomReceiptFormat::default);
+	let mut sample = use_signal(|| None::<PathBuf>);
+	let mut test_result = use_signal(|| None::<Result<SampleOutput, ReceiptParseError>>);
+	let mut test_pending = use_signal(|| false);
+	let mut save_err = use_signal(|| false);
+	let mut hash_func_opts = vec![SelectOption::new(
+		tid!("view_config_receipt_formats_hash_func_auto"),
+		String::new(),
+	)];
+	hash_func_opts.extend(
+		HashFunc::iter().map(|h| SelectOption::new(h.to_string(), h.to_string().to_lowercase())),
+	);
+	let selected_hash_func = draft()
+		.hash_function
+		.map(|h| h.to_string().to_lowercase())
+		.unwrap_or_default();
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_config_title") }
+				}
+				ConfigMenu { hl: ConfigMenuHighlight::ReceiptFormats }
+				p {
+					{ tid!("view_config_receipt_formats_intro") }
+				}
+				ul {
+					class: "view-config-receipt-formats-list",
+					for (idx, fmt) in cfg_sig().custom_receipt_formats.into_iter().enumerate() {
+						li {
+							span {
+								class: "view-config-receipt-formats-name",
+								"{fmt.name}"
+							}
+							code { "{fmt.line_regex}" }
+							span {
+								class: "ri-edit-line view-config-receipt-formats-action",
+								title: tid!("view_config_receipt_formats_edit"),
+								onclick: move |_| {
+									draft.set(fmt.clone());
+									editing.set(Some(idx));
+									test_result.set(None);
+									save_err.set(false);
+								},
+							}
+							span {
+								class: "ri-delete-bin-line view-config-receipt-formats-action",
+								title: tid!("view_config_receipt_formats_delete"),
+								onclick: move |_| {
+									let mut cfg = cfg_sig();
+									if idx < cfg.custom_receipt_formats.len() {
+										cfg.custom_receipt_formats.remove(idx);
+										cfg.write_to_file();
+										cfg_sig.set(cfg);
+									}
+									editing.set(None);
+								},
+							}
+						}
+					}
+				}
+				h2 {
+					if editing().is_some() {
+						{ tid!("view_config_receipt_formats_edit_title") }
+					} else {
+						{ tid!("view_config_receipt_formats_new_title") }
+					}
+				}
+				form {
+					ConfigElement {
+						id: "cfg_receipt_format_name",
+						label: tid!("view_config_receipt_formats_name"),
+						input {
+							id: "cfg_receipt_format_name",
+							name: "cfg_receipt_format_name",
+							value: "{draft().name}",
+							oninput: move |event| draft.write().name = event.value(),
+						}
+					}
+					ConfigElement {
+						id: "cfg_receipt_format_regex",
+						label: tid!("view_config_receipt_formats_regex"),
+						input {
+							id: "cfg_receipt_format_regex",
+							name: "cfg_receipt_format_regex",
+							class: "view-config-receipt-formats-regex",
+							value: "{draft().line_regex}",
+							oninput: move |event| draft.write().line_regex = event.value(),
+						}
+					}
+					ConfigElement {
+						id: "cfg_receipt_format_begin",
+						label: tid!("view_config_receipt_formats_begin_marker"),
+						input {
+							id: "cfg_receipt_format_begin",
+							name: "cfg_receipt_format_begin",
+							value: "{draft().begin_marker.unwrap_or_default()}",
+							oninput: move |event| draft.write().begin_marker = non_empty(event.value()),
+						}
+					}
+					ConfigElement {
+						id: "cfg_receipt_format_end",
+						label: tid!("view_config_receipt_formats_end_marker"),
+						input {
+							id: "cfg_receipt_format_end",
+							name: "cfg_receipt_format_end",
+							value: "{draft().end_marker.unwrap_or_default()}",
+							oninput: move |event| draft.write().end_marker = non_empty(event.value()),
+						}
+					}
+					ConfigElement {
+						id: "cfg_receipt_format_hash_func",
+						label: tid!("view_config_receipt_formats_hash_func"),
+						Select {
+							id: "cfg_receipt_format_hash_func",
+							name: "cfg_receipt_format_hash_func",
+							options: hash_func_opts,
+							selected_option: selected_hash_func,
+							onchange: move |event: FormEvent| {
+								draft.write().hash_function = HashFunc::from_str(&event.data.value()).ok();
+							},
+						}
+					}
+				}
+				p {
+					class: "view-config-receipt-formats-help",
+					{ tid!("view_config_receipt_formats_help") }
+				}
+				FileButton {
+					icon: "ri-mail-check-line",
+					accept: "",
+					multiple: false,
+					directory: false,
+					name: "cfg-receipt-format-sample",
+					onchange: move |event: FormEvent| {
+						if let Some(f) = event.files().first() {
+							sample.set(Some(f.path()));
+							test_result.set(None);
+						}
+					},
+					{ tid!("view_config_receipt_formats_select_sample") }
+				}
+				if let Some(path) = sample() {
+					p {
+						class: "component-file-list",
+						span {
+							class: "component-file-list-icon ri-mail-check-line",
+						}
+						span {
+							class: "component-file-list-content",
+							"{path.display()}"
+						}
+					}
+					Button {
+						icon: "ri-test-tube-line",
+						onclick: move |_event| {
+							let cfg = cfg_sig();
+							let path = path.clone();
+							let custom_format = draft();
+							let format = ReceiptFormat::Custom(editing().unwrap_or_default());
+							test_result.set(None);
+							test_pending.set(true);
+							let (tx, rx) = tokio::sync::oneshot::channel();
+							thread::spawn(move || {
+								let default_hash = match crate::analyse_hash::from_path(&path) {
+									Some(h) => h,
+									None => cfg.hash_function,
+								};
+								let res = custom_get_files(
+									&path,
+									default_hash,
+									cfg.get_parse_mode(),
+									&custom_format,
+									format,
+								)
+								.map(|output| {
+									let mut files: Vec<HashedFile> = output.files.get_files().cloned().collect();
+									files.sort_by(|a, b| a.get_relative_path().cmp(b.get_relative_path()));
+									(files, output.warnings.len())
+								});
+								if let Err(ref e) = res {
+									tracing::info!("Custom receipt format test failed: {e}");
+								}
+								let _ = tx.send(res);
+							});
+							spawn(async move {
+								if let Ok(res) = rx.await {
+									test_result.set(Some(res));
+								}
+								test_pending.set(false);
+							});
+						},
+						{ tid!("view_config_receipt_formats_test") }
+					}
+				}
+				if test_pending() {
+					p { { tid!("view_config_receipt_formats_test_pending") } }
+				}
+				if let Some(res) = test_result() {
+					SampleResult { result: res }
+				}
+				div {
+					class: "view-config-receipt-formats-buttons",
+					Button {
+						icon: "ri-save-line",
+						onclick: move |_event| {
+							let new_format = draft();
+							if new_format.name.trim().is_empty() || new_format.get_regex().is_err() {
+								save_err.set(true);
+								return;
+							}
+							let mut cfg = cfg_sig();
+							match editing() {
+								Some(idx) if idx < cfg.custom_receipt_formats.len() => {
+									cfg.custom_receipt_formats[idx] = new_format;
+								}
+								_ => cfg.custom_receipt_formats.push(new_format),
+							}
+							cfg.write_to_file();
+							cfg_sig.set(cfg);
+							draft.set(CustomReceiptFormat::default());
+							editing.set(None);
+							test_result.set(None);
+							save_err.set(false);
+						},
+						{ tid!("cpn_form_apply_config") }
+					}
+					if editing().is_some() {
+						Button {
+							icon: "ri-close-line",
+							onclick: move |_event| {
+								draft.set(CustomReceiptFormat::default());
+								editing.set(None);
+								test_result.set(None);
+								save_err.set(false);
+							},
+							{ tid!("view_config_receipt_formats_cancel") }
+						}
+					}
+				}
+				if save_err() {
+					Notification {
+						id: "view-config-receipt-formats-invalid",
+						level: NotificationLevel::Error,
+						title: tid!("view_config_receipt_formats_invalid_title"),
+						p { { tid!("view_config_receipt_formats_invalid") } }
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn SampleResult(result: Result<SampleOutput, ReceiptParseError>) -> Element {
+	match result {
+		Ok((files, nb_warnings)) => {
+			let nb_files = files.len();
+			rsx! {
+				div {
+					class: "view-config-receipt-formats-result",
+					p {
+						{ tid!("view_config_receipt_formats_result", nb: nb_files, warnings: nb_warnings) }
+					}
+					table {
+						tr {
+							th { { tid!("view_config_receipt_formats_result_path") } }
+							th { { tid!("view_config_receipt_formats_result_hash_func") } }
+							th { { tid!("view_config_receipt_formats_result_size") } }
+							th { { tid!("view_config_receipt_formats_result_hash") } }
+						}
+						for f in files.into_iter().take(MAX_SAMPLE_FILES) {
+							tr {
+								td { "{f.get_relative_path().display()}" }
+								td { "{f.get_hash_func()}" }
+								td { "{f.get_size()}" }
+								td { code { "{f.get_hash()}" } }
+							}
+						}
+					}
+					if nb_files > MAX_SAMPLE_FILES {
+						p { { tid!("view_config_receipt_formats_result_more", nb: nb_files - MAX_SAMPLE_FILES) } }
+					}
+				}
+			}
+		}
+		Err(e) => rsx! {
+			Notification {
+				id: "view-config-receipt-formats-err-{e}",
+				level: NotificationLevel::Error,
+				title: tid!("view_config_receipt_formats_result_err"),
+				p {
+					match e.get_line() {
+						Some(line) => tid!("view_main_receipt_err_line", line: line, reason: tid!(e.get_reason().get_tid_key())),
+						None => tid!(e.get_reason().get_tid_key()),
+					}
+				}
+			}
+		},
+	}
+}
+
+fn non_empty(value: String) -> Option<String> {
+	if value.is_empty() {
+		None
+	} else {
+		Some(value)
+	}
+}
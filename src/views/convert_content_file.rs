@@ -0,0 +1,178 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::config::ConfigElement;
+use crate::components::{
+	Button, Checkbox, FileButton, Header, MainSection, Notification, Root, Select, SelectOption,
+};
+use crate::config::Config;
+use crate::content_file_conversion::{
+	convert_content_file, get_source_hash_func, ConversionError, ConversionOptions,
+};
+use crate::content_file_format::ContentFileFormat;
+use crate::notifications::NotificationLevel;
+use crate::parsers::parse_bool;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+#[component]
+pub fn ConvertContentFile() -> Element {
+	let cfg_sig = use_context::<Signal<Config>>();
+	let ctn_file_format_opts = ContentFileFormat::iter()
+		.map(|h| SelectOption::new(h.to_string(), h.get_value()))
+		.collect();
+	let mut source = use_signal(|| None::<PathBuf>);
+	let mut source_hash = use_signal(|| cfg_sig().hash_function);
+	let mut format = use_signal(|| cfg_sig().content_file_format);
+	let mut file_name = use_signal(String::new);
+	let mut sizes_from_disk = use_signal(|| false);
+	let mut result = use_signal(|| None::<Result<PathBuf, ConversionError>>);
+	let default_name = format().default_content_file_name(source_hash());
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Tools {}),
+				h1 {
+					{ tid!("view_convert_title") }
+				}
+				p {
+					{ tid!("view_convert_intro") }
+				}
+				FileButton {
+					icon: "ri-file-list-3-line",
+					accept: ".txt",
+					multiple: false,
+					directory: false,
+					name: "view-convert-btn-select-source",
+					onchange: move |event: FormEvent| {
+						if let Some(f) = event.files().first() {
+							let cfg = cfg_sig();
+							let path = f.path();
+							let default_hash = crate::analyse_hash::from_path(&path).unwrap_or(cfg.hash_function);
+							source_hash.set(get_source_hash_func(&path, default_hash, cfg.get_parse_mode()));
+							source.set(Some(path));
+							result.set(None);
+						}
+					},
+					{ tid!("view_convert_select_source") }
+				}
+				if let Some(path) = source() {
+					p {
+						class: "component-file-list",
+						span {
+							class: "component-file-list-icon ri-file-list-3-line",
+						}
+						span {
+							class: "component-file-list-content",
+							"{path.display()}"
+						}
+					}
+				}
+				form {
+					ConfigElement {
+						id: "view_convert_format",
+						label: tid!("view_config_hash_msg_content_file_format"),
+						Select {
+							id: "view_convert_format",
+							name: "view_convert_format",
+							options: ctn_file_format_opts,
+							selected_option: format().get_value(),
+							onchange: move |event: FormEvent| {
+								if let Ok(new_value) = ContentFileFormat::from_str(&event.data.value()) {
+									format.set(new_value);
+								}
+							},
+						}
+					}
+					ConfigElement {
+						id: "view_convert_file_name",
+						label: tid!("view_config_hash_msg_content_file_name"),
+						input {
+							id: "view_convert_file_name",
+							name: "view_convert_file_name",
+							placeholder: "{default_name}",
+							value: "{file_name}",
+							oninput: move |event| file_name.set(event.value()),
+						}
+					}
+					ConfigElement {
+						id: "view_convert_sizes_from_disk",
+						label: tid!("view_convert_sizes_from_disk"),
+						Checkbox {
+							id: "view_convert_sizes_from_disk",
+							name: "view_convert_sizes_from_disk",
+							checked: sizes_from_disk(),
+							onchange: move |event: FormEvent| {
+								sizes_from_disk.set(parse_bool(&event.data.value()));
+							},
+						}
+					}
+				}
+				if let Some(src) = source() {
+					Button {
+						icon: "ri-file-transfer-line",
+						onclick: move |_event| {
+							let cfg = cfg_sig();
+							let src = src.clone();
+							let name = match file_name() {
+								n if n.trim().is_empty() => format().default_content_file_name(source_hash()),
+								n => n,
+							};
+							let options = ConversionOptions {
+								format: format(),
+								file_name: name,
+								sizes_from_disk: sizes_from_disk(),
+								with_trailer: cfg.has_content_file_trailer(),
+								parse_mode: cfg.get_parse_mode(),
+							};
+							let default_hash = match crate::analyse_hash::from_path(&src) {
+								Some(h) => h,
+								None => cfg.hash_function,
+							};
+							let res = convert_content_file(&src, default_hash, &options);
+							if let Err(ref e) = res {
+								tracing::error!("Unable to convert content file: {e}");
+							}
+							result.set(Some(res));
+						},
+						{ tid!("view_convert_submit") }
+					}
+				}
+				if let Some(res) = result() {
+					match res {
+						Ok(path) => rsx! {
+							Notification {
+								id: "view-convert-ok-{path.display()}",
+								level: NotificationLevel::Success,
+								title: tid!("view_convert_title"),
+								p { { tid!("view_convert_ok", path: path.display().to_string()) } }
+							}
+						},
+						Err(e) => rsx! {
+							Notification {
+								id: "view-convert-err-{e}",
+								level: NotificationLevel::Error,
+								title: tid!("view_convert_title"),
+								p {
+									match e {
+										ConversionError::DestinationExists(p) => tid!("view_convert_err_exists", path: p.display().to_string()),
+										ConversionError::Io(msg) => tid!("view_convert_err_io", msg: msg),
+										ConversionError::MissingSizes => tid!("view_convert_err_sizes"),
+										ConversionError::MixedHashFunctions => tid!("view_convert_err_mixed"),
+										ConversionError::Parse => tid!("view_convert_err_parse"),
+										ConversionError::UnknownSizes(paths) => tid!("view_convert_err_unknown_sizes", nb: paths.len(), path: paths[0].display().to_string()),
+									}
+								}
+							}
+						},
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,224 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{Button, FileButton, Header, MainSection, Notification, ProgressBar, Root};
+use crate::config::Config;
+use crate::copy::{copy_and_hash, CopyReport};
+use crate::events::ExternalEventSender;
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+use std::thread;
+use tokio::runtime::Handle;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CopyStatus {
+	Pending,
+	Done(Result<CopyReport, String>),
+}
+
+#[component]
+pub fn CopyAndHash() -> Element {
+	let cfg_sig = use_context::<Signal<Config>>();
+	let tx_sig = use_context::<Signal<ExternalEventSender>>();
+	let mut source = use_signal(|| None::<PathBuf>);
+	let mut destination = use_signal(|| None::<PathBuf>);
+	let mut status = use_signal(|| None::<CopyStatus>);
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Tools {}),
+				h1 {
+					{ tid!("view_copy_title") }
+				}
+				p {
+					{ tid!("view_copy_intro") }
+				}
+				div {
+					class: "view-copy-dirs",
+					DirSelector {
+						name: "source",
+						title: tid!("view_copy_source"),
+						path: source(),
+						onselect: move |path| {
+							source.set(Some(path));
+							status.set(None);
+						},
+					}
+					DirSelector {
+						name: "destination",
+						title: tid!("view_copy_destination"),
+						path: destination(),
+						onselect: move |path| {
+							destination.set(Some(path));
+							status.set(None);
+						},
+					}
+				}
+				if let (Some(src), Some(dest)) = (source(), destination()) {
+					if status() != Some(CopyStatus::Pending) {
+						Button {
+							icon: "ri-file-copy-2-line",
+							onclick: move |_event| {
+								let (src, dest) = (src.clone(), dest.clone());
+								let config = cfg_sig();
+								let tx = tx_sig();
+								status.set(Some(CopyStatus::Pending));
+								let handle = Handle::current();
+								let (res_tx, res_rx) = tokio::sync::oneshot::channel();
+								thread::spawn(move || {
+									let res = handle
+										.block_on(copy_and_hash(&src, &dest, &config, tx))
+										.map_err(|e| {
+											tracing::error!("Unable to copy the files: {e}");
+											e.to_string()
+										});
+									let _ = res_tx.send(res);
+								});
+								spawn(async move {
+									if let Ok(res) = res_rx.await {
+										status.set(Some(CopyStatus::Done(res)));
+									}
+								});
+							},
+							{ tid!("view_copy_submit") }
+						}
+					}
+				}
+				ProgressBar {}
+				match status() {
+					Some(CopyStatus::Pending) => rsx! {
+						p { { tid!("view_copy_pending") } }
+					},
+					Some(CopyStatus::Done(Ok(report))) => rsx! {
+						CopyResult { report: report }
+					},
+					Some(CopyStatus::Done(Err(msg))) => rsx! {
+						Notification {
+							id: "view-copy-err",
+							level: NotificationLevel::Error,
+							title: tid!("view_copy_title"),
+							p { { tid!("view_copy_err", msg: msg) } }
+						}
+					},
+					None => rsx! {},
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn DirSelector(
+	name: String,
+	title: String,
+	path: Option<PathBuf>,
+	onselect: EventHandler<PathBuf>,
+) -> Element {
+	rsx! {
+		div {
+			class: "view-copy-dir",
+			h2 { "{title}" }
+			FileButton {
+				icon: "ri-folder-line",
+				accept: "",
+				multiple: false,
+				directory: true,
+				name: "view-copy-btn-{name}",
+				onchange: move |event: FormEvent| {
+					if let Some(f) = event.files().first() {
+						onselect.call(f.path());
+					}
+				},
+				{ tid!("view_copy_select_dir") }
+			}
+			if let Some(path) = path {
+				p {
+					class: "component-file-list",
+					span {
+						class: "component-file-list-icon ri-folder-line",
+					}
+					span {
+						class: "component-file-list-content",
+						"{path.display()}"
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn CopyResult(report: CopyReport) -> Element {
+	let mismatches = report.get_mismatches().to_vec();
+	let errors = report.get_errors().to_vec();
+
+	rsx! {
+		if !errors.is_empty() {
+			Notification {
+				id: "view-copy-errors",
+				level: NotificationLevel::Error,
+				title: tid!("view_copy_title"),
+				p { { tid!("view_copy_errors", nb: errors.len()) } }
+			}
+			table {
+				class: "view-copy-mismatches",
+				tr {
+					th { { tid!("view_copy_file") } }
+					th { { tid!("view_copy_error") } }
+				}
+				for e in errors {
+					tr {
+						td { "{e.get_path().display()}" }
+						td { "{e.get_message()}" }
+					}
+				}
+			}
+		}
+		if mismatches.is_empty() {
+			Notification {
+				id: "view-copy-ok",
+				level: NotificationLevel::Success,
+				title: tid!("view_copy_title"),
+				p {
+					{ tid!(
+						"view_copy_ok",
+						nb: report.get_nb_files(),
+						path: report.get_destination().display().to_string()
+					) }
+				}
+				p {
+					{ tid!("view_copy_ctn_file", path: report.get_content_file_path().display().to_string()) }
+				}
+			}
+		} else {
+			Notification {
+				id: "view-copy-mismatches",
+				level: NotificationLevel::Error,
+				title: tid!("view_copy_title"),
+				p { { tid!("view_copy_mismatches", nb: mismatches.len()) } }
+				p {
+					{ tid!("view_copy_ctn_file", path: report.get_content_file_path().display().to_string()) }
+				}
+			}
+			table {
+				class: "view-copy-mismatches",
+				tr {
+					th { { tid!("view_copy_file") } }
+					th { { tid!("view_copy_source_hash") } }
+					th { { tid!("view_copy_destination_hash") } }
+				}
+				for m in mismatches {
+					tr {
+						td { "{m.get_path().display()}" }
+						td { code { "{m.get_source_hash()}" } }
+						td { code { "{m.get_destination_hash()}" } }
+					}
+				}
+			}
+		}
+	}
+}
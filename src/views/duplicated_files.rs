@@ -1,14 +1,34 @@
 #![allow(non_snake_case)]
 
 use crate::app::Route;
-use crate::components::{Header, MainSection, Root};
-use crate::files::FileList;
+use crate::components::config::ConfigElement;
+use crate::components::{Checkbox, Header, MainSection, Root};
+use crate::files::{FileList, HashedFile};
+use crate::known_files::KnownFileKind;
+use crate::parsers::parse_bool;
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
 
 #[component]
 pub fn DuplicatedFiles() -> Element {
 	let file_list = use_context::<Signal<FileList>>()();
+	let mut hide_known_good = use_signal(|| false);
+	let has_known_good = file_list.nb_known_files(KnownFileKind::Good) != 0;
+	let is_known_good = |f: &HashedFile| match &file_list {
+		FileList::Hashed(lst) => lst
+			.get_known_file(f)
+			.is_some_and(|m| m.get_kind() == KnownFileKind::Good),
+		FileList::NonHashed(_) | FileList::None => false,
+	};
+	let duplicated_files: Vec<Vec<HashedFile>> = file_list
+		.duplicated_files()
+		.into_iter()
+		.filter(|lst| !(hide_known_good() && lst.iter().all(is_known_good)))
+		.collect();
+	let previous_evidence = match &file_list {
+		FileList::Hashed(lst) => lst.get_previous_evidence(),
+		FileList::NonHashed(_) | FileList::None => Vec::new(),
+	};
 
 	rsx! {
 		Root {
@@ -18,7 +38,23 @@ pub fn DuplicatedFiles() -> Element {
 				h1 {
 					{ tid!("view_duplicated_files_title") }
 				}
-				for lst in file_list.duplicated_files() {
+				if has_known_good {
+					form {
+						ConfigElement {
+							id: "view_duplicated_files_hide_known_good",
+							label: tid!("view_duplicated_files_hide_known_good"),
+							Checkbox {
+								id: "view_duplicated_files_hide_known_good",
+								name: "view_duplicated_files_hide_known_good",
+								checked: hide_known_good(),
+								onchange: move |event: FormEvent| {
+									hide_known_good.set(parse_bool(&event.data.value()));
+								},
+							}
+						}
+					}
+				}
+				for lst in duplicated_files {
 					div {
 						class: "view-duplicated-files-list",
 						ul {
@@ -30,6 +66,28 @@ pub fn DuplicatedFiles() -> Element {
 						}
 					}
 				}
+				if !previous_evidence.is_empty() {
+					h2 {
+						{ tid!("view_duplicated_files_previous_title") }
+					}
+					table {
+						class: "view-duplicated-files-previous",
+						tr {
+							th { { tid!("view_duplicated_files_previous_file") } }
+							th { { tid!("view_duplicated_files_previous_case") } }
+							th { { tid!("view_duplicated_files_previous_path") } }
+							th { { tid!("view_duplicated_files_previous_date") } }
+						}
+						for (file, prev) in previous_evidence {
+							tr {
+								td { "{file.get_relative_path().display()}" }
+								td { "{prev.get_case_id()}" }
+								td { "{prev.get_path().display()}" }
+								td { { prev.get_recorded_at().format("%Y-%m-%d %H:%M UTC").to_string() } }
+							}
+						}
+					}
+				}
 			}
 		}
 	}
@@ -0,0 +1,73 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::config::ConfigElement;
+use crate::components::{Header, MainSection, Root, Select, SelectOption};
+use crate::files::FileList;
+use crate::known_files::KnownFileKind;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::str::FromStr;
+
+#[component]
+pub fn KnownFiles() -> Element {
+	let file_list = use_context::<Signal<FileList>>()();
+	let mut filter = use_signal(|| None::<KnownFileKind>);
+	let known_files = match file_list {
+		FileList::Hashed(lst) => lst.get_known_files(),
+		FileList::NonHashed(_) | FileList::None => Vec::new(),
+	};
+	let filter_opts = vec![
+		SelectOption::new(tid!("view_known_files_filter_all"), String::new()),
+		SelectOption::new(
+			tid!("view_known_files_filter_bad"),
+			KnownFileKind::Bad.get_value().to_string(),
+		),
+		SelectOption::new(
+			tid!("view_known_files_filter_good"),
+			KnownFileKind::Good.get_value().to_string(),
+		),
+	];
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_known_files_title") }
+				}
+				form {
+					ConfigElement {
+						id: "view_known_files_filter",
+						label: tid!("view_known_files_filter"),
+						Select {
+							id: "view_known_files_filter",
+							name: "view_known_files_filter",
+							options: filter_opts,
+							selected_option: filter().map(|k| k.get_value()).unwrap_or_default(),
+							onchange: move |event: FormEvent| {
+								filter.set(KnownFileKind::from_str(&event.data.value()).ok());
+							},
+						}
+					}
+				}
+				table {
+					class: "view-known-files-list",
+					for (file, known) in known_files.into_iter().filter(|(_, m)| filter().is_none_or(|k| k == m.get_kind())) {
+						tr {
+							class: "view-known-files-{known.get_kind().get_value()}",
+							td { "{file.get_relative_path().display()}" }
+							td {
+								match known.get_kind() {
+									KnownFileKind::Bad => tid!("view_known_files_bad", set: known.get_set_name()),
+									KnownFileKind::Good => tid!("view_known_files_good", set: known.get_set_name()),
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{
+	FileButton, Header, MainSection, Notification, Root, Select, SelectOption,
+};
+use crate::logging;
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+#[component]
+pub fn Logs() -> Element {
+	let log_files = use_signal(|| logging::list_log_files().unwrap_or_default());
+	let mut selected = use_signal(|| log_files().first().cloned());
+	let mut export_result = use_signal(|| None::<Result<(usize, PathBuf), String>>);
+	let file_opts = log_files()
+		.iter()
+		.map(|p| {
+			let name = p.file_name().unwrap_or_default().to_string_lossy();
+			SelectOption::new(name.to_string(), p.display().to_string())
+		})
+		.collect();
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_logs_title") }
+				}
+				p {
+					{ tid!("view_logs_intro", path: logging::get_log_dir().display().to_string()) }
+				}
+				if log_files().is_empty() {
+					p {
+						{ tid!("view_logs_empty") }
+					}
+				} else {
+					div {
+						class: "view-logs-actions",
+						Select {
+							id: "view_logs_file",
+							name: "view_logs_file",
+							options: file_opts,
+							selected_option: selected().map(|p| p.display().to_string()).unwrap_or_default(),
+							onchange: move |event: FormEvent| {
+								selected.set(Some(PathBuf::from(event.data.value())));
+							},
+						}
+						FileButton {
+							icon: "ri-folder-download-line",
+							accept: "",
+							multiple: false,
+							directory: true,
+							name: "view_logs_export",
+							onchange: move |event: FormEvent| {
+								if let Some(f) = event.files().first() {
+									let dest_dir = f.path();
+									let res = logging::export_log_files(&dest_dir)
+										.map(|nb| (nb, dest_dir))
+										.map_err(|e| e.to_string());
+									if let Err(ref e) = res {
+										tracing::error!("Unable to export the log files: {e}");
+									}
+									export_result.set(Some(res));
+								}
+							},
+							{ tid!("view_logs_export") }
+						}
+					}
+					match export_result() {
+						Some(Ok((nb, dest_dir))) => rsx! {
+							Notification {
+								id: "view-logs-export-ok",
+								level: NotificationLevel::Success,
+								title: tid!("view_logs_export"),
+								p { { tid!("view_logs_export_ok", nb: nb, path: dest_dir.display().to_string()) } }
+							}
+						},
+						Some(Err(msg)) => rsx! {
+							Notification {
+								id: "view-logs-export-err",
+								level: NotificationLevel::Error,
+								title: tid!("view_logs_export"),
+								p { { tid!("view_logs_export_err", msg: msg) } }
+							}
+						},
+						None => rsx! {},
+					}
+					if let Some(path) = selected() {
+						pre {
+							class: "view-logs-content",
+							{ read_to_string(&path).unwrap_or_else(|e| e.to_string()) }
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -1,32 +1,43 @@
 #![allow(non_snake_case)]
 
 use crate::app::Route;
+use crate::audit::{self, AuditAction};
+use crate::case::CaseMetadata;
 use crate::check::{check, CheckResult, CheckResultError, CheckType};
 use crate::clipboard::{Clipboard, ClipboardStart};
+use crate::components::config::ConfigElement;
 use crate::components::{
-	Button, FileButton, FileListIndicator, FileListReceipt, Header, LoadingBar, MainSection,
-	Notification, NotificationList, ProgressBar, Root,
+	receipt_format_name, Button, FileButton, FileListIndicator, FileListReceipt, Header,
+	LoadingBar, MainSection, Notification, NotificationList, ProgressBar, Root,
 };
 use crate::config::Config;
+use crate::content_file_trailer::has_invalid_trailer;
+use crate::content_file_update::ContentFileUpdate;
 use crate::events::{send_event, ExternalEvent, ExternalEventSender};
-use crate::files::{FileList, NonHashedFileList};
+use crate::files::{FileList, HashedFileList, NonHashedFileList};
+use crate::hash::HashFunc;
+use crate::history::{default_case_id, History};
+use crate::known_files::KnownFiles;
 use crate::notifications::NotificationLevel;
+use crate::parsers::{ReceiptError, ReceiptParseError};
+use crate::path_matching::PathMatchingOptions;
 use crate::progress::{LoadingBarStatus, ProgressBarStatus};
-use crate::receipt::Receipt;
+use crate::protection;
+use crate::receipt::{find_conflicts, Receipt};
 use dioxus::html::{FileData, HasFileData};
 use dioxus::prelude::*;
 use dioxus_i18n::tid;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::thread;
 use tokio::runtime::Handle;
 
 #[component]
 pub fn Main() -> Element {
-	let file_list_sig = use_context::<Signal<FileList>>();
+	let mut file_list_sig = use_context::<Signal<FileList>>();
 	let pg_status_opt = use_context::<Signal<Option<ProgressBarStatus>>>()();
 	let lb_status = use_context::<Signal<LoadingBarStatus>>()();
 	let config_sig = use_context::<Signal<Config>>();
-	let receipt_opt_sig = use_context::<Signal<Option<Receipt>>>();
+	let receipts_sig = use_context::<Signal<Vec<Receipt>>>();
 	let tx_sig = use_context::<Signal<ExternalEventSender>>();
 	let mut clipboard_sig = use_context::<Signal<Clipboard>>();
 	let clipboard_start_sig = use_context::<Signal<ClipboardStart>>();
@@ -66,8 +77,8 @@ pub fn Main() -> Element {
 					}
 					FileButton {
 						icon: "ri-file-check-line",
-						accept: ".msg,.txt",
-						multiple: false,
+						accept: ".eml,.msg,.pdf,.txt",
+						multiple: true,
 						directory: false,
 						name: "view-main-btn-select-receipt",
 						onchange: move |event: FormEvent| {
@@ -80,6 +91,16 @@ pub fn Main() -> Element {
 				}
 				FileListIndicator {}
 				FileListReceipt {}
+				ReceiptErrorNotification {}
+				for rcpt in receipts_sig() {
+					if !rcpt.get_warnings().is_empty() {
+						ParseWarningsNotification {
+							id: "view-main-receipt-warnings-{rcpt}",
+							title: tid!("view_main_receipt_warnings_title"),
+							warnings: rcpt.get_warnings().to_vec(),
+						}
+					}
+				}
 				NotificationList {}
 				ProgressBar {}
 				LoadingBar {}
@@ -88,22 +109,58 @@ pub fn Main() -> Element {
 					div {
 						if let FileList::NonHashed(file_lst) = file_list_sig() {
 							if !is_waiting {
+								if let Some(ctn_file_path) = external_content_file_path(&config_sig(), &file_lst) {
+									p {
+										class: "view-main-ctn-file-location",
+										{ tid!("view_main_ctn_file_location", path: ctn_file_path.display().to_string()) }
+									}
+								}
+								FileButton {
+									icon: "ri-folder-received-line",
+									accept: "",
+									multiple: false,
+									directory: true,
+									name: "view-main-btn-select-ctn-file-dir",
+									onchange: move |event: FormEvent| {
+										if let Some(f) = event.files().first() {
+											if let FileList::NonHashed(mut lst) = file_list_sig() {
+												let path = f.path().join(config_sig().get_content_file_name());
+												tracing::info!("Content file location set to: {}", path.display());
+												lst.set_explicit_content_file_path(Some(path));
+												file_list_sig.set(FileList::NonHashed(lst));
+											}
+										}
+									},
+									{ tid!("view_main_select_ctn_file_dir") }
+								}
+								CaseForm {}
 								if file_lst.content_file_exists(&config_sig()) {
 									Button {
 										icon: "ri-shield-check-line",
 										onclick: move |_event| {
 											spawn(async move {
-												calc_fingerprints(&config_sig(), tx_sig(), receipt_opt_sig(), file_list_sig()).await;
+												calc_fingerprints(&config_sig(), tx_sig(), receipts_sig(), file_list_sig()).await;
 											});
 										},
 										{ tid!("view_main_check_fingerprints") }
 									}
+									Button {
+										icon: "ri-file-edit-line",
+										onclick: move |_event| {
+											spawn(async move {
+												prepare_update(&config_sig(), tx_sig(), file_list_sig()).await;
+											});
+											navigator().push(Route::UpdateContentFile {});
+										},
+										{ tid!("view_main_update_ctn_file") }
+									}
+									ProtectionUnlock {}
 								} else {
 									Button {
 										icon: "ri-shield-flash-line",
 										onclick: move |_event| {
 											spawn(async move {
-												calc_fingerprints(&config_sig(), tx_sig(), receipt_opt_sig(), file_list_sig()).await;
+												calc_fingerprints(&config_sig(), tx_sig(), receipts_sig(), file_list_sig()).await;
 											});
 										},
 										{ tid!("view_main_calc_fingerprints") }
@@ -112,12 +169,23 @@ pub fn Main() -> Element {
 							}
 						}
 						if let FileList::Hashed(lst) = file_list_sig() {
+							if !lst.get_parse_warnings().is_empty() {
+								ParseWarningsNotification {
+									id: "view-main-ctn-file-warnings-{lst.get_id()}",
+									title: tid!("view_main_ctn_file_warnings_title"),
+									warnings: lst.get_parse_warnings().to_vec(),
+								}
+							}
 							if let CheckResult::Ok = lst.get_result() {
 								Notification {
 									id: "view-main-file-check-ok",
 									level: NotificationLevel::Success,
 									title: tid!("view_main_check_result_title"),
 									p { { tid!("view_main_check_result_ok_text") } }
+									p {
+										class: "view-main-merkle-root",
+										{ tid!("view_main_merkle_root", hash_func: lst.get_main_hashing_function().to_string(), root: lst.get_merkle_root()) }
+									}
 								}
 								Button {
 									icon: "ri-clipboard-line",
@@ -151,6 +219,38 @@ pub fn Main() -> Element {
 									{ tid!("view_main_clipboard_btn_file") }
 								}
 							}
+							if let Some(coverage) = lst.get_receipt_coverage() {
+								Notification {
+									id: "view-main-receipt-coverage",
+									level: NotificationLevel::Info,
+									title: tid!("view_main_receipt_coverage_title"),
+									p { { tid!("view_main_receipt_coverage_text", nb: coverage.len(), total: lst.get_files().len()) } }
+									p {
+										Button {
+											onclick: move |_event| {
+												navigator().push(Route::ReceiptCoverage {});
+											},
+											{ tid!("view_main_receipt_coverage_link") }
+										}
+									}
+								}
+							}
+							if !lst.get_renamed_files().is_empty() {
+								Notification {
+									id: "view-main-file-check-renamed",
+									level: NotificationLevel::Info,
+									title: tid!("view_main_check_result_title"),
+									p { { tid!("view_main_check_result_renamed_text", nb: lst.get_renamed_files().len()) } }
+									p {
+										Button {
+											onclick: move |_event| {
+												navigator().push(Route::CheckErrors {});
+											},
+											{ tid!("view_main_check_result_renamed_link") }
+										}
+									}
+								}
+							}
 							if let CheckResult::Error(_) = lst.get_result() {
 								Notification {
 									id: "view-main-file-check-err",
@@ -175,19 +275,229 @@ pub fn Main() -> Element {
 	}
 }
 
-async fn load_files(config: &Config, tx: ExternalEventSender, files: Vec<FileData>) {
-	tracing::info!("File loading: {:?}", files);
-	if let Some(f) = files.first() {
-		let path = f.path();
-		if path.is_file() {
-			load_receipt(config, tx.clone(), &path).await;
+// The case metadata are edited directly in the file list, so they are written along with the
+// content file.
+#[component]
+fn CaseForm() -> Element {
+	let mut file_list_sig = use_context::<Signal<FileList>>();
+	let case = match file_list_sig() {
+		FileList::NonHashed(lst) => lst.get_case().clone(),
+		_ => return rsx! {},
+	};
+	let mut update_case = move |update: &dyn Fn(&mut CaseMetadata)| {
+		if let FileList::NonHashed(lst) = &mut *file_list_sig.write() {
+			let mut case = lst.get_case().clone();
+			update(&mut case);
+			lst.set_case(case);
+		}
+	};
+
+	rsx! {
+		details {
+			class: "view-main-case",
+			open: !case.is_empty(),
+			summary {
+				{ tid!("view_main_case_title") }
+			}
+			form {
+				ConfigElement {
+					id: "view_main_case_id",
+					label: tid!("view_main_case_id"),
+					input {
+						id: "view_main_case_id",
+						name: "view_main_case_id",
+						value: "{case.case_id}",
+						oninput: move |event| {
+							let value = event.value();
+							update_case(&|c| c.case_id = value.clone());
+						},
+					}
+				}
+				ConfigElement {
+					id: "view_main_case_organisation",
+					label: tid!("view_main_case_organisation"),
+					input {
+						id: "view_main_case_organisation",
+						name: "view_main_case_organisation",
+						value: "{case.organisation}",
+						oninput: move |event| {
+							let value = event.value();
+							update_case(&|c| c.organisation = value.clone());
+						},
+					}
+				}
+				ConfigElement {
+					id: "view_main_case_operator",
+					label: tid!("view_main_case_operator"),
+					input {
+						id: "view_main_case_operator",
+						name: "view_main_case_operator",
+						value: "{case.operator}",
+						oninput: move |event| {
+							let value = event.value();
+							update_case(&|c| c.operator = value.clone());
+						},
+					}
+				}
+				ConfigElement {
+					id: "view_main_case_notes",
+					label: tid!("view_main_case_notes"),
+					textarea {
+						id: "view_main_case_notes",
+						name: "view_main_case_notes",
+						value: "{case.notes}",
+						oninput: move |event| {
+							let value = event.value();
+							update_case(&|c| c.notes = value.clone());
+						},
+					}
+				}
+			}
+		}
+	}
+}
+
+// Restores the permissions the files and directories had before they were protected, which is
+// usually done once the case is closed.
+#[component]
+fn ProtectionUnlock() -> Element {
+	let file_list_sig = use_context::<Signal<FileList>>();
+	let config_sig = use_context::<Signal<Config>>();
+	let mut result = use_signal(|| None::<Result<usize, String>>);
+	let FileList::NonHashed(lst) = file_list_sig() else {
+		return rsx! {};
+	};
+	let Ok(ctn_file_path) = lst.get_content_file_absolute_path(&config_sig()) else {
+		return rsx! {};
+	};
+	let base_dir = lst.get_base_dir().to_path_buf();
+
+	rsx! {
+		if protection::is_protected(&ctn_file_path) {
+			Button {
+				icon: "ri-lock-unlock-line",
+				onclick: move |_event| {
+					let res = protection::unlock(&base_dir, &ctn_file_path, &audit::get_file_path()).map_err(|e| e.to_string());
+					if let Err(ref e) = res {
+						tracing::error!("Unable to remove the protection: {e}");
+					}
+					result.set(Some(res));
+				},
+				{ tid!("view_main_unlock") }
+			}
+		}
+		match result() {
+			Some(Ok(nb)) => rsx! {
+				Notification {
+					id: "view-main-unlock-ok",
+					level: NotificationLevel::Success,
+					title: tid!("view_main_unlock_title"),
+					p { { tid!("view_main_unlock_ok", nb: nb) } }
+				}
+			},
+			Some(Err(msg)) => rsx! {
+				Notification {
+					id: "view-main-unlock-err",
+					level: NotificationLevel::Error,
+					title: tid!("view_main_unlock_title"),
+					p { { tid!("view_main_unlock_err", msg: msg) } }
+				}
+			},
+			None => rsx! {},
+		}
+	}
+}
+
+#[component]
+fn ReceiptErrorNotification() -> Element {
+	let receipt_error_sig = use_context::<Signal<Option<ReceiptError>>>();
+	let Some(error) = receipt_error_sig() else {
+		return rsx! {};
+	};
+	let Some(err) = error.most_plausible() else {
+		return rsx! {};
+	};
+	let custom_formats = use_context::<Signal<Config>>()().custom_receipt_formats;
+	let formats = error
+		.get_attempts()
+		.iter()
+		.map(|e| receipt_format_name(e.get_format(), &custom_formats))
+		.collect::<Vec<String>>()
+		.join(", ");
+	rsx! {
+		Notification {
+			id: "view-main-receipt-err-{err}",
+			level: NotificationLevel::Error,
+			title: tid!("view_main_receipt_err_title"),
+			p {
+				{ tid!("view_main_receipt_err_format", format: receipt_format_name(err.get_format(), &custom_formats)) }
+			}
+			p {
+				match (err.get_line(), err.get_column()) {
+					(Some(line), Some(column)) => tid!("view_main_receipt_err_position", line: line, column: column, reason: tid!(err.get_reason().get_tid_key())),
+					(Some(line), None) => tid!("view_main_receipt_err_line", line: line, reason: tid!(err.get_reason().get_tid_key())),
+					_ => tid!(err.get_reason().get_tid_key()),
+				}
+			}
+			p {
+				{ tid!("view_main_receipt_err_tried", formats: formats) }
+			}
 		}
-		if path.is_dir() {
-			load_directory(config, tx, &path).await;
+	}
+}
+
+#[component]
+fn ParseWarningsNotification(
+	id: String,
+	title: String,
+	warnings: Vec<ReceiptParseError>,
+) -> Element {
+	rsx! {
+		Notification {
+			id: id,
+			level: NotificationLevel::Warning,
+			title: title,
+			p { { tid!("view_main_parse_warnings_text", nb: warnings.len()) } }
+			ul {
+				class: "view-main-parse-warnings",
+				for w in warnings {
+					li {
+						match (w.get_line(), w.get_column()) {
+							(Some(line), Some(column)) => tid!("view_main_receipt_err_position", line: line, column: column, reason: tid!(w.get_reason().get_tid_key())),
+							(Some(line), None) => tid!("view_main_receipt_err_line", line: line, reason: tid!(w.get_reason().get_tid_key())),
+							_ => tid!(w.get_reason().get_tid_key()),
+						}
+					}
+				}
+			}
 		}
 	}
 }
 
+fn external_content_file_path(config: &Config, file_list: &NonHashedFileList) -> Option<PathBuf> {
+	let ctn_file_path = file_list.get_content_file_absolute_path(config).ok()?;
+	let base_dir = file_list.get_base_dir().canonicalize().ok()?;
+	if ctn_file_path.parent() == Some(base_dir.as_path()) {
+		return None;
+	}
+	Some(ctn_file_path)
+}
+
+// Several receipts may be loaded at once, but only a single directory.
+async fn load_files(config: &Config, tx: ExternalEventSender, files: Vec<FileData>) {
+	tracing::info!("File loading: {:?}", files);
+	let paths: Vec<PathBuf> = files.iter().map(|f| f.path()).collect();
+	if paths.iter().any(|p| p.is_file()) {
+		send_event(&tx, ExternalEvent::ReceiptErrorReset);
+	}
+	for path in paths.iter().filter(|p| p.is_file()) {
+		load_receipt(config, tx.clone(), path).await;
+	}
+	if let Some(path) = paths.iter().find(|p| p.is_dir()) {
+		load_directory(config, tx, path).await;
+	}
+}
+
 async fn load_directory(config: &Config, tx: ExternalEventSender, path: &Path) {
 	tracing::info!(
 		"Directory loading async function started: {}",
@@ -195,6 +505,7 @@ async fn load_directory(config: &Config, tx: ExternalEventSender, path: &Path) {
 	);
 	let include_hidden_files = config.include_hidden_files();
 	let include_system_files = config.include_system_files();
+	let config = config.clone();
 	send_event(&tx, ExternalEvent::FileListReset);
 	let handle = Handle::current();
 	let path = path.to_path_buf();
@@ -206,7 +517,21 @@ async fn load_directory(config: &Config, tx: ExternalEventSender, path: &Path) {
 			match NonHashedFileList::from_dir(&path, include_hidden_files, include_system_files)
 				.await
 			{
-				Ok(new_lst) => {
+				Ok(mut new_lst) => {
+					new_lst.load_case(&config);
+					if config.is_metadata_capture_enabled() {
+						new_lst.capture_metadata();
+					}
+					audit::record(AuditAction::DirectoryLoad {
+						path: path.clone(),
+						nb_files: new_lst.len(Some(&config)),
+					});
+					if new_lst.nb_excluded_files() != 0 {
+						audit::record(AuditAction::Exclusion {
+							path: path.clone(),
+							nb_files: new_lst.nb_excluded_files(),
+						});
+					}
 					send_event(&tx, ExternalEvent::NonHashedFileListSet(new_lst));
 				}
 				Err(e) => tracing::error!("Unable to load directory: {}: {e}", path.display()),
@@ -224,7 +549,8 @@ async fn load_receipt(config: &Config, tx: ExternalEventSender, path: &Path) {
 		Some(h) => h,
 		None => config.hash_function,
 	};
-	send_event(&tx, ExternalEvent::ReceiptReset);
+	let parse_mode = config.get_parse_mode();
+	let custom_formats = config.custom_receipt_formats.clone();
 	let handle = Handle::current();
 	let path = path.to_path_buf();
 
@@ -232,11 +558,14 @@ async fn load_receipt(config: &Config, tx: ExternalEventSender, path: &Path) {
 		handle.spawn(async move {
 			tracing::info!("Receipt loading thread started");
 			send_event(&tx, ExternalEvent::LoadingBarAdd);
-			match Receipt::new(&path, default_hash) {
+			match Receipt::new(&path, default_hash, parse_mode, &custom_formats) {
 				Ok(new_receipt) => {
 					send_event(&tx, ExternalEvent::ReceiptSet(new_receipt));
 				}
-				Err(_) => tracing::error!("Unable to load receipt: {}", path.display()),
+				Err(e) => {
+					tracing::error!("Unable to load receipt: {}: {e}", path.display());
+					send_event(&tx, ExternalEvent::ReceiptErrorSet(e));
+				}
 			};
 			send_event(&tx, ExternalEvent::LoadingBarDelete);
 			tracing::info!("Receipt loading thread done");
@@ -245,30 +574,66 @@ async fn load_receipt(config: &Config, tx: ExternalEventSender, path: &Path) {
 	tracing::info!("Receipt loading async function done");
 }
 
+async fn prepare_update(config: &Config, tx: ExternalEventSender, file_list: FileList) {
+	tracing::info!("Content file update async function started");
+	send_event(&tx, ExternalEvent::ContentFileUpdateReset);
+	let config = config.clone();
+
+	if let FileList::NonHashed(file_list) = file_list {
+		thread::spawn(move || {
+			tracing::info!("Content file update thread started");
+			let update = ContentFileUpdate::new(&file_list, &config, tx.clone());
+			if let Err(e) = &update {
+				tracing::error!("Unable to prepare the content file update: {e}");
+			}
+			send_event(&tx, ExternalEvent::ContentFileUpdateSet(update));
+			tracing::info!("Content file update thread done");
+		});
+	}
+
+	tracing::info!("Content file update async function done");
+}
+
 async fn calc_fingerprints(
 	config: &Config,
 	tx: ExternalEventSender,
-	receipt_opt: Option<Receipt>,
+	receipts: Vec<Receipt>,
 	file_list: FileList,
 ) {
 	tracing::info!("File hashing async function started");
-	let hash_func = match &receipt_opt {
-		Some(rcpt) => rcpt.get_main_hashing_function(),
-		None => config.hash_function,
-	};
+	// The files are hashed once using each hashing function required by the receipts, the content
+	// file being written using the first one.
+	let mut hash_funcs: Vec<HashFunc> = Vec::new();
+	for rcpt in &receipts {
+		let hash_func = rcpt.get_main_hashing_function();
+		if !hash_funcs.contains(&hash_func) {
+			hash_funcs.push(hash_func);
+		}
+	}
+	let hash_func = hash_funcs.first().copied().unwrap_or(config.hash_function);
 	let config = config.clone();
 
 	if let FileList::NonHashed(file_list) = file_list {
 		thread::spawn(move || {
 			tracing::info!("File hashing thread started");
 
-			let total_size = file_list.total_size();
+			let total_size = file_list.total_size() * hash_funcs.len().max(1) as u64;
 			send_event(&tx, ExternalEvent::ProgressBarCreate(total_size));
 			tracing::info!("Total size to hash: {total_size} bytes");
 
 			// Calculating fingerprints
 			match file_list.hash(&config, hash_func, tx.clone()) {
 				Ok(mut hashed_file_list) => {
+					let mut other_lists = Vec::new();
+					for other_func in hash_funcs.iter().skip(1) {
+						tracing::info!("Calculating fingerprints using {other_func}");
+						match file_list.fingerprints(&config, *other_func, tx.clone()) {
+							Ok(lst) => other_lists.push((*other_func, lst)),
+							Err(e) => {
+								tracing::error!("Unable to hash files using {other_func}: {e}")
+							}
+						}
+					}
 					send_event(&tx, ExternalEvent::ProgressBarDelete);
 					send_event(&tx, ExternalEvent::LoadingBarAdd);
 
@@ -281,13 +646,28 @@ async fn calc_fingerprints(
 							Some(h) => h,
 							None => config.hash_function,
 						};
-						match Receipt::new(&ctn_file_path, default_hash) {
+						match Receipt::new(
+							&ctn_file_path,
+							default_hash,
+							config.get_parse_mode(),
+							&[],
+						) {
 							Ok(ctn_file) => {
-								match check(
+								for w in ctn_file.get_warnings() {
+									hashed_file_list.push_parse_warning(w.clone());
+								}
+								let report = check(
 									&hashed_file_list,
 									ctn_file.get_file_list(),
 									CheckType::ContentFile,
-								) {
+									&PathMatchingOptions::default(),
+								);
+								audit::record(AuditAction::Check {
+									path: hashed_file_list.get_base_dir().to_path_buf(),
+									reference: ctn_file_path.clone(),
+									nb_errors: report.result.nb_errors(),
+								});
+								match report.result {
 									CheckResult::Ok => hashed_file_list.set_result_ok(),
 									CheckResult::Error(err_lst) => {
 										for e in err_lst {
@@ -298,28 +678,31 @@ async fn calc_fingerprints(
 								}
 							}
 							Err(_) => {
-								hashed_file_list
-									.push_result_error(CheckResultError::ContentFileParseError);
+								let e = if has_invalid_trailer(&ctn_file_path) {
+									CheckResultError::ContentFileTrailerMismatch
+								} else {
+									CheckResultError::ContentFileParseError
+								};
+								hashed_file_list.push_result_error(e);
 							}
 						};
 					}
 
-					// Checking fingerprints against the receipt
-					if let Some(rcpt) = receipt_opt {
-						tracing::info!("Checking fingerprints against the receipt");
-						match check(&hashed_file_list, rcpt.get_file_list(), CheckType::Receipt) {
-							CheckResult::Ok => {
-								if !hashed_file_list.get_result().is_err() {
-									hashed_file_list.set_result_ok()
-								}
-							}
-							CheckResult::Error(err_lst) => {
-								for e in err_lst {
-									hashed_file_list.push_result_error(e);
-								}
-							}
-							CheckResult::None => {}
-						}
+					// Checking fingerprints against the receipts
+					if !receipts.is_empty() {
+						check_receipts(&mut hashed_file_list, hash_func, &other_lists, &receipts);
+					}
+
+					// Looking for known files
+					if !config.known_hash_sets.is_empty() {
+						tracing::info!("Looking for known files");
+						let known_files = KnownFiles::load(&config.known_hash_sets);
+						hashed_file_list.set_known_files(&known_files);
+					}
+
+					// Looking for files received in previous cases
+					if config.is_history_enabled() {
+						update_history(&mut hashed_file_list);
 					}
 
 					send_event(&tx, ExternalEvent::HashedFileListSet(hashed_file_list));
@@ -334,3 +717,78 @@ async fn calc_fingerprints(
 
 	tracing::info!("File hashing async function done");
 }
+
+// Each receipt is checked against the fingerprints calculated using its own hashing function.
+fn check_receipts(
+	hashed_file_list: &mut HashedFileList,
+	hash_func: HashFunc,
+	other_lists: &[(HashFunc, HashedFileList)],
+	receipts: &[Receipt],
+) {
+	for path in find_conflicts(receipts.iter().map(|r| r.get_file_list())) {
+		hashed_file_list.push_result_error(CheckResultError::ReceiptConflict(path));
+	}
+	for rcpt in receipts {
+		tracing::info!("Checking fingerprints against the receipt: {rcpt}");
+		let rcpt_hash_func = rcpt.get_main_hashing_function();
+		let calculated = if rcpt_hash_func == hash_func {
+			&*hashed_file_list
+		} else {
+			match other_lists.iter().find(|(f, _)| *f == rcpt_hash_func) {
+				Some((_, lst)) => lst,
+				None => {
+					tracing::error!("{rcpt}: no fingerprints calculated using {rcpt_hash_func}");
+					hashed_file_list.push_result_error(CheckResultError::ReceiptNotChecked(
+						rcpt.get_path().to_path_buf(),
+					));
+					continue;
+				}
+			}
+		};
+		let report = check(
+			calculated,
+			rcpt.get_file_list(),
+			CheckType::Receipt,
+			rcpt.get_path_matching(),
+		);
+		audit::record(AuditAction::Check {
+			path: hashed_file_list.get_base_dir().to_path_buf(),
+			reference: rcpt.get_path().to_path_buf(),
+			nb_errors: report.result.nb_errors(),
+		});
+		hashed_file_list.add_receipt_coverage(rcpt.get_path(), &report.covered);
+		for r in report.renamed {
+			hashed_file_list.push_renamed_file(r);
+		}
+		match report.result {
+			CheckResult::Ok => {
+				if !hashed_file_list.get_result().is_err() {
+					hashed_file_list.set_result_ok()
+				}
+			}
+			CheckResult::Error(err_lst) => {
+				for e in err_lst {
+					hashed_file_list.push_result_error(e);
+				}
+			}
+			CheckResult::None => {}
+		}
+	}
+}
+
+fn update_history(hashed_file_list: &mut HashedFileList) {
+	let case_id = match hashed_file_list.get_case().get_case_id() {
+		Some(id) => id.to_string(),
+		None => default_case_id(hashed_file_list.get_base_dir()),
+	};
+	tracing::info!("Looking for files received in previous cases, case: {case_id}");
+	match History::open() {
+		Ok(mut history) => {
+			hashed_file_list.set_previous_evidence(&history, &case_id);
+			if let Err(e) = history.record(&case_id, hashed_file_list) {
+				tracing::error!("Unable to update the history database: {e}");
+			}
+		}
+		Err(e) => tracing::error!("Unable to open the history database: {e}"),
+	}
+}
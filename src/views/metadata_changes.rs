@@ -0,0 +1,54 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{Header, MainSection, Root};
+use crate::file_metadata::MetadataField;
+use crate::files::FileList;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+
+#[component]
+pub fn MetadataChanges() -> Element {
+	let changes = use_context::<Signal<FileList>>()().get_metadata_changes();
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_metadata_changes_title") }
+				}
+				p {
+					{ tid!("view_metadata_changes_intro") }
+				}
+				table {
+					class: "view-metadata-changes-list",
+					tr {
+						th { { tid!("view_metadata_changes_file") } }
+						th { { tid!("view_metadata_changes_field") } }
+						th { { tid!("view_metadata_changes_recorded") } }
+						th { { tid!("view_metadata_changes_current") } }
+					}
+					for change in changes {
+						tr {
+							td { "{change.get_path().display()}" }
+							td {
+								match change.get_field() {
+									MetadataField::Modified => tid!("view_metadata_changes_field_modified"),
+									MetadataField::Changed => tid!("view_metadata_changes_field_changed"),
+									MetadataField::Created => tid!("view_metadata_changes_field_created"),
+									MetadataField::Mode => tid!("view_metadata_changes_field_mode"),
+									MetadataField::Owner => tid!("view_metadata_changes_field_owner"),
+									MetadataField::ExtendedAttributes => tid!("view_metadata_changes_field_xattrs"),
+								}
+							}
+							td { "{change.get_recorded()}" }
+							td { "{change.get_current()}" }
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,220 @@
+#![allow(non_snake_case)]
+
+use crate::analyse_hash::analyse_hash;
+use crate::app::Route;
+use crate::components::config::ConfigElement;
+use crate::components::{Button, FileButton, Header, MainSection, Notification, Root};
+use crate::config::Config;
+use crate::files::{FileList, HashedFileList};
+use crate::notifications::NotificationLevel;
+use crate::quick_verify::{find_digest, find_file, normalize_digest, DigestMatch, MatchSource};
+use crate::receipt::Receipt;
+use dioxus::html::{FileData, HasFileData};
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Lookup {
+	Digest(String),
+	File(PathBuf),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum LookupResult {
+	Pending,
+	Matches(Vec<DigestMatch>),
+	InvalidDigest,
+	Io(String),
+}
+
+#[component]
+pub fn QuickVerify() -> Element {
+	let cfg_sig = use_context::<Signal<Config>>();
+	let file_list_sig = use_context::<Signal<FileList>>();
+	let receipts_sig = use_context::<Signal<Vec<Receipt>>>();
+	let mut digest_input = use_signal(String::new);
+	let mut lookup = use_signal(|| None::<Lookup>);
+	let mut result = use_signal(|| None::<LookupResult>);
+	let has_entries = hashed_file_list(&file_list_sig()).is_some() || !receipts_sig().is_empty();
+
+	let mut verify_file = move |files: Vec<FileData>| {
+		let Some(path) = files.first().map(|f| f.path()) else {
+			return;
+		};
+		tracing::info!("Quick verification of file: {}", path.display());
+		lookup.set(Some(Lookup::File(path.clone())));
+		result.set(Some(LookupResult::Pending));
+		let file_list = hashed_file_list(&file_list_sig());
+		let receipts = receipts_sig();
+		let (tx, rx) = tokio::sync::oneshot::channel();
+		std::thread::spawn(move || {
+			let res = match find_file(&path, file_list.as_ref(), &receipts) {
+				Ok(matches) => LookupResult::Matches(matches),
+				Err(e) => {
+					tracing::error!("Unable to hash {}: {e}", path.display());
+					LookupResult::Io(e.to_string())
+				}
+			};
+			let _ = tx.send(res);
+		});
+		spawn(async move {
+			if let Ok(res) = rx.await {
+				result.set(Some(res));
+			}
+		});
+	};
+
+	rsx! {
+		Root {
+			ondrop: move |event: DragEvent| verify_file(event.files()),
+			Header {}
+			MainSection {
+				close_view: Some(Route::Tools {}),
+				h1 {
+					{ tid!("view_quick_verify_title") }
+				}
+				p {
+					{ tid!("view_quick_verify_intro") }
+				}
+				if !has_entries {
+					Notification {
+						id: "view-quick-verify-no-entries",
+						level: NotificationLevel::Warning,
+						title: tid!("view_quick_verify_title"),
+						p { { tid!("view_quick_verify_no_entries") } }
+					}
+				}
+				FileButton {
+					icon: "ri-file-search-line",
+					accept: "",
+					multiple: false,
+					directory: false,
+					name: "view-quick-verify-btn-select-file",
+					onchange: move |event: FormEvent| verify_file(event.files()),
+					{ tid!("view_quick_verify_select_file") }
+				}
+				form {
+					ConfigElement {
+						id: "view_quick_verify_digest",
+						label: tid!("view_quick_verify_digest"),
+						input {
+							id: "view_quick_verify_digest",
+							name: "view_quick_verify_digest",
+							class: "view-quick-verify-digest",
+							value: "{digest_input}",
+							oninput: move |event| digest_input.set(event.value()),
+						}
+					}
+				}
+				Button {
+					icon: "ri-search-line",
+					onclick: move |_event| {
+						let input = digest_input();
+						lookup.set(Some(Lookup::Digest(input.clone())));
+						let res = match normalize_digest(&input) {
+							Some(digest) => {
+								let file_list = hashed_file_list(&file_list_sig());
+								LookupResult::Matches(find_digest(&digest, None, file_list.as_ref(), &receipts_sig()))
+							}
+							None => LookupResult::InvalidDigest,
+						};
+						result.set(Some(res));
+					},
+					{ tid!("view_quick_verify_search") }
+				}
+				if let Some(lookup) = lookup() {
+					div {
+						class: "view-quick-verify-lookup",
+						match lookup {
+							Lookup::File(path) => rsx! {
+								p { { tid!("view_quick_verify_lookup_file", path: path.display().to_string()) } }
+							},
+							Lookup::Digest(input) => match normalize_digest(&input) {
+								Some(digest) => rsx! {
+									p { { tid!("view_quick_verify_lookup_digest", digest: digest.clone()) } }
+									p { { tid!("view_quick_verify_detected_algorithm", algorithm: analyse_hash(&digest, cfg_sig().hash_function).to_string()) } }
+								},
+								None => rsx! {},
+							},
+						}
+					}
+				}
+				match result() {
+					Some(LookupResult::Pending) => rsx! {
+						p { { tid!("view_quick_verify_pending") } }
+					},
+					Some(LookupResult::Matches(matches)) => rsx! {
+						MatchList { matches: matches }
+					},
+					Some(LookupResult::InvalidDigest) => rsx! {
+						Notification {
+							id: "view-quick-verify-invalid-digest",
+							level: NotificationLevel::Error,
+							title: tid!("view_quick_verify_title"),
+							p { { tid!("view_quick_verify_invalid_digest") } }
+						}
+					},
+					Some(LookupResult::Io(msg)) => rsx! {
+						Notification {
+							id: "view-quick-verify-io-err",
+							level: NotificationLevel::Error,
+							title: tid!("view_quick_verify_title"),
+							p { { tid!("view_quick_verify_io_err", msg: msg) } }
+						}
+					},
+					None => rsx! {},
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn MatchList(matches: Vec<DigestMatch>) -> Element {
+	if matches.is_empty() {
+		return rsx! {
+			Notification {
+				id: "view-quick-verify-no-match",
+				level: NotificationLevel::Warning,
+				title: tid!("view_quick_verify_title"),
+				p { { tid!("view_quick_verify_no_match") } }
+			}
+		};
+	}
+	rsx! {
+		Notification {
+			id: "view-quick-verify-match",
+			level: NotificationLevel::Success,
+			title: tid!("view_quick_verify_title"),
+			p { { tid!("view_quick_verify_match", nb: matches.len()) } }
+		}
+		table {
+			class: "view-quick-verify-matches",
+			tr {
+				th { { tid!("view_quick_verify_match_path") } }
+				th { { tid!("view_quick_verify_match_source") } }
+				th { { tid!("view_quick_verify_match_algorithm") } }
+			}
+			for m in matches {
+				tr {
+					td { "{m.get_file().get_relative_path().display()}" }
+					td {
+						match m.get_source() {
+							MatchSource::Evidence => tid!("view_quick_verify_source_evidence"),
+							MatchSource::Receipt(path) => tid!("view_quick_verify_source_receipt", path: path.display().to_string()),
+						}
+					}
+					td { "{m.get_file().get_hash_func()}" }
+				}
+			}
+		}
+	}
+}
+
+fn hashed_file_list(file_list: &FileList) -> Option<HashedFileList> {
+	match file_list {
+		FileList::Hashed(lst) => Some(lst.clone()),
+		_ => None,
+	}
+}
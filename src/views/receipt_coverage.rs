@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{Header, MainSection, Root};
+use crate::files::FileList;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+use std::path::PathBuf;
+
+#[component]
+pub fn ReceiptCoverage() -> Element {
+	let file_list = use_context::<Signal<FileList>>()();
+	let FileList::Hashed(lst) = file_list else {
+		return rsx! {};
+	};
+	let Some(coverage) = lst.get_receipt_coverage() else {
+		return rsx! {};
+	};
+	let mut uncovered: Vec<PathBuf> = lst
+		.get_files()
+		.map(|f| f.get_relative_path().to_path_buf())
+		.filter(|p| !coverage.contains_key(p))
+		.collect();
+	uncovered.sort();
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_receipt_coverage_title") }
+				}
+				if !uncovered.is_empty() {
+					h2 {
+						{ tid!("view_receipt_coverage_uncovered", nb: uncovered.len()) }
+					}
+					ul {
+						class: "view-receipt-coverage-uncovered",
+						for path in uncovered {
+							li { "{path.display()}" }
+						}
+					}
+				}
+				h2 {
+					{ tid!("view_receipt_coverage_covered", nb: coverage.len()) }
+				}
+				dl {
+					class: "view-receipt-coverage-covered",
+					for (path, receipts) in coverage {
+						dt { "{path.display()}" }
+						for rcpt in receipts {
+							dd {
+								"{rcpt.file_name().unwrap_or_default().to_string_lossy()}"
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,59 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{Button, Header, MainSection, Root};
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+
+#[component]
+pub fn Tools() -> Element {
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_tools_title") }
+				}
+				div {
+					class: "view-tools-list",
+					Button {
+						icon: "ri-file-transfer-line",
+						onclick: move |_event| {
+							navigator().push(Route::ConvertContentFile {});
+						},
+						{ tid!("view_tools_convert_ctn_file") }
+					}
+					Button {
+						icon: "ri-file-search-line",
+						onclick: move |_event| {
+							navigator().push(Route::QuickVerify {});
+						},
+						{ tid!("view_tools_quick_verify") }
+					}
+					Button {
+						icon: "ri-git-compare-line",
+						onclick: move |_event| {
+							navigator().push(Route::Compare {});
+						},
+						{ tid!("view_tools_compare") }
+					}
+					Button {
+						icon: "ri-file-copy-2-line",
+						onclick: move |_event| {
+							navigator().push(Route::CopyAndHash {});
+						},
+						{ tid!("view_tools_copy") }
+					}
+					Button {
+						icon: "ri-file-shield-2-line",
+						onclick: move |_event| {
+							navigator().push(Route::AuditLog {});
+						},
+						{ tid!("view_tools_audit_log") }
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,122 @@
+#![allow(non_snake_case)]
+
+use crate::app::Route;
+use crate::components::{Button, Header, LoadingBar, MainSection, Notification, ProgressBar, Root};
+use crate::config::Config;
+use crate::content_file_update::{ContentFileUpdate, UpdateError};
+use crate::events::{send_event, ExternalEvent, ExternalEventSender};
+use crate::notifications::NotificationLevel;
+use dioxus::prelude::*;
+use dioxus_i18n::tid;
+
+#[component]
+pub fn UpdateContentFile() -> Element {
+	let config_sig = use_context::<Signal<Config>>();
+	let tx_sig = use_context::<Signal<ExternalEventSender>>();
+	let update_sig = use_context::<Signal<Option<Result<ContentFileUpdate, UpdateError>>>>();
+	let mut apply_err = use_signal(|| None::<UpdateError>);
+
+	rsx! {
+		Root {
+			Header {}
+			MainSection {
+				close_view: Some(Route::Main {}),
+				h1 {
+					{ tid!("view_update_title") }
+				}
+				ProgressBar {}
+				LoadingBar {}
+				if let Some(Ok(update)) = update_sig() {
+					if update.has_changes() {
+						p {
+							{ tid!("view_update_intro") }
+						}
+						FileSet {
+							title: tid!("view_update_added"),
+							class: "view-update-added",
+							files: update.get_added().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+						}
+						FileSet {
+							title: tid!("view_update_modified"),
+							class: "view-update-modified",
+							files: update.get_modified().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+						}
+						FileSet {
+							title: tid!("view_update_removed"),
+							class: "view-update-removed",
+							files: update.get_removed().iter().map(|p| p.display().to_string()).collect::<Vec<String>>(),
+						}
+						Button {
+							icon: "ri-file-edit-line",
+							onclick: move |_event| {
+								if let Some(Ok(update)) = update_sig() {
+									match update.apply(&config_sig()) {
+										Ok((lst, archive_path)) => {
+											tracing::info!("Content file updated, previous version archived as {}", archive_path.display());
+											send_event(&tx_sig(), ExternalEvent::ContentFileUpdateReset);
+											send_event(&tx_sig(), ExternalEvent::HashedFileListSet(lst));
+											navigator().push(Route::Main {});
+										}
+										Err(e) => {
+											tracing::error!("Unable to update the content file: {e}");
+											apply_err.set(Some(e));
+										}
+									}
+								}
+							},
+							{ tid!("view_update_confirm") }
+						}
+					} else {
+						Notification {
+							id: "view-update-no-change",
+							level: NotificationLevel::Info,
+							title: tid!("view_update_title"),
+							p { { tid!("view_update_no_change") } }
+						}
+					}
+				}
+				if let Some(Err(e)) = update_sig() {
+					UpdateErrorNotification { error: e }
+				}
+				if let Some(e) = apply_err() {
+					UpdateErrorNotification { error: e }
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn FileSet(title: String, class: String, files: Vec<String>) -> Element {
+	rsx! {
+		if !files.is_empty() {
+			div {
+				class: "view-update-file-set {class}",
+				h2 { "{title}" }
+				ul {
+					for f in files {
+						li { "{f}" }
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn UpdateErrorNotification(error: UpdateError) -> Element {
+	rsx! {
+		Notification {
+			id: "view-update-err-{error}",
+			level: NotificationLevel::Error,
+			title: tid!("view_update_title"),
+			p {
+				match error {
+					UpdateError::ArchiveExists(p) => tid!("view_update_err_archive_exists", path: p.display().to_string()),
+					UpdateError::Io(msg) => tid!("view_update_err_io", msg: msg),
+					UpdateError::Parse => tid!("view_update_err_parse"),
+				}
+			}
+		}
+	}
+}